@@ -2,17 +2,60 @@ pub mod tui;
 
 use anyhow::Result;
 use tui::app::ViewType;
-use tui::bridge::default_service_bundle;
+use tui::bridge::service_bundle;
+use tui::deep_link::DeepLink;
+use tui::preferences::PreferencesOverrides;
+use tui::session::SessionStore;
+use tui::snapshot::SnapshotFormat;
 
-pub fn run_tui(initial_view: ViewType, protocol_url: Option<&str>) -> Result<()> {
+fn set_protocol_url(protocol_url: Option<&str>) {
     if let Some(url) = protocol_url {
         unsafe {
             std::env::set_var("DORA_PROTOCOL_URL", url);
         }
     }
+}
+
+/// Run the TUI. `deep_link` opens the view it names; without one, and with
+/// `restore_session`, the views of the last session are reopened. A deep
+/// link that does not resolve fails before the terminal is touched.
+pub fn run_tui(
+    deep_link: Option<DeepLink>,
+    protocol_url: Option<&str>,
+    preferences: PreferencesOverrides,
+    restore_session: bool,
+) -> Result<()> {
+    tui::crash::install_panic_hook();
+    set_protocol_url(protocol_url);
 
-    let bundle = default_service_bundle();
-    let mut app = tui::app::DoraApp::from_service_bundle(initial_view, bundle);
+    let bundle = service_bundle(preferences).map_err(anyhow::Error::msg)?;
+    let linked_view = match &deep_link {
+        Some(link) => {
+            let dataflows = if link.needs_dataflows() {
+                bundle.coordinator_client.list_dataflows().map_err(|err| {
+                    anyhow::anyhow!("could not look up dataflows for --dataflow: {err}")
+                })?
+            } else {
+                Vec::new()
+            };
+            Some(
+                link.resolve(&dataflows)
+                    .map_err(|err| anyhow::anyhow!("--view {}: {err}", link.view))?,
+            )
+        }
+        None => None,
+    };
+
+    let mut app = tui::app::DoraApp::from_service_bundle(ViewType::Dashboard, bundle);
+    if let Some(path) = SessionStore::default_path() {
+        app = app.with_session(
+            SessionStore::new(path),
+            restore_session && deep_link.is_none(),
+        );
+    }
+    if let Some(view) = linked_view {
+        app = app.with_deep_link(view);
+    }
 
     let runtime = tokio::runtime::Runtime::new()?;
     runtime.block_on(async move {
@@ -22,3 +65,23 @@ pub fn run_tui(initial_view: ViewType, protocol_url: Option<&str>) -> Result<()>
     })?;
     Ok(())
 }
+
+/// Print the dataflows and system metrics to stdout without entering the
+/// TUI. Fails after printing if any of the data could not be fetched.
+pub fn run_snapshot(protocol_url: Option<&str>, format: SnapshotFormat) -> Result<()> {
+    set_protocol_url(protocol_url);
+
+    let bundle = service_bundle(PreferencesOverrides::default()).map_err(anyhow::Error::msg)?;
+    let mut app = tui::app::DoraApp::from_service_bundle(ViewType::Dashboard, bundle);
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let snapshot = runtime
+        .block_on(app.snapshot())
+        .map_err(|err| anyhow::Error::msg(err.to_string()))?;
+
+    println!("{}", snapshot.render(format).trim_end());
+    if !snapshot.errors.is_empty() {
+        anyhow::bail!("snapshot is incomplete: {}", snapshot.errors.join("; "));
+    }
+    Ok(())
+}
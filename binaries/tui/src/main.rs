@@ -1,28 +1,100 @@
 use anyhow::Result;
-use clap::Parser;
-use dora_tui::{run_tui, tui::ViewType};
+use clap::{Parser, Subcommand};
+use dora_tui::{
+    run_snapshot, run_tui,
+    tui::{deep_link::DeepLink, preferences::PreferencesOverrides, snapshot::SnapshotFormat},
+};
 
 #[derive(Debug, Parser)]
 #[command(name = "dora-tui", version, about = "Dora Terminal UI")]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Override the protocol gateway URL (default: http://127.0.0.1:7267)
-    #[arg(long, env = "DORA_PROTOCOL_URL")]
+    #[arg(long, env = "DORA_PROTOCOL_URL", global = true)]
     protocol_url: Option<String>,
 
-    /// Initial view (dashboard, logs, etc.)
-    #[arg(long, default_value = "dashboard")]
-    view: String,
+    /// Open this view (dashboard, explorer, logs, inspector, etc.) instead of
+    /// the saved default view or the last session
+    #[arg(long)]
+    view: Option<String>,
+
+    /// Dataflow name or id for --view inspector, logs, explorer or debug
+    #[arg(long, requires = "view")]
+    dataflow: Option<String>,
+
+    /// Node id for --view inspector or logs
+    #[arg(long, requires = "view")]
+    node: Option<String>,
+
+    /// Least severe log level shown by --view logs (error, warn, info, debug,
+    /// trace)
+    #[arg(long, requires = "view")]
+    level: Option<String>,
+
+    /// Tab opened by --view explorer or inspector
+    #[arg(long, requires = "view")]
+    tab: Option<String>,
+
+    /// Recording id for --view recording
+    #[arg(long, requires = "view")]
+    recording: Option<String>,
+
+    /// Color theme for this session; overrides saved preferences
+    #[arg(long)]
+    theme: Option<String>,
+
+    /// Refresh interval in seconds; overrides saved preferences
+    #[arg(long, value_name = "SECS")]
+    refresh_interval: Option<u64>,
+
+    /// ASCII output, the high-contrast theme and no blinking; overrides
+    /// saved preferences
+    #[arg(long)]
+    accessible: bool,
+
+    /// Start from the initial view instead of restoring the last session
+    #[arg(long)]
+    fresh: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the current dataflows and system metrics and exit
+    Snapshot {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = SnapshotFormat::Table)]
+        format: SnapshotFormat,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let view = match cli.view.as_str() {
-        "logs" => ViewType::LogViewer {
-            target: "system".to_string(),
-        },
-        "monitor" => ViewType::SystemMonitor,
-        _ => ViewType::Dashboard,
+    if let Some(Command::Snapshot { format }) = cli.command {
+        return run_snapshot(cli.protocol_url.as_deref(), format);
+    }
+
+    let preferences = PreferencesOverrides {
+        theme: cli.theme,
+        auto_refresh_interval_secs: cli.refresh_interval,
+        accessible: cli.accessible.then_some(true),
+        ..Default::default()
     };
 
-    run_tui(view, cli.protocol_url.as_deref())
+    let deep_link = cli.view.map(|view| DeepLink {
+        view,
+        dataflow: cli.dataflow,
+        node: cli.node,
+        level: cli.level,
+        tab: cli.tab,
+        recording: cli.recording,
+    });
+
+    run_tui(
+        deep_link,
+        cli.protocol_url.as_deref(),
+        preferences,
+        !cli.fresh,
+    )
 }
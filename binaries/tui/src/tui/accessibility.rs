@@ -0,0 +1,220 @@
+//! Accessibility mode.
+//!
+//! Views draw emoji and colored symbols freely. Rather than teach every view
+//! about screen readers and monochrome terminals, the finished frame is
+//! rewritten before it is flushed: emoji become ASCII, blinking stops, and
+//! with `NO_COLOR` every color is dropped. Cells that were only told apart
+//! by their background, such as the selected row, are shown reversed
+//! instead.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Glyphs replaced in ASCII output. Anything else outside ASCII that is an
+/// emoji is blanked; arrows, box drawing and the like are kept.
+const ASCII_GLYPHS: &[(&str, &str)] = &[
+    // Status markers
+    ("●", "*"),
+    ("○", "o"),
+    ("◐", "~"),
+    ("▲", "!"),
+    ("■", "#"),
+    ("✓", "v"),
+    ("✗", "x"),
+    ("🟢", "+"),
+    ("🔴", "x"),
+    ("🟡", "!"),
+    ("🟠", "!"),
+    ("🔵", "o"),
+    ("🟣", "o"),
+    ("⚫", "-"),
+    ("⚪", "o"),
+    // Message prefixes
+    ("✅", "ok"),
+    ("❌", "x"),
+    ("⚠", "!"),
+    ("⚠\u{fe0f}", "!"),
+    ("ℹ", "i"),
+    ("ℹ\u{fe0f}", "i"),
+    ("❓", "?"),
+    ("⚡", "!"),
+    ("🔥", "!"),
+    ("⭐", "*"),
+    // Bullets and controls
+    ("•", "*"),
+    ("◆", "*"),
+    ("◾", "#"),
+    ("▶", ">"),
+    ("▼", "v"),
+    ("⏸", "="),
+    ("⏹", "#"),
+];
+
+/// How the finished frame is adjusted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputMode {
+    /// Replace emoji with ASCII and stop blinking
+    pub ascii: bool,
+    /// Drop all colors
+    pub monochrome: bool,
+}
+
+impl OutputMode {
+    /// ASCII output if `accessible`; monochrome if `NO_COLOR` is set.
+    pub fn new(accessible: bool) -> Self {
+        Self::from_vars(accessible, |name| std::env::var(name).ok())
+    }
+
+    fn from_vars(accessible: bool, var: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            ascii: accessible,
+            // https://no-color.org: set and not empty.
+            monochrome: var("NO_COLOR").is_some_and(|value| !value.is_empty()),
+        }
+    }
+
+    pub fn is_plain(self) -> bool {
+        self == Self::default()
+    }
+
+    /// Rewrite `buffer` in place. `background` is the theme's background,
+    /// which is not worth reversing.
+    pub fn apply(self, buffer: &mut Buffer, background: Color) {
+        if self.is_plain() {
+            return;
+        }
+        let area = buffer.area;
+        for y in area.top()..area.bottom() {
+            let mut x = area.left();
+            while x < area.right() {
+                let width = buffer.get(x, y).symbol().width().max(1) as u16;
+                if self.monochrome {
+                    for offset in 0..width.min(area.right() - x) {
+                        monochrome(buffer, x + offset, y, background);
+                    }
+                }
+                if self.ascii {
+                    asciify(buffer, x, y, width);
+                }
+                x += width;
+            }
+        }
+    }
+}
+
+fn monochrome(buffer: &mut Buffer, x: u16, y: u16, background: Color) {
+    let cell = buffer.get_mut(x, y);
+    if !matches!(cell.bg, Color::Reset) && cell.bg != background {
+        cell.modifier.insert(Modifier::REVERSED);
+    }
+    cell.fg = Color::Reset;
+    cell.bg = Color::Reset;
+    cell.underline_color = Color::Reset;
+}
+
+/// Replace the grapheme at `x`, which covers `width` cells, and blank the
+/// cells it hid.
+fn asciify(buffer: &mut Buffer, x: u16, y: u16, width: u16) {
+    let cell = buffer.get_mut(x, y);
+    cell.modifier
+        .remove(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK);
+    let symbol = cell.symbol();
+    if symbol.is_ascii() {
+        return;
+    }
+    let replacement = match ASCII_GLYPHS.iter().find(|(glyph, _)| *glyph == symbol) {
+        Some((_, ascii)) => *ascii,
+        None if is_emoji(symbol) => "",
+        None => return,
+    };
+
+    let style = cell.style();
+    let right = buffer.area.right();
+    let mut chars = replacement.chars();
+    for offset in 0..width.min(right - x) {
+        let cell = buffer.get_mut(x + offset, y);
+        cell.set_char(chars.next().unwrap_or(' ')).set_style(style);
+    }
+}
+
+fn is_emoji(symbol: &str) -> bool {
+    symbol
+        .chars()
+        .any(|c| matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        style::{Style, Stylize},
+    };
+
+    fn row(buffer: &Buffer) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer.get(x, 0).symbol())
+            .collect()
+    }
+
+    #[test]
+    fn no_color_must_be_non_empty() {
+        let mode = |value: Option<&str>| {
+            OutputMode::from_vars(false, |_| value.map(str::to_string)).monochrome
+        };
+        assert!(!mode(None));
+        assert!(!mode(Some("")));
+        assert!(mode(Some("1")));
+    }
+
+    #[test]
+    fn emoji_become_ascii_without_shifting_text() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 1));
+        buffer.set_string(0, 0, "🚀 Dora ✅ ● ⚠️ ▶", Style::default().green());
+        buffer.set_string(18, 0, "_", Style::default().slow_blink());
+        let plain = buffer.clone();
+
+        OutputMode::default().apply(&mut buffer, Color::Reset);
+        assert_eq!(buffer, plain);
+
+        let mode = OutputMode {
+            ascii: true,
+            monochrome: false,
+        };
+        mode.apply(&mut buffer, Color::Reset);
+        assert_eq!(row(&buffer), "   Dora ok * !  > _ ");
+        assert_eq!(buffer.get(8, 0).fg, Color::Green);
+        assert!(!buffer.get(18, 0).modifier.contains(Modifier::SLOW_BLINK));
+    }
+
+    #[test]
+    fn monochrome_reverses_highlighted_cells() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(0, 0, "ab", Style::default().fg(Color::Red).bg(Color::Black));
+        buffer.set_string(
+            2,
+            0,
+            "cd",
+            Style::default().fg(Color::Black).bg(Color::White).bold(),
+        );
+
+        let mode = OutputMode {
+            ascii: false,
+            monochrome: true,
+        };
+        mode.apply(&mut buffer, Color::Black);
+        let plain = buffer.get(0, 0);
+        assert_eq!((plain.fg, plain.bg), (Color::Reset, Color::Reset));
+        assert!(!plain.modifier.contains(Modifier::REVERSED));
+        let selected = buffer.get(2, 0);
+        assert_eq!((selected.fg, selected.bg), (Color::Reset, Color::Reset));
+        assert!(
+            selected
+                .modifier
+                .contains(Modifier::REVERSED | Modifier::BOLD)
+        );
+    }
+}
@@ -0,0 +1,694 @@
+//! Threshold alerts.
+//!
+//! Rules compare system and node metrics against thresholds, optionally for
+//! a while ("CPU above 90% for 30s"), or watch for dataflows and nodes
+//! entering a status ("any node failed"). The app evaluates them on every
+//! metrics update and dataflow refresh. A rule that keeps firing stays one
+//! alert, and the alert is resolved once the rule stops firing for its
+//! subject.
+//!
+//! Rules are read from `alerts.toml` in the dora config dir, which replaces
+//! the default rules:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "cpu"
+//! metric = "cpu"
+//! above = 90
+//! for_secs = 30
+//! severity = "critical"
+//!
+//! [[rule]]
+//! name = "node failed"
+//! node_status = "failed"
+//! severity = "error"
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use tui_interface::{DataflowStatus, NodeStatus};
+
+use crate::tui::{
+    app::{AppState, DataflowInfo},
+    views::{Alert, AlertLevel},
+};
+
+const ALERTS_FILE: &str = "alerts.toml";
+
+/// Node samples older than this are not evaluated; they are only refreshed
+/// while the node is inspected.
+const NODE_SAMPLE_TTL: Duration = Duration::from_secs(30);
+
+/// A value a threshold rule can watch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Cpu,
+    Memory,
+    Disk,
+    NodeCpu,
+    NodeMemory,
+    NodeLatency,
+    NodeMessageRate,
+}
+
+impl Metric {
+    const ALL: [Metric; 7] = [
+        Self::Cpu,
+        Self::Memory,
+        Self::Disk,
+        Self::NodeCpu,
+        Self::NodeMemory,
+        Self::NodeLatency,
+        Self::NodeMessageRate,
+    ];
+
+    /// Name in the rules file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::NodeCpu => "node_cpu",
+            Self::NodeMemory => "node_memory",
+            Self::NodeLatency => "node_latency",
+            Self::NodeMessageRate => "node_message_rate",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cpu | Self::NodeCpu => "CPU",
+            Self::Memory | Self::NodeMemory => "memory",
+            Self::Disk => "disk",
+            Self::NodeLatency => "latency",
+            Self::NodeMessageRate => "message rate",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Self::NodeLatency => "ms",
+            Self::NodeMessageRate => " msg/s",
+            _ => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Above(Metric, f64),
+    Below(Metric, f64),
+    NodeStatus(NodeStatus),
+    DataflowStatus(DataflowStatus),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub name: String,
+    pub condition: Condition,
+    /// How long the condition must hold before the alert is raised
+    pub duration: Duration,
+    pub level: AlertLevel,
+}
+
+impl AlertRule {
+    fn new(name: &str, condition: Condition, secs: u64, level: AlertLevel) -> Self {
+        Self {
+            name: name.to_string(),
+            condition,
+            duration: Duration::from_secs(secs),
+            level,
+        }
+    }
+
+    /// Rules used without an alerts file.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(
+                "cpu",
+                Condition::Above(Metric::Cpu, 90.0),
+                30,
+                AlertLevel::Critical,
+            ),
+            Self::new(
+                "memory",
+                Condition::Above(Metric::Memory, 90.0),
+                30,
+                AlertLevel::Warning,
+            ),
+            Self::new(
+                "disk",
+                Condition::Above(Metric::Disk, 95.0),
+                0,
+                AlertLevel::Warning,
+            ),
+            Self::new(
+                "dataflow failed",
+                Condition::DataflowStatus(DataflowStatus::Failed),
+                0,
+                AlertLevel::Critical,
+            ),
+            Self::new(
+                "node failed",
+                Condition::NodeStatus(NodeStatus::Failed),
+                0,
+                AlertLevel::Error,
+            ),
+        ]
+    }
+
+    fn watches_metrics(&self) -> bool {
+        matches!(self.condition, Condition::Above(..) | Condition::Below(..))
+    }
+}
+
+/// A rule as written in the file; which fields are set decides the kind.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    name: String,
+    metric: Option<String>,
+    above: Option<f64>,
+    below: Option<f64>,
+    node_status: Option<String>,
+    dataflow_status: Option<String>,
+    #[serde(default)]
+    for_secs: u64,
+    #[serde(default)]
+    severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRules {
+    #[serde(default)]
+    rule: Vec<RawRule>,
+}
+
+impl TryFrom<RawRule> for AlertRule {
+    type Error = String;
+
+    fn try_from(raw: RawRule) -> Result<Self, String> {
+        let name = raw.name;
+        let level = match raw.severity.as_deref().unwrap_or("warning") {
+            "info" => AlertLevel::Info,
+            "warning" => AlertLevel::Warning,
+            "error" => AlertLevel::Error,
+            "critical" => AlertLevel::Critical,
+            other => {
+                return Err(format!(
+                    "rule `{name}`: unknown severity `{other}`; expected info, warning, error \
+                     or critical"
+                ));
+            }
+        };
+        let condition = match (raw.metric, raw.node_status, raw.dataflow_status) {
+            (Some(metric), None, None) => {
+                let metric = Metric::ALL
+                    .into_iter()
+                    .find(|candidate| candidate.name() == metric)
+                    .ok_or_else(|| {
+                        format!(
+                            "rule `{name}`: unknown metric `{metric}`; expected one of: {}",
+                            Metric::ALL.map(Metric::name).join(", ")
+                        )
+                    })?;
+                match (raw.above, raw.below) {
+                    (Some(value), None) => Condition::Above(metric, value),
+                    (None, Some(value)) => Condition::Below(metric, value),
+                    _ => {
+                        return Err(format!(
+                            "rule `{name}`: a metric rule needs either `above` or `below`"
+                        ));
+                    }
+                }
+            }
+            (None, Some(status), None) => match NodeStatus::from(status.as_str()) {
+                NodeStatus::Unknown if status != "unknown" => {
+                    return Err(format!("rule `{name}`: unknown node status `{status}`"));
+                }
+                status => Condition::NodeStatus(status),
+            },
+            (None, None, Some(status)) => match DataflowStatus::from(status.as_str()) {
+                DataflowStatus::Unknown if status != "unknown" => {
+                    return Err(format!("rule `{name}`: unknown dataflow status `{status}`"));
+                }
+                status => Condition::DataflowStatus(status),
+            },
+            _ => {
+                return Err(format!(
+                    "rule `{name}`: set exactly one of `metric`, `node_status` and \
+                     `dataflow_status`"
+                ));
+            }
+        };
+        let has_threshold = raw.above.is_some() || raw.below.is_some();
+        if has_threshold && !matches!(condition, Condition::Above(..) | Condition::Below(..)) {
+            return Err(format!(
+                "rule `{name}`: `above` and `below` need a `metric`"
+            ));
+        }
+        Ok(Self {
+            name,
+            condition,
+            duration: Duration::from_secs(raw.for_secs),
+            level,
+        })
+    }
+}
+
+/// A metric value observed for a subject.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// Empty for system metrics, otherwise `node <id> of <dataflow>`
+    pub subject: String,
+    pub metric: Metric,
+    pub value: f64,
+}
+
+/// The readings the rules can look at in `state`.
+pub fn readings(state: &AppState) -> Vec<Reading> {
+    let metrics = &state.system_metrics;
+    let mut readings = vec![
+        Reading {
+            subject: String::new(),
+            metric: Metric::Cpu,
+            value: metrics.cpu_usage as f64,
+        },
+        Reading {
+            subject: String::new(),
+            metric: Metric::Memory,
+            value: metrics.memory_usage as f64,
+        },
+        Reading {
+            subject: String::new(),
+            metric: Metric::Disk,
+            value: metrics.disk.usage_percent as f64,
+        },
+    ];
+    for dataflow in &state.dataflows {
+        for node in &dataflow.nodes {
+            let Some(sample) = state.node_telemetry_sample(&dataflow.id, &node.id) else {
+                continue;
+            };
+            if sample.last_updated.elapsed() > NODE_SAMPLE_TTL {
+                continue;
+            }
+            let subject = format!("node {} of {}", node.id, dataflow.name);
+            let current = &sample.current;
+            readings.extend(
+                [
+                    (Metric::NodeCpu, current.cpu_percent),
+                    (Metric::NodeMemory, current.memory_percent),
+                    (Metric::NodeLatency, current.processing_latency_ms),
+                    (Metric::NodeMessageRate, current.message_rate),
+                ]
+                .map(|(metric, value)| Reading {
+                    subject: subject.clone(),
+                    metric,
+                    value,
+                }),
+            );
+        }
+    }
+    readings
+}
+
+/// A change worth telling the user about.
+#[derive(Debug, Clone)]
+pub enum AlertEvent {
+    Raised(Alert),
+    Resolved(Alert),
+}
+
+/// Which rule fired for what.
+type AlertKey = (usize, String);
+
+#[derive(Debug, Clone)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    /// When each firing condition started holding, until it is raised
+    pending: HashMap<AlertKey, Instant>,
+    active: BTreeMap<AlertKey, Alert>,
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self::new(AlertRule::defaults())
+    }
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            pending: HashMap::new(),
+            active: BTreeMap::new(),
+        }
+    }
+
+    /// `<config dir>/dora/alerts.toml`, if the platform has a config dir.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("dora").join(ALERTS_FILE))
+    }
+
+    /// The rules in the file at `path`, or the default rules if there is
+    /// none. Rules that do not parse are skipped and reported.
+    pub fn load(path: Option<&Path>) -> (Self, Vec<String>) {
+        let Some(path) = path else {
+            return (Self::default(), Vec::new());
+        };
+        let (rules, problems) = match std::fs::read_to_string(path) {
+            Ok(contents) => match Self::parse(&contents) {
+                Ok(parsed) => parsed,
+                Err(err) => (AlertRule::defaults(), vec![err]),
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                (AlertRule::defaults(), Vec::new())
+            }
+            Err(err) => (AlertRule::defaults(), vec![err.to_string()]),
+        };
+        let problems = problems
+            .into_iter()
+            .map(|problem| format!("{}: {problem}", path.display()))
+            .collect();
+        (Self::new(rules), problems)
+    }
+
+    fn parse(contents: &str) -> Result<(Vec<AlertRule>, Vec<String>), String> {
+        let raw: RawRules = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let mut rules = Vec::new();
+        let mut problems = Vec::new();
+        for rule in raw.rule {
+            match AlertRule::try_from(rule) {
+                Ok(rule) => rules.push(rule),
+                Err(problem) => problems.push(problem),
+            }
+        }
+        Ok((rules, problems))
+    }
+
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// Raised alerts that are not resolved, most severe first.
+    pub fn active(&self) -> Vec<&Alert> {
+        let mut active: Vec<_> = self.active.values().collect();
+        active.sort_by(|a, b| b.level.cmp(&a.level).then(a.timestamp.cmp(&b.timestamp)));
+        active
+    }
+
+    pub fn acknowledge_all(&mut self) {
+        for alert in self.active.values_mut() {
+            alert.acknowledged = true;
+        }
+    }
+
+    /// Evaluate the metric rules against `readings`.
+    pub fn evaluate_metrics(&mut self, readings: &[Reading], now: Instant) -> Vec<AlertEvent> {
+        let mut firing = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            let (metric, threshold, above) = match rule.condition {
+                Condition::Above(metric, threshold) => (metric, threshold, true),
+                Condition::Below(metric, threshold) => (metric, threshold, false),
+                _ => continue,
+            };
+            for reading in readings.iter().filter(|reading| reading.metric == metric) {
+                let fires = if above {
+                    reading.value > threshold
+                } else {
+                    reading.value < threshold
+                };
+                if !fires {
+                    continue;
+                }
+                let subject = if reading.subject.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", reading.subject)
+                };
+                let message = format!(
+                    "{subject}{} at {:.1}{unit}, {} {threshold:.1}{unit}",
+                    metric.label(),
+                    reading.value,
+                    if above { "above" } else { "below" },
+                    unit = metric.unit(),
+                );
+                firing.push(((index, reading.subject.clone()), message));
+            }
+        }
+        self.update(RuleKind::Metrics, firing, now)
+    }
+
+    /// Evaluate the status rules against `dataflows`.
+    pub fn evaluate_dataflows(
+        &mut self,
+        dataflows: &[DataflowInfo],
+        now: Instant,
+    ) -> Vec<AlertEvent> {
+        let mut firing = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            match rule.condition {
+                Condition::DataflowStatus(status) => {
+                    for dataflow in dataflows.iter().filter(|df| df.status == status) {
+                        firing.push((
+                            (index, dataflow.id.clone()),
+                            format!("dataflow {} is {status}", dataflow.name),
+                        ));
+                    }
+                }
+                Condition::NodeStatus(status) => {
+                    for dataflow in dataflows {
+                        for node in dataflow.nodes.iter().filter(|node| node.status == status) {
+                            firing.push((
+                                (index, format!("{}/{}", dataflow.id, node.id)),
+                                format!("node {} of {} is {status}", node.id, dataflow.name),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.update(RuleKind::Status, firing, now)
+    }
+
+    /// Raise the conditions in `firing` that held long enough and resolve
+    /// the alerts of `kind` that no longer fire.
+    fn update(
+        &mut self,
+        kind: RuleKind,
+        firing: Vec<(AlertKey, String)>,
+        now: Instant,
+    ) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        let of_kind = |rules: &[AlertRule], index: usize| {
+            rules[index].watches_metrics() == (kind == RuleKind::Metrics)
+        };
+
+        let firing: HashMap<AlertKey, String> = firing.into_iter().collect();
+        self.pending
+            .retain(|key, _| !of_kind(&self.rules, key.0) || firing.contains_key(key));
+        let resolved: Vec<AlertKey> = self
+            .active
+            .keys()
+            .filter(|key| of_kind(&self.rules, key.0) && !firing.contains_key(*key))
+            .cloned()
+            .collect();
+        for key in resolved {
+            if let Some(alert) = self.active.remove(&key) {
+                events.push(AlertEvent::Resolved(alert));
+            }
+        }
+
+        for (key, message) in firing {
+            if let Some(alert) = self.active.get_mut(&key) {
+                alert.message = message;
+                continue;
+            }
+            let rule = &self.rules[key.0];
+            let since = *self.pending.entry(key.clone()).or_insert(now);
+            if now.duration_since(since) < rule.duration {
+                continue;
+            }
+            self.pending.remove(&key);
+            let alert = Alert {
+                level: rule.level,
+                title: rule.name.clone(),
+                message,
+                timestamp: now,
+                acknowledged: false,
+            };
+            events.push(AlertEvent::Raised(alert.clone()));
+            self.active.insert(key, alert);
+        }
+        events
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleKind {
+    Metrics,
+    Status,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_interface::NodeSummary;
+
+    fn cpu(value: f64) -> Vec<Reading> {
+        vec![Reading {
+            subject: String::new(),
+            metric: Metric::Cpu,
+            value,
+        }]
+    }
+
+    #[test]
+    fn thresholds_must_hold_for_their_duration() {
+        let mut engine = AlertEngine::new(vec![AlertRule::new(
+            "cpu",
+            Condition::Above(Metric::Cpu, 90.0),
+            30,
+            AlertLevel::Critical,
+        )]);
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(engine.evaluate_metrics(&cpu(95.0), at(0)).is_empty());
+        assert!(engine.evaluate_metrics(&cpu(96.0), at(20)).is_empty());
+        // Dropping below the threshold starts the wait over.
+        assert!(engine.evaluate_metrics(&cpu(50.0), at(25)).is_empty());
+        assert!(engine.evaluate_metrics(&cpu(95.0), at(40)).is_empty());
+
+        let events = engine.evaluate_metrics(&cpu(97.0), at(70));
+        let [AlertEvent::Raised(alert)] = events.as_slice() else {
+            panic!("expected one raised alert, got {events:?}");
+        };
+        assert_eq!(alert.title, "cpu");
+        assert_eq!(alert.message, "CPU at 97.0%, above 90.0%");
+
+        // Still firing: one alert, kept up to date.
+        assert!(engine.evaluate_metrics(&cpu(99.0), at(75)).is_empty());
+        assert_eq!(engine.active().len(), 1);
+        assert_eq!(engine.active()[0].message, "CPU at 99.0%, above 90.0%");
+
+        let events = engine.evaluate_metrics(&cpu(40.0), at(80));
+        assert!(matches!(events.as_slice(), [AlertEvent::Resolved(_)]));
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn status_rules_fire_per_subject() {
+        let mut engine = AlertEngine::default();
+        let node = |id: &str, status| NodeSummary {
+            id: id.to_string(),
+            status,
+            ..Default::default()
+        };
+        let mut dataflows = vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "camera".to_string(),
+            status: DataflowStatus::Running,
+            nodes: vec![
+                node("source", NodeStatus::Failed),
+                node("detector", NodeStatus::Running),
+            ],
+        }];
+        let now = Instant::now();
+
+        let events = engine.evaluate_dataflows(&dataflows, now);
+        let [AlertEvent::Raised(alert)] = events.as_slice() else {
+            panic!("expected one raised alert, got {events:?}");
+        };
+        assert_eq!(alert.message, "node source of camera is failed");
+        // Metric updates leave status alerts alone.
+        engine.evaluate_metrics(&cpu(10.0), now);
+        assert_eq!(engine.active().len(), 1);
+
+        dataflows[0].status = DataflowStatus::Failed;
+        dataflows[0].nodes[1].status = NodeStatus::Failed;
+        let events = engine.evaluate_dataflows(&dataflows, now);
+        assert_eq!(events.len(), 2);
+        let active = engine.active();
+        assert_eq!(active[0].message, "dataflow camera is failed");
+        assert_eq!(active[0].level, AlertLevel::Critical);
+
+        engine.acknowledge_all();
+        assert!(engine.active().iter().all(|alert| alert.acknowledged));
+        let events = engine.evaluate_dataflows(&[], now);
+        assert_eq!(events.len(), 3);
+        assert!(engine.active().is_empty());
+    }
+
+    #[test]
+    fn rules_files_replace_the_defaults() {
+        let (rules, problems) = AlertEngine::parse(
+            r#"
+            [[rule]]
+            name = "slow"
+            metric = "node_latency"
+            above = 200
+            for_secs = 10
+            severity = "error"
+
+            [[rule]]
+            name = "idle"
+            metric = "cpu"
+            below = 1
+
+            [[rule]]
+            name = "exited"
+            node_status = "exited"
+
+            [[rule]]
+            name = "broken"
+            metric = "gpu"
+            above = 1
+
+            [[rule]]
+            name = "both"
+            metric = "cpu"
+            node_status = "failed"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules,
+            vec![
+                AlertRule::new(
+                    "slow",
+                    Condition::Above(Metric::NodeLatency, 200.0),
+                    10,
+                    AlertLevel::Error
+                ),
+                AlertRule::new(
+                    "idle",
+                    Condition::Below(Metric::Cpu, 1.0),
+                    0,
+                    AlertLevel::Warning
+                ),
+                AlertRule::new(
+                    "exited",
+                    Condition::NodeStatus(NodeStatus::Exited),
+                    0,
+                    AlertLevel::Warning
+                ),
+            ]
+        );
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("rule `broken`: unknown metric `gpu`; expected one of: "));
+        assert!(problems[1].contains("set exactly one of"));
+
+        let (engine, problems) = AlertEngine::load(Some(Path::new("/nonexistent/alerts.toml")));
+        assert!(problems.is_empty());
+        assert_eq!(engine.rules(), AlertRule::defaults());
+    }
+}
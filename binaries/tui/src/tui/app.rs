@@ -1,17 +1,21 @@
+use chrono::{DateTime, Local};
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
     },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use futures::StreamExt;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Modifier, Style},
     terminal::{Frame, Terminal},
-    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "protocol")]
 use std::sync::Mutex;
 use std::{
@@ -27,20 +31,38 @@ use dora_message::{
     descriptor::{CoreNodeKind, EnvValue, ResolvedNode},
 };
 
-use super::{Result, command_executor::StateUpdate, theme::ThemeConfig};
+use super::{
+    Result,
+    accessibility::OutputMode,
+    alerts::{self, AlertEngine, AlertEvent},
+    cli_integration::{CommandLine, CommandLineEvent, CommandMode},
+    command_executor::{Command, StateUpdate},
+    connection::{ConnectionMonitor, Link, Transition},
+    crash::{self, CrashContext, TerminalGuard},
+    keymap::{GlobalAction, KeyChord, KeyContext, KeyTarget, Keymap},
+    modal::Modal,
+    notifier::{Notifier, NotifyEvent},
+    responsive::Breakpoint,
+    session::{SavedView, SessionStore},
+    snapshot::Snapshot,
+    tasks::{DataTasks, DataUpdate},
+    theme::ThemeConfig,
+    views::{AlertLevel, StateUpdate as ViewStateUpdate, ViewAction, utils},
+    workspace::{FocusDirection, SplitDirection, Workspaces},
+};
 #[cfg(any(feature = "tui-cli-services", feature = "protocol"))]
 use crate::tui::bridge::ServiceBundle;
-#[cfg(feature = "protocol")]
-use dora_protocol_client::ProtocolClients;
 use tui_interface::{
-    CoordinatorClient, DataflowSummary, DiskMetrics as InterfaceDiskMetrics,
-    LoadAverages as InterfaceLoadAverages, MemoryMetrics as InterfaceMemoryMetrics,
-    NetworkMetrics as InterfaceNetworkMetrics, NodeSummary, PreferencesStore,
+    CoordinatorClient, DataflowCommand, DataflowController, DataflowSummary,
+    DiskMetrics as InterfaceDiskMetrics, LoadAverages as InterfaceLoadAverages, LogService,
+    MemoryMetrics as InterfaceMemoryMetrics, NetworkMetrics as InterfaceNetworkMetrics, NodeStatus,
+    NodeSummary, OperationHandle, PreferencesStore, StartDataflowRequest,
     SystemMetrics as InterfaceSystemMetrics, SystemMetricsSample as InterfaceSystemMetricsSample,
-    TelemetryService,
+    TelemetryService, UserPreferencesSnapshot,
 };
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum ViewType {
     #[default]
     Dashboard,
@@ -51,6 +73,7 @@ pub enum ViewType {
         node_id: String,
     },
     SystemMonitor,
+    PerformanceAnalyzer,
     LogViewer {
         target: String,
     },
@@ -62,6 +85,50 @@ pub enum ViewType {
     },
     SettingsManager,
     Help,
+    Notifications,
+}
+
+impl ViewType {
+    /// The view called `name` in commands and on the command line. Views
+    /// that need parameters have no name.
+    pub fn from_name(name: &str) -> Option<ViewType> {
+        match name.to_lowercase().as_str() {
+            "dashboard" => Some(ViewType::Dashboard),
+            "dataflow" | "dataflow_manager" => Some(ViewType::DataflowManager),
+            "explorer" | "dataflow_explorer" => Some(ViewType::DataflowExplorer),
+            "system" | "monitor" | "system_monitor" => Some(ViewType::SystemMonitor),
+            "performance" | "analyzer" | "performance_analyzer" => {
+                Some(ViewType::PerformanceAnalyzer)
+            }
+            "logs" | "log" => Some(ViewType::LogViewer {
+                target: "system".to_string(),
+            }),
+            "settings" => Some(ViewType::SettingsManager),
+            "help" => Some(ViewType::Help),
+            "notifications" | "messages" => Some(ViewType::Notifications),
+            _ => None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Dashboard => "Dashboard".to_string(),
+            Self::DataflowManager => "Dataflow Manager".to_string(),
+            Self::DataflowExplorer => "Dataflow Explorer".to_string(),
+            Self::NodeInspector {
+                dataflow_id,
+                node_id,
+            } => format!("Node Inspector: {node_id} @ {dataflow_id}"),
+            Self::SystemMonitor => "System Monitor".to_string(),
+            Self::PerformanceAnalyzer => "Performance Analyzer".to_string(),
+            Self::LogViewer { target } => format!("Logs: {target}"),
+            Self::RecordingAnalyzer { recording_id } => format!("Recording: {recording_id}"),
+            Self::DebugSession { dataflow_id } => format!("Debug: {dataflow_id}"),
+            Self::SettingsManager => "Settings".to_string(),
+            Self::Help => "Help".to_string(),
+            Self::Notifications => "Notifications".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -85,7 +152,13 @@ pub struct AppState {
     /// Last error message
     pub last_error: Option<String>,
 
-    /// Status messages
+    /// Health of the gateway and the streams
+    pub connection: ConnectionMonitor,
+
+    /// Alert rules and the alerts they raised
+    pub alerts: AlertEngine,
+
+    /// Status message history, oldest first
     pub status_messages: VecDeque<StatusMessage>,
 
     /// Cached per-node telemetry samples
@@ -94,6 +167,25 @@ pub struct AppState {
 
 impl AppState {
     const SYSTEM_HISTORY_CAPACITY: usize = 180;
+    const MESSAGE_HISTORY_CAPACITY: usize = 500;
+    const TOAST_LIFETIME: Duration = Duration::from_secs(5);
+    const MAX_TOASTS: usize = 3;
+
+    pub fn push_status_message(&mut self, message: StatusMessage) {
+        self.status_messages.push_back(message);
+        while self.status_messages.len() > Self::MESSAGE_HISTORY_CAPACITY {
+            self.status_messages.pop_front();
+        }
+    }
+
+    /// Messages recent enough to show as toasts, newest first.
+    pub fn toasts(&self, now: Instant) -> impl Iterator<Item = &StatusMessage> {
+        self.status_messages
+            .iter()
+            .rev()
+            .take_while(move |msg| now.duration_since(msg.timestamp) < Self::TOAST_LIFETIME)
+            .take(Self::MAX_TOASTS)
+    }
 
     pub fn record_system_metrics(&mut self, metrics: &SystemMetrics) {
         self.system_metrics_history.push_back(SystemMetricsSample {
@@ -177,7 +269,7 @@ pub type NetworkMetrics = InterfaceNetworkMetrics;
 pub type LoadAverages = InterfaceLoadAverages;
 pub type SystemMetricsSample = InterfaceSystemMetricsSample;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NodeMetrics {
     pub cpu_percent: f64,
     pub memory_percent: f64,
@@ -212,16 +304,45 @@ pub struct UserConfig {
     pub theme_name: String,
     pub auto_refresh_interval: Duration,
     pub show_system_info: bool,
+    /// Whether the terminal reports mouse events; off lets the terminal
+    /// select text
+    pub mouse_capture: bool,
+    /// ASCII output, the high-contrast theme and no blinking
+    pub accessible: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct StatusMessage {
     pub message: String,
     pub level: MessageLevel,
+    /// What reported the message, e.g. `coordinator` or a view title
+    pub source: String,
+    /// The dataflow or node the message is about
+    pub target: Option<MessageTarget>,
     pub timestamp: Instant,
+    /// Wall-clock time, for display
+    pub time: DateTime<Local>,
 }
 
-#[derive(Debug, Clone)]
+impl StatusMessage {
+    pub fn new(source: impl Into<String>, message: String, level: MessageLevel) -> Self {
+        Self {
+            message,
+            level,
+            source: source.into(),
+            target: None,
+            timestamp: Instant::now(),
+            time: Local::now(),
+        }
+    }
+
+    pub fn with_target(mut self, target: Option<MessageTarget>) -> Self {
+        self.target = target;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MessageLevel {
     Info,
     Success,
@@ -229,6 +350,55 @@ pub enum MessageLevel {
     Error,
 }
 
+impl MessageLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageTarget {
+    Dataflow(String),
+    Node {
+        dataflow_id: String,
+        node_id: String,
+    },
+}
+
+impl MessageTarget {
+    /// The dataflow or node a view is showing, if any.
+    pub fn for_view(view: &ViewType) -> Option<Self> {
+        match view {
+            ViewType::NodeInspector {
+                dataflow_id,
+                node_id,
+            } => Some(Self::Node {
+                dataflow_id: dataflow_id.clone(),
+                node_id: node_id.clone(),
+            }),
+            ViewType::DebugSession { dataflow_id } => Some(Self::Dataflow(dataflow_id.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for MessageTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dataflow(dataflow_id) => f.write_str(dataflow_id),
+            Self::Node {
+                dataflow_id,
+                node_id,
+            } => write!(f, "{dataflow_id}/{node_id}"),
+        }
+    }
+}
+
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
 pub(crate) fn dataflow_from_entry(entry: DataflowListEntry) -> DataflowSummary {
     let DataflowListEntry { id, status, nodes } = entry;
@@ -265,7 +435,7 @@ fn node_from_runtime(mut runtime: NodeRuntimeInfo) -> NodeSummary {
             .name
             .clone()
             .unwrap_or_else(|| runtime.node.id.to_string()),
-        status: status.to_string(),
+        status,
         kind,
         description,
         inputs: runtime.inputs,
@@ -276,35 +446,30 @@ fn node_from_runtime(mut runtime: NodeRuntimeInfo) -> NodeSummary {
 }
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
-fn format_dataflow_status(status: DataflowStatus) -> String {
+fn format_dataflow_status(status: DataflowStatus) -> tui_interface::DataflowStatus {
     match status {
-        DataflowStatus::Running => "running",
-        DataflowStatus::Finished => "finished",
-        DataflowStatus::Failed => "failed",
+        DataflowStatus::Running => tui_interface::DataflowStatus::Running,
+        DataflowStatus::Finished => tui_interface::DataflowStatus::Finished,
+        DataflowStatus::Failed => tui_interface::DataflowStatus::Failed,
     }
-    .to_string()
 }
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
-fn format_node_status(status: NodeRuntimeState) -> &'static str {
+fn format_node_status(status: NodeRuntimeState) -> tui_interface::NodeStatus {
     match status {
-        NodeRuntimeState::Running => "running",
-        NodeRuntimeState::Exited => "exited",
-        NodeRuntimeState::Completed => "completed",
-        NodeRuntimeState::Failed => "failed",
-        NodeRuntimeState::Unknown => "unknown",
+        NodeRuntimeState::Running => tui_interface::NodeStatus::Running,
+        NodeRuntimeState::Exited => tui_interface::NodeStatus::Exited,
+        NodeRuntimeState::Completed => tui_interface::NodeStatus::Completed,
+        NodeRuntimeState::Failed => tui_interface::NodeStatus::Failed,
+        NodeRuntimeState::Unknown => tui_interface::NodeStatus::Unknown,
     }
 }
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
-fn describe_node_kind(kind: &CoreNodeKind) -> String {
+fn describe_node_kind(kind: &CoreNodeKind) -> tui_interface::NodeKind {
     match kind {
-        CoreNodeKind::Custom(custom) => {
-            format!("custom ({})", custom.path)
-        }
-        CoreNodeKind::Runtime(runtime) => {
-            format!("runtime ({} operators)", runtime.operators.len())
-        }
+        CoreNodeKind::Custom(_) => tui_interface::NodeKind::Custom,
+        CoreNodeKind::Runtime(_) => tui_interface::NodeKind::Runtime,
     }
 }
 
@@ -410,11 +575,8 @@ fn format_env_value(value: &EnvValue) -> String {
 }
 
 pub struct DoraApp {
-    /// Current active view
-    current_view: ViewType,
-
-    /// View navigation stack
-    view_stack: Vec<ViewType>,
+    /// Open tabs, their panes and the views live in each pane
+    workspaces: Workspaces,
 
     /// Global application state
     state: AppState,
@@ -431,19 +593,65 @@ pub struct DoraApp {
     /// Preference storage backend
     preferences_store: Arc<dyn PreferencesStore>,
 
+    /// Dataflow lifecycle backend, absent when running without services
+    dataflow_controller: Option<Arc<dyn DataflowController>>,
+
+    /// Log backend for the log viewer; demo logs are shown without one
+    log_service: Option<Arc<dyn LogService>>,
+
+    /// Key bindings for global actions and for each view
+    keymap: Arc<Keymap>,
+
+    /// Open dialog; it receives all keys until it closes
+    modal: Option<Modal>,
+
+    /// Whether keys go to the `:` command line
+    command_mode: CommandMode,
+    command_line: CommandLine,
+
+    /// Whether mouse capture is enabled on the terminal right now
+    mouse_captured: bool,
+
+    /// How finished frames are adjusted for accessibility
+    output_mode: OutputMode,
+
+    /// Terminal notifications for the events the user opted into
+    notifier: Notifier,
+
+    /// Backend fetches running off the event loop
+    tasks: DataTasks,
+
+    /// Whether anything on screen may have changed since the last draw
+    dirty: bool,
+
+    /// Toasts shown on the last frame, to redraw when one expires
+    drawn_toasts: usize,
+
+    /// Where the session is saved on exit
+    session_store: Option<SessionStore>,
+    /// Restored views still need checking against the first dataflow list
+    check_restored_views: bool,
+
     /// Should quit flag
     should_quit: bool,
     #[cfg(feature = "protocol")]
-    protocol_clients: Option<Arc<ProtocolClients>>,
-    #[cfg(feature = "protocol")]
     metrics_cache: Option<Arc<Mutex<Option<SystemMetrics>>>>,
 }
 
 impl DoraApp {
     const DATAFLOW_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
-
+    /// How often periodic work runs while no input or data arrives.
+    const TICK_INTERVAL: Duration = Duration::from_millis(250);
+
+    /// An app on the default services.
+    ///
+    /// # Panics
+    ///
+    /// If `DORA_PROTOCOL_URL` is not a valid URL; `run_tui` reports that as
+    /// an error instead.
     pub fn new(initial_view: ViewType) -> Self {
-        let bundle = crate::tui::bridge::default_service_bundle();
+        let bundle =
+            crate::tui::bridge::default_service_bundle().unwrap_or_else(|err| panic!("{err}"));
         Self::from_service_bundle(initial_view, bundle)
     }
 
@@ -453,22 +661,41 @@ impl DoraApp {
         coordinator_client: Arc<dyn CoordinatorClient>,
         telemetry_service: Arc<dyn TelemetryService>,
     ) -> Self {
+        let theme = ThemeConfig::from_name("dark");
         let mut app = Self {
-            current_view: initial_view,
-            view_stack: Vec::new(),
+            workspaces: Workspaces::new(initial_view, theme.clone()),
             state: AppState::default(),
-            theme: ThemeConfig::load_user_theme(),
+            theme,
             telemetry_service,
             coordinator_client,
             preferences_store,
+            dataflow_controller: None,
+            log_service: None,
+            keymap: Arc::default(),
+            modal: None,
+            command_mode: CommandMode::Normal,
+            command_line: CommandLine::default(),
+            mouse_captured: false,
+            output_mode: OutputMode::default(),
+            notifier: Notifier::default(),
+            tasks: DataTasks::default(),
+            dirty: true,
+            drawn_toasts: 0,
+            session_store: None,
+            check_restored_views: false,
             should_quit: false,
             #[cfg(feature = "protocol")]
-            protocol_clients: None,
-            #[cfg(feature = "protocol")]
             metrics_cache: None,
         };
 
-        app.apply_user_preferences();
+        // Before the event loop starts, so loading in place is fine.
+        let loaded = app
+            .preferences_store
+            .load()
+            .map_err(|err| format!("failed to load preferences: {err}"));
+        if let Some(view_name) = app.apply_user_preferences(loaded) {
+            app.open_default_view(&view_name);
+        }
         app
     }
 
@@ -477,12 +704,13 @@ impl DoraApp {
         let ServiceBundle {
             preferences_store,
             coordinator_client,
+            dataflow_controller,
             telemetry_service,
+            log_service,
             legacy_cli_service: _legacy_cli_service,
             #[cfg(feature = "protocol")]
-            protocol_clients,
-            #[cfg(feature = "protocol")]
             metrics_cache,
+            ..
         } = bundle;
 
         #[cfg_attr(not(feature = "protocol"), allow(unused_mut))]
@@ -491,297 +719,900 @@ impl DoraApp {
             preferences_store,
             coordinator_client,
             telemetry_service,
-        );
+        )
+        .with_dataflow_controller(dataflow_controller)
+        .with_log_service(log_service);
+
+        let (keymap, problems) = Keymap::load(Keymap::default_path().as_deref());
+        app = app.with_keymap(Arc::new(keymap));
+        for problem in problems {
+            tracing::warn!("keymap: {problem}");
+            app.notify(StatusMessage::new(
+                "keymap",
+                format!("⚠️ {problem}"),
+                MessageLevel::Warning,
+            ));
+        }
+
+        let (alerts, problems) = AlertEngine::load(AlertEngine::default_path().as_deref());
+        app.state.alerts = alerts;
+        for problem in problems {
+            tracing::warn!("alerts: {problem}");
+            app.notify(StatusMessage::new(
+                "alerts",
+                format!("⚠️ {problem}"),
+                MessageLevel::Warning,
+            ));
+        }
 
         #[cfg(feature = "protocol")]
         {
-            app.protocol_clients = Some(protocol_clients);
             app.metrics_cache = Some(metrics_cache);
         }
 
         app
     }
 
-    fn apply_user_preferences(&mut self) {
-        match self.preferences_store.load() {
+    pub fn with_dataflow_controller(mut self, controller: Arc<dyn DataflowController>) -> Self {
+        self.dataflow_controller = Some(controller);
+        self
+    }
+
+    pub fn with_log_service(mut self, service: Arc<dyn LogService>) -> Self {
+        self.log_service = Some(service);
+        self.workspaces.set_log_service(self.log_service.clone());
+        self
+    }
+
+    pub fn with_keymap(mut self, keymap: Arc<Keymap>) -> Self {
+        self.workspaces.set_keymap(keymap.clone());
+        self.keymap = keymap;
+        self
+    }
+
+    /// Save the session to `store` on exit, and restore the saved one now
+    /// if `restore` is set.
+    pub fn with_session(mut self, store: SessionStore, restore: bool) -> Self {
+        if restore {
+            match store.load() {
+                Ok(Some(session)) => {
+                    self.workspaces.restore_session(session);
+                    self.check_restored_views = true;
+                }
+                Ok(None) => {}
+                Err(err) => self.notify(StatusMessage::new(
+                    "session",
+                    format!("⚠️ ignoring the last session: {err}"),
+                    MessageLevel::Warning,
+                )),
+            }
+        }
+        self.session_store = Some(store);
+        self
+    }
+
+    /// Open `view`, resolved from a command-line deep link, in place of the
+    /// initial view.
+    pub fn with_deep_link(mut self, view: SavedView) -> Self {
+        self.workspaces.focused_mut().restore_views(vec![view]);
+        // An explorer selection is applied once the dataflows arrive.
+        self.check_restored_views = true;
+        self
+    }
+
+    fn save_session(&self) {
+        let Some(store) = &self.session_store else {
+            return;
+        };
+        if let Err(err) = store.save(&self.workspaces.session(&self.state)) {
+            tracing::warn!("failed to save the session: {err}");
+        }
+    }
+
+    /// Apply loaded preferences to the running app. Returns the configured
+    /// default view, which is only opened at startup.
+    fn apply_user_preferences(
+        &mut self,
+        loaded: std::result::Result<UserPreferencesSnapshot, String>,
+    ) -> Option<String> {
+        match loaded {
             Ok(snapshot) => {
                 self.state.user_config.theme_name = snapshot.theme.clone();
                 self.state.user_config.auto_refresh_interval =
                     Duration::from_secs(snapshot.auto_refresh_interval_secs.max(1));
                 self.state.user_config.show_system_info = snapshot.show_system_info;
+                self.state.user_config.mouse_capture = snapshot.mouse_capture;
+                self.state.user_config.accessible = snapshot.accessible;
+                self.output_mode = OutputMode::new(snapshot.accessible);
+
+                let (events, unknown) = NotifyEvent::parse_list(&snapshot.notify_on);
+                self.notifier.set_events(events);
+                for name in unknown {
+                    self.notify(StatusMessage::new(
+                        "preferences",
+                        format!("⚠️ unknown notification event `{name}`, ignoring it"),
+                        MessageLevel::Warning,
+                    ));
+                }
 
-                self.theme = ThemeConfig::from_name(&self.state.user_config.theme_name);
-
-                if let Some(view_name) = snapshot.default_view.as_deref() {
-                    if let Some(view) = Self::view_from_name(view_name) {
-                        self.current_view = view;
+                // Accessibility mode brings its own theme unless the user
+                // picked one.
+                let theme_name = if snapshot.accessible
+                    && snapshot.theme == UserPreferencesSnapshot::default().theme
+                {
+                    "high-contrast"
+                } else {
+                    snapshot.theme.as_str()
+                };
+                self.theme = match ThemeConfig::load_user_theme(theme_name) {
+                    Ok(theme) => theme,
+                    Err(err) => {
+                        self.notify(StatusMessage::new(
+                            "theme",
+                            format!("⚠️ {err}, using dark"),
+                            MessageLevel::Warning,
+                        ));
+                        ThemeConfig::from_name("dark")
                     }
-                }
+                };
+                self.workspaces.set_theme(&self.theme);
+                snapshot.default_view
             }
             Err(err) => {
-                self.show_status_message(
-                    format!("❌ failed to load preferences: {err}"),
+                self.notify(StatusMessage::new(
+                    "preferences",
+                    format!("❌ {err}"),
                     MessageLevel::Error,
-                );
+                ));
                 self.state.user_config.auto_refresh_interval = Duration::from_secs(5);
                 self.state.user_config.show_system_info = true;
+                self.state.user_config.mouse_capture = true;
+                self.state.user_config.accessible = false;
+                self.output_mode = OutputMode::new(false);
+                self.notifier.set_events(Vec::new());
                 self.state.user_config.theme_name = "dark".to_string();
                 self.theme = ThemeConfig::from_name(&self.state.user_config.theme_name);
+                self.workspaces.set_theme(&self.theme);
+                None
             }
         }
     }
 
-    fn view_from_name(name: &str) -> Option<ViewType> {
-        match name.to_lowercase().as_str() {
-            "dashboard" => Some(ViewType::Dashboard),
-            "dataflow" | "dataflow_manager" => Some(ViewType::DataflowManager),
-            "explorer" | "dataflow_explorer" => Some(ViewType::DataflowExplorer),
-            "system" | "monitor" | "system_monitor" => Some(ViewType::SystemMonitor),
-            "logs" | "log" => Some(ViewType::LogViewer {
-                target: "system".to_string(),
-            }),
-            _ => None,
+    fn open_default_view(&mut self, view_name: &str) {
+        match ViewType::from_name(view_name) {
+            Some(view) => self.workspaces.focused_mut().switch(view),
+            None => self.notify(StatusMessage::new(
+                "preferences",
+                format!("⚠️ unknown default view `{view_name}`, ignoring it"),
+                MessageLevel::Warning,
+            )),
         }
     }
 
     pub async fn run(&mut self) -> Result<()> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        // Restores the terminal however this function is left
+        let guard = TerminalGuard::enter()?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
 
         // Initialize application state
         self.initialize().await?;
 
         // Main application loop
         let result = self.run_event_loop(&mut terminal).await;
+        self.save_session();
 
-        // Cleanup terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
-
+        drop(guard);
+        self.mouse_captured = false;
         result
     }
 
     async fn initialize(&mut self) -> Result<()> {
-        // Initialize application state
-        self.state.user_config.auto_refresh_interval = Duration::from_secs(5);
-        self.state.user_config.show_system_info = true;
-        self.state.user_config.theme_name = "dark".to_string();
-
-        // Load initial data
-        self.refresh_dataflow_list().await?;
-        self.update_system_metrics().await?;
+        // Load initial data in the background so the first frame is not
+        // held up by the backend
+        self.tasks
+            .fetch_dataflows(Arc::clone(&self.coordinator_client));
+        self.tasks
+            .fetch_system_metrics(Arc::clone(&self.telemetry_service));
 
         Ok(())
     }
 
+    /// Load what the first frame would show and return it without touching
+    /// the terminal.
+    pub async fn snapshot(&mut self) -> Result<Snapshot> {
+        self.initialize().await?;
+
+        let mut errors = Vec::new();
+        while !self.tasks.is_idle() {
+            let update = self.tasks.recv().await;
+            errors.extend(update.error().map(str::to_string));
+            self.apply_data_update(update);
+        }
+
+        Ok(Snapshot {
+            dataflows: self.state.dataflows.clone(),
+            system_metrics: self.state.system_metrics.clone(),
+            errors,
+        })
+    }
+
+    /// Wait for terminal input, finished fetches or the next tick, and
+    /// redraw only when something changed.
     async fn run_event_loop(
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     ) -> Result<()> {
-        loop {
-            // Render current view
-            terminal.draw(|f| self.ui(f))?;
-
-            // Handle events with timeout for periodic updates
-            if crossterm::event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    if key.kind == KeyEventKind::Press {
-                        self.handle_key_event(key).await?;
-                    }
-                }
+        let mut events = EventStream::new();
+        let mut tick = tokio::time::interval(Self::TICK_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        while !self.should_quit {
+            self.sync_mouse_capture(terminal.backend_mut())?;
+            if std::mem::take(&mut self.dirty) {
+                terminal.draw(|f| self.ui(f))?;
+                crash::record_context(CrashContext::new(
+                    self.view_title(),
+                    self.state.status_messages.iter(),
+                ));
+            }
+            self.flush_notifications(terminal.backend_mut())?;
+
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(event) => self.handle_terminal_event(event?).await?,
+                    None => break,
+                },
+                update = self.tasks.recv() => self.apply_data_update(update),
+                _ = tick.tick() => self.update().await?,
             }
+        }
 
-            // Periodic updates
-            self.update().await?;
+        Ok(())
+    }
 
-            if self.should_quit {
-                break;
+    async fn handle_terminal_event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                self.handle_key_event(key).await?;
+            }
+            Event::Mouse(mouse) => self.handle_mouse_event(mouse).await?,
+            Event::Resize(..) => {}
+            _ => return Ok(()),
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn apply_data_update(&mut self, update: DataUpdate) {
+        match update {
+            DataUpdate::Dataflows(result, latency) => self.apply_dataflows(result, latency),
+            DataUpdate::SystemMetrics(result, latency) => {
+                self.apply_system_metrics(result, latency)
+            }
+            DataUpdate::Control(command, result) => self.apply_control_result(command, result),
+            DataUpdate::Preferences(result) => {
+                self.apply_user_preferences(result);
+            }
+            DataUpdate::PreferenceSet(key, value, result) => {
+                self.apply_preference_set(key, value, result)
             }
         }
+    }
 
+    /// Turn terminal mouse capture on or off to match the preference.
+    fn sync_mouse_capture(&mut self, out: &mut impl io::Write) -> io::Result<()> {
+        let wanted = self.state.user_config.mouse_capture;
+        if wanted != self.mouse_captured {
+            if wanted {
+                execute!(out, EnableMouseCapture)?;
+            } else {
+                execute!(out, DisableMouseCapture)?;
+            }
+            self.mouse_captured = wanted;
+        }
         Ok(())
     }
 
+    /// Write queued notifications to the terminal.
+    fn flush_notifications(&mut self, out: &mut impl io::Write) -> io::Result<()> {
+        let pending = self.notifier.take_pending();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        for sequence in pending {
+            out.write_all(sequence.as_bytes())?;
+        }
+        out.flush()
+    }
+
     fn ui(&mut self, f: &mut Frame) {
         let size = f.size();
 
+        // Bordered header and footer take six rows; drop the borders when
+        // that would leave a compact body.
+        let full_chrome_body = Rect {
+            height: size.height.saturating_sub(6),
+            ..size
+        };
+        let compact = Breakpoint::of(full_chrome_body).is_compact();
+        let chrome_height = if compact { 1 } else { 3 };
+
         // Main layout: header + body + footer
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Min(0),    // Body
-                Constraint::Length(3), // Footer/Status
+                Constraint::Length(chrome_height), // Header
+                Constraint::Min(0),                // Body
+                Constraint::Length(chrome_height), // Footer/Status
             ])
             .split(size);
 
-        self.render_header(f, chunks[0]);
-        self.render_current_view(f, chunks[1]);
-        self.render_footer(f, chunks[2]);
+        let mut body = chunks[1];
+        if let Some(banner) = self.connection_banner() {
+            f.render_widget(banner, Rect { height: 1, ..body });
+            body.y += 1;
+            body.height = body.height.saturating_sub(1);
+        }
+
+        self.render_header(f, chunks[0], compact);
+        self.render_current_view(f, body);
+        self.render_footer(f, chunks[2], compact);
+        self.render_toasts(f, body);
 
         // Render overlays (command mode, help, etc.)
         self.render_overlays(f, size);
+
+        self.output_mode
+            .apply(f.buffer_mut(), self.theme.colors.background);
     }
 
-    fn render_header(&mut self, f: &mut Frame, area: Rect) {
-        let title = format!("🚀 Dora TUI - {}", self.view_title());
-        let header = Paragraph::new(title)
+    fn render_header(&mut self, f: &mut Frame, area: Rect, compact: bool) {
+        // The tab strip only shows once there is more than one tab.
+        let mut spans = Vec::new();
+        let tabs = self.workspaces.tabs();
+        if tabs.len() > 1 {
+            for (index, tab) in tabs.iter().enumerate() {
+                let style = if index == self.workspaces.active_index() {
+                    self.theme.styles.selection_style
+                } else {
+                    Style::default().fg(self.theme.colors.muted)
+                };
+                spans.push(Span::styled(
+                    format!(" {}:{} ", index + 1, tab.name()),
+                    style,
+                ));
+            }
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::raw(format!("🚀 Dora TUI - {}", self.view_title())));
+        let mut header = Paragraph::new(Line::from(spans))
             .style(self.theme.styles.highlight_style)
-            .block(
+            .alignment(Alignment::Center);
+        if !compact {
+            header = header.block(
                 self.theme
                     .styled_block("Dora CLI/TUI")
                     .borders(Borders::ALL),
-            )
-            .alignment(Alignment::Center);
+            );
+        }
 
         f.render_widget(header, area);
     }
 
-    fn render_current_view(&mut self, f: &mut Frame, area: Rect) {
-        use super::views::*;
+    /// A row above the views while a link is down.
+    fn connection_banner(&self) -> Option<Paragraph<'static>> {
+        let (link, health) = self.state.connection.outage()?;
+        let mut text = format!(" Reconnecting to the {link}");
+        if let Some(err) = &health.last_error {
+            text.push_str(&format!(": {err}"));
+        }
+        text.push_str(&format!(
+            " ({} failed attempts",
+            health.consecutive_failures
+        ));
+        if let Some(last) = health.last_success {
+            text.push_str(&format!(
+                ", last connected {} ago",
+                utils::format_duration(last.elapsed())
+            ));
+        }
+        text.push(')');
 
-        match &self.current_view {
-            ViewType::Dashboard => {
-                let mut view = DashboardView::new(&self.theme);
-                view.render(f, area, &self.state);
-            }
-            ViewType::DataflowManager => {
-                let mut view = DataflowManagerView::new(&self.theme);
-                view.render(f, area, &self.state);
-            }
-            ViewType::DataflowExplorer => {
-                let mut view = DataflowExplorerView::new(&self.theme);
-                view.render(f, area, &self.state);
-            }
-            ViewType::NodeInspector {
-                dataflow_id,
-                node_id,
-            } => {
-                let mut view =
-                    NodeInspectorView::new(&self.theme, dataflow_id.clone(), node_id.clone());
-                view.render(f, area, &self.state);
-            }
-            ViewType::SystemMonitor => {
-                let mut view = SystemMonitorView::new(&self.theme);
-                view.render(f, area, &self.state);
-            }
-            ViewType::LogViewer { target } => {
-                #[cfg(feature = "protocol")]
-                let mut view =
-                    LogViewerView::new(target, &self.theme, self.protocol_clients.clone());
-                #[cfg(not(feature = "protocol"))]
-                let mut view = LogViewerView::new(target, &self.theme);
-                view.render(f, area, &self.state);
+        let style = Style::default()
+            .fg(self.theme.colors.background)
+            .bg(self.theme.colors.warning)
+            .add_modifier(Modifier::BOLD);
+        Some(Paragraph::new(text).style(style))
+    }
+
+    /// Status and latency of every tracked link, for the status bar.
+    fn connection_indicator(&self) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (link, health) in self.state.connection.links() {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
             }
-            ViewType::Help => {
-                let mut view = HelpView::new(&self.theme);
-                view.render(f, area, &self.state);
+            spans.push(utils::status_indicator(health.status()));
+            let mut label = format!(" {link}");
+            if let Some(latency) = health.latency {
+                label.push_str(&format!(" {}ms", latency.as_millis()));
             }
-            _ => {
-                // Fallback for unimplemented views
-                let content = match &self.current_view {
-                    ViewType::RecordingAnalyzer { recording_id } => format!("🎬 Recording Analyzer\n\nAnalyzing recording: {recording_id}\n\nPlayback controls and data analysis tools."),
-                    ViewType::DebugSession { dataflow_id } => format!("🐛 Debug Session\n\nDebugging dataflow: {dataflow_id}\n\nBreakpoints, step-through debugging, and variable inspection."),
-                    ViewType::SettingsManager => "⚙️ Settings Manager\n\nConfigure Dora CLI/TUI:\n- Theme settings\n- Key bindings\n- Default behaviors\n- Performance tuning".to_string(),
-                    _ => "View not implemented yet".to_string(),
-                };
+            spans.push(Span::styled(
+                label,
+                Style::default().fg(self.theme.colors.muted),
+            ));
+        }
+        Line::from(spans)
+    }
 
-                let title = self.view_title();
-                let view_widget = Paragraph::new(content)
-                    .style(Style::default().fg(self.theme.colors.text))
-                    .block(self.theme.styled_block(&title).borders(Borders::ALL))
-                    .wrap(Wrap { trim: true });
+    fn render_current_view(&mut self, f: &mut Frame, area: Rect) {
+        self.workspaces.render(f, area, &self.state);
+    }
 
-                f.render_widget(view_widget, area);
-            }
+    fn render_footer(&mut self, f: &mut Frame, area: Rect, compact: bool) {
+        let block = Block::default()
+            .borders(if compact { Borders::NONE } else { Borders::ALL })
+            .border_type(self.theme.styles.border_style)
+            .border_style(Style::default().fg(self.theme.colors.border));
+
+        if self.command_mode == CommandMode::Command {
+            let input = self.command_line.input();
+            let candidates = self.command_line.candidates().join("  ");
+            let inner = block.inner(area);
+            let footer = if compact {
+                // No border to put the candidates in; show them after the
+                // input instead.
+                Paragraph::new(Line::from(vec![
+                    Span::styled(
+                        format!(":{}", input.value()),
+                        Style::default().fg(self.theme.colors.text),
+                    ),
+                    Span::styled(
+                        format!("  {candidates}"),
+                        Style::default().fg(self.theme.colors.muted),
+                    ),
+                ]))
+            } else {
+                Paragraph::new(format!(":{}", input.value()))
+                    .style(Style::default().fg(self.theme.colors.text))
+                    .block(block.title(candidates))
+            };
+            f.render_widget(footer, area);
+            f.set_cursor(inner.x + 1 + input.cursor() as u16, inner.y);
+            return;
         }
+
+        let key = |action| {
+            self.keymap
+                .keys_label(KeyContext::Global, action)
+                .unwrap_or_else(|| "(unbound)".to_string())
+        };
+        let hint = format!(
+            "Press '{}' to quit, '{}' for commands, {} for help",
+            key("quit"),
+            key("command"),
+            key("help")
+        );
+        let inner = block.inner(area);
+        let footer = Paragraph::new(hint)
+            .style(self.theme.styles.status_style)
+            .block(block);
+
+        f.render_widget(footer, area);
+        f.render_widget(
+            Paragraph::new(self.connection_indicator()).alignment(Alignment::Right),
+            inner,
+        );
     }
 
-    fn render_footer(&mut self, f: &mut Frame, area: Rect) {
-        let footer_text = "Press 'q' to quit, F1 for help".to_string();
+    /// Recent messages, stacked upwards from the bottom-right corner of the
+    /// view. They never take focus and disappear on their own; older
+    /// messages stay in the notification center.
+    fn render_toasts(&mut self, f: &mut Frame, area: Rect) {
+        const TOAST_HEIGHT: u16 = 3;
+        let width = area.width.min(50);
+        let mut drawn = 0;
+        let mut bottom = area.bottom();
+
+        for toast in self.state.toasts(Instant::now()) {
+            if bottom < area.y + TOAST_HEIGHT {
+                break;
+            }
+            bottom -= TOAST_HEIGHT;
+            let toast_area = Rect::new(area.right() - width, bottom, width, TOAST_HEIGHT);
+            let style = self.theme.message_style(toast.level);
 
-        let footer = Paragraph::new(footer_text)
-            .style(self.theme.styles.status_style)
-            .block(
+            let widget = Paragraph::new(toast.message.as_str()).style(style).block(
                 Block::default()
+                    .title(toast.source.as_str())
                     .borders(Borders::ALL)
                     .border_type(self.theme.styles.border_style)
-                    .border_style(Style::default().fg(self.theme.colors.border)),
+                    .border_style(style),
             );
-
-        f.render_widget(footer, area);
+            f.render_widget(Clear, toast_area);
+            f.render_widget(widget, toast_area);
+            drawn += 1;
+        }
+        self.drawn_toasts = drawn;
     }
 
     fn render_overlays(&mut self, f: &mut Frame, area: Rect) {
-        // Render status messages as overlay
-        if let Some(status) = self.state.status_messages.back() {
-            let popup_area = centered_rect(60, 20, area);
-
-            let message = Paragraph::new(status.message.as_str())
-                .style(match status.level {
-                    MessageLevel::Info => Style::default().fg(self.theme.colors.text),
-                    MessageLevel::Success => Style::default().fg(self.theme.colors.success),
-                    MessageLevel::Warning => Style::default().fg(self.theme.colors.warning),
-                    MessageLevel::Error => Style::default().fg(self.theme.colors.error),
-                })
-                .block(
-                    Block::default()
-                        .title("Status")
-                        .borders(Borders::ALL)
-                        .border_type(self.theme.styles.border_style),
-                )
-                .alignment(Alignment::Center);
-
-            f.render_widget(Clear, popup_area);
-            f.render_widget(message, popup_area);
+        if let Some(modal) = &self.modal {
+            modal.render(f, area, &self.theme);
         }
     }
 
     async fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
-        // Global key bindings
-        match key.code {
-            KeyCode::Char('q') => {
-                self.should_quit = true;
-                return Ok(());
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.should_quit = true;
+            return Ok(());
+        }
+
+        if let Some(modal) = self.modal.as_mut() {
+            if let Some(result) = modal.handle_key(key) {
+                let modal = self.modal.take().expect("modal is open");
+                let action = self.workspaces.focused_mut().deliver_modal_result(
+                    modal.origin(),
+                    modal.id(),
+                    result,
+                    &mut self.state,
+                );
+                self.apply_view_action(action).await?;
             }
-            KeyCode::F(1) => {
-                self.switch_view(ViewType::Help);
-                return Ok(());
+            return Ok(());
+        }
+
+        if self.command_mode == CommandMode::Command {
+            match self.command_line.handle_key(key, &self.state.dataflows) {
+                CommandLineEvent::Editing => {}
+                CommandLineEvent::Cancelled => self.command_mode = CommandMode::Normal,
+                CommandLineEvent::Submitted(line) => {
+                    self.command_mode = CommandMode::Normal;
+                    self.apply_view_action(ViewAction::ExecuteCommand(line))
+                        .await?;
+                }
             }
-            KeyCode::Esc => {
-                if !self.view_stack.is_empty() {
+            return self
+                .workspaces
+                .focused_mut()
+                .mount_pending(&mut self.state)
+                .await;
+        }
+
+        // A view editing text gets every key. Otherwise pane and tab keys
+        // apply first, then the view's keymap context is tried, and global
+        // bindings apply to keys the view does not bind or use. Views without
+        // a context get raw keys.
+        let view_key = if self.workspaces.focused_mut().captures_text() {
+            Some(key)
+        } else if let Some(action) = self.workspace_action(key) {
+            self.apply_workspace_action(action);
+            return self
+                .workspaces
+                .focused_mut()
+                .mount_pending(&mut self.state)
+                .await;
+        } else {
+            match KeyContext::for_view(self.workspaces.focused().current()) {
+                Some(context) => self.keymap.translate(context, KeyChord::from(key)),
+                None => Some(key),
+            }
+        };
+
+        let action = match view_key {
+            Some(view_key) => {
+                self.workspaces
+                    .focused_mut()
+                    .handle_key(view_key, &mut self.state)
+                    .await?
+            }
+            None => ViewAction::Unhandled,
+        };
+        if matches!(action, ViewAction::Unhandled) {
+            self.handle_global_key(key);
+        } else {
+            self.apply_view_action(action).await?;
+        }
+
+        self.workspaces
+            .focused_mut()
+            .mount_pending(&mut self.state)
+            .await
+    }
+
+    async fn handle_mouse_event(&mut self, event: MouseEvent) -> Result<()> {
+        // Dialogs and the command line are keyboard only.
+        if self.modal.is_some() || self.command_mode == CommandMode::Command {
+            return Ok(());
+        }
+
+        // A click focuses the pane under the pointer before it is handled.
+        if let MouseEventKind::Down(_) = event.kind {
+            self.workspaces
+                .active_mut()
+                .focus_at(event.column, event.row);
+        }
+
+        let action = self
+            .workspaces
+            .focused_mut()
+            .handle_mouse(event, &mut self.state)
+            .await?;
+        if !matches!(action, ViewAction::Unhandled) {
+            self.apply_view_action(action).await?;
+        }
+        self.workspaces
+            .focused_mut()
+            .mount_pending(&mut self.state)
+            .await
+    }
+
+    fn handle_global_key(&mut self, key: KeyEvent) {
+        let Some(binding) = self.keymap.resolve(KeyContext::Global, KeyChord::from(key)) else {
+            return;
+        };
+        let KeyTarget::Global(action) = binding.target else {
+            return;
+        };
+
+        match action {
+            GlobalAction::Quit => self.should_quit = true,
+            GlobalAction::Help => self.push_view(ViewType::Help),
+            GlobalAction::Back => self.pop_view(),
+            GlobalAction::CommandLine => {
+                self.command_line.open();
+                self.command_mode = CommandMode::Command;
+            }
+            GlobalAction::ToggleMouse => {
+                let capture = !self.state.user_config.mouse_capture;
+                self.set_user_preference("mouse_capture", &capture.to_string());
+            }
+            action if action.is_workspace() => self.apply_workspace_action(action),
+            view_shortcut => {
+                if let Some(view) = view_shortcut.view() {
+                    self.switch_view(view);
+                }
+            }
+        }
+    }
+
+    fn workspace_action(&self, key: KeyEvent) -> Option<GlobalAction> {
+        match self
+            .keymap
+            .resolve(KeyContext::Global, KeyChord::from(key))?
+            .target
+        {
+            KeyTarget::Global(action) if action.is_workspace() => Some(action),
+            _ => None,
+        }
+    }
+
+    /// Split, close, focus or resize panes and open, close or cycle tabs.
+    fn apply_workspace_action(&mut self, action: GlobalAction) {
+        const RESIZE_STEP: i16 = 5;
+
+        let workspace = self.workspaces.active_mut();
+        match action {
+            GlobalAction::FocusLeft => {
+                workspace.focus_towards(FocusDirection::Left);
+            }
+            GlobalAction::FocusRight => {
+                workspace.focus_towards(FocusDirection::Right);
+            }
+            GlobalAction::FocusUp => {
+                workspace.focus_towards(FocusDirection::Up);
+            }
+            GlobalAction::FocusDown => {
+                workspace.focus_towards(FocusDirection::Down);
+            }
+            GlobalAction::GrowPane => {
+                workspace.resize(RESIZE_STEP);
+            }
+            GlobalAction::ShrinkPane => {
+                workspace.resize(-RESIZE_STEP);
+            }
+            GlobalAction::SplitRight => self.workspaces.split(SplitDirection::Horizontal, None),
+            GlobalAction::SplitDown => self.workspaces.split(SplitDirection::Vertical, None),
+            GlobalAction::ClosePane => {
+                let closed = self.workspaces.close_pane();
+                if !closed {
+                    self.workspace_warning("the last pane of a tab can't be closed");
+                }
+            }
+            GlobalAction::NewTab => self.workspaces.new_tab(None),
+            GlobalAction::CloseTab => {
+                let closed = self.workspaces.close_tab();
+                if !closed {
+                    self.workspace_warning("the last tab can't be closed");
+                }
+            }
+            GlobalAction::NextTab => self.workspaces.next_tab(),
+            GlobalAction::PreviousTab => self.workspaces.previous_tab(),
+            _ => {}
+        }
+    }
+
+    fn workspace_warning(&mut self, message: &str) {
+        self.notify(StatusMessage::new(
+            "workspace",
+            message.to_string(),
+            MessageLevel::Warning,
+        ));
+    }
+
+    /// Carry out an action returned by a view.
+    async fn apply_view_action(&mut self, action: ViewAction) -> Result<()> {
+        match action {
+            ViewAction::None | ViewAction::Unhandled | ViewAction::FocusWidget(_) => {}
+            ViewAction::SwitchView(view) => self.switch_view(view),
+            ViewAction::PushView(view) => self.push_view(view),
+            ViewAction::PopView => {
+                // A root view asking to go back returns to the dashboard.
+                if self.workspaces.focused().stack_len() > 0 {
                     self.pop_view();
+                } else {
+                    self.switch_view(ViewType::Dashboard);
                 }
+            }
+            ViewAction::Quit => self.should_quit = true,
+            ViewAction::ShowHelp => self.push_view(ViewType::Help),
+            ViewAction::ShowError(message) => self.notify_from_view(message, MessageLevel::Error),
+            ViewAction::ShowStatus(message) => self.notify_from_view(message, MessageLevel::Info),
+            ViewAction::ShowDataflow(dataflow) => self.show_dataflow(&dataflow),
+            ViewAction::Refresh => self.refresh_current_view_data(),
+            ViewAction::ControlDataflow(command) => self.control_dataflow(command),
+            ViewAction::UpdateState(update) => self.apply_view_state_update(update).await?,
+            ViewAction::ExecuteCommand(command) => self.execute_command(&command).await?,
+            ViewAction::ShowConfirmation {
+                message,
+                confirm_action,
+            } => {
+                self.modal = Some(Modal::confirm(
+                    confirm_action,
+                    message,
+                    self.workspaces.focused().current().clone(),
+                ))
+            }
+            ViewAction::ShowInput {
+                id,
+                prompt,
+                default,
+            } => {
+                self.modal = Some(Modal::input(
+                    id,
+                    prompt,
+                    default,
+                    self.workspaces.focused().current().clone(),
+                ))
+            }
+            ViewAction::ShowSelection { id, title, options } => {
+                self.modal = Some(Modal::select(
+                    id,
+                    title,
+                    options,
+                    self.workspaces.focused().current().clone(),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a command line typed after `:` or sent by a view.
+    async fn execute_command(&mut self, line: &str) -> Result<()> {
+        let command = match Command::parse(line) {
+            Ok(command) => command,
+            Err(err) => {
+                self.command_error(err);
                 return Ok(());
             }
-            _ => {}
+        };
+
+        match command {
+            Command::Start { descriptor, name } => {
+                self.control_dataflow(DataflowCommand::Start(StartDataflowRequest {
+                    descriptor_path: descriptor.into(),
+                    name,
+                    uv: false,
+                }))
+            }
+            Command::Stop(dataflow) => self.control_dataflow(DataflowCommand::Stop(dataflow)),
+            Command::Logs { dataflow, node } => {
+                let target = match node {
+                    Some(node) => format!("{dataflow}/{node}"),
+                    None => dataflow,
+                };
+                self.push_view(ViewType::LogViewer { target });
+            }
+            Command::Inspect { dataflow, node } => {
+                let dataflow_id = self
+                    .state
+                    .dataflows
+                    .iter()
+                    .find(|df| df.id == dataflow || df.name == dataflow)
+                    .map_or(dataflow, |df| df.id.clone());
+                self.push_view(ViewType::NodeInspector {
+                    dataflow_id,
+                    node_id: node,
+                });
+            }
+            Command::View(name) => match ViewType::from_name(&name) {
+                Some(view) => self.switch_view(view),
+                None => self.command_error(format!("unknown view `{name}`")),
+            },
+            // Loading the theme up front reports unknown names and broken
+            // files without touching the preferences.
+            Command::Theme(name) => match ThemeConfig::load_user_theme(&name) {
+                Ok(_) => self.set_user_preference("theme", &name),
+                Err(err) => self.command_error(err),
+            },
+            Command::Set { key, value } => self.set_user_preference(&key, &value),
+            Command::Split {
+                direction,
+                view: None,
+            } => self.workspaces.split(direction, None),
+            Command::Split {
+                direction,
+                view: Some(name),
+            } => match ViewType::from_name(&name) {
+                Some(view) => self.workspaces.split(direction, Some(view)),
+                None => self.command_error(format!("unknown view `{name}`")),
+            },
+            Command::NewTab(name) => self.workspaces.new_tab(name),
+            Command::RenameTab(name) => self.workspaces.rename_tab(name),
+            Command::CloseTab => self.apply_workspace_action(GlobalAction::CloseTab),
+            Command::Help => self.push_view(ViewType::Help),
+            Command::Quit => self.should_quit = true,
         }
 
-        // View navigation shortcuts
-        match key.code {
-            KeyCode::Char('1') => self.switch_view(ViewType::Dashboard),
-            KeyCode::Char('2') => self.switch_view(ViewType::DataflowManager),
-            KeyCode::Char('3') => self.switch_view(ViewType::SystemMonitor),
-            KeyCode::Char('4') => self.switch_view(ViewType::LogViewer {
-                target: "system".to_string(),
-            }),
-            KeyCode::Char('5') => self.switch_view(ViewType::SettingsManager),
-            KeyCode::Char('e') => self.switch_view(ViewType::DataflowExplorer),
-            _ => {}
+        Ok(())
+    }
+
+    async fn apply_view_state_update(&mut self, update: ViewStateUpdate) -> Result<()> {
+        match update {
+            ViewStateUpdate::RefreshDataflows => self
+                .tasks
+                .fetch_dataflows(Arc::clone(&self.coordinator_client)),
+            ViewStateUpdate::UpdateSystemMetrics => self
+                .tasks
+                .fetch_system_metrics(Arc::clone(&self.telemetry_service)),
+            ViewStateUpdate::ClearError => self.state.last_error = None,
+            ViewStateUpdate::SetUserPreference(key, value) => {
+                self.set_user_preference(&key, &value)
+            }
+            ViewStateUpdate::AddStatusMessage(message, level) => {
+                self.notify_from_view(message, level)
+            }
         }
 
         Ok(())
     }
 
+    fn set_user_preference(&mut self, key: &str, value: &str) {
+        self.tasks
+            .set_preference(Arc::clone(&self.preferences_store), key, value);
+    }
+
+    fn apply_preference_set(
+        &mut self,
+        key: String,
+        value: String,
+        result: std::result::Result<UserPreferencesSnapshot, String>,
+    ) {
+        let message = match result {
+            Ok(snapshot) => {
+                self.apply_user_preferences(Ok(snapshot));
+                StatusMessage::new(
+                    "preferences",
+                    format!("{key} set to {value}"),
+                    MessageLevel::Success,
+                )
+            }
+            Err(err) => StatusMessage::new("preferences", err, MessageLevel::Error),
+        };
+        self.notify(message);
+    }
+
     #[cfg_attr(not(test), allow(dead_code))]
     async fn process_state_update(&mut self, update: StateUpdate) -> Result<()> {
         match update {
@@ -833,15 +1664,11 @@ impl DoraApp {
                 }
                 self.state.dataflow_last_refresh = Some(Instant::now());
             }
-            StateUpdate::SystemMetricsUpdated => {
-                self.update_system_metrics().await?;
-            }
-            StateUpdate::ConfigurationChanged => {
-                self.apply_user_preferences();
-            }
-            StateUpdate::RefreshRequired => {
-                self.refresh_current_view_data().await?;
-            }
+            StateUpdate::SystemMetricsUpdated => self.update_system_metrics(),
+            StateUpdate::ConfigurationChanged => self
+                .tasks
+                .load_preferences(Arc::clone(&self.preferences_store)),
+            StateUpdate::RefreshRequired => self.refresh_current_view_data(),
         }
 
         Ok(())
@@ -852,26 +1679,39 @@ impl DoraApp {
         self.process_state_update(update).await
     }
 
-    #[cfg_attr(not(test), allow(dead_code))]
-    async fn refresh_current_view_data(&mut self) -> Result<()> {
+    #[cfg(test)]
+    pub async fn test_handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
+        self.handle_key_event(key).await
+    }
+
+    #[cfg(test)]
+    pub async fn test_handle_mouse_event(&mut self, event: MouseEvent) -> Result<()> {
+        self.handle_mouse_event(event).await
+    }
+
+    /// Start fetching what the current view shows; the results arrive
+    /// through `DataTasks`.
+    fn refresh_current_view_data(&mut self) {
         // Refresh data based on current view
-        match &self.current_view {
+        match self.workspaces.focused().current() {
             ViewType::Dashboard | ViewType::DataflowManager | ViewType::DataflowExplorer => {
-                self.refresh_dataflow_list().await?;
+                self.refresh_dataflow_list();
             }
             ViewType::SystemMonitor => {
-                self.update_system_metrics().await?;
+                self.update_system_metrics();
             }
             ViewType::NodeInspector { .. } => {
-                self.refresh_dataflow_list().await?;
+                self.refresh_dataflow_list();
             }
             _ => {
                 // Other views don't need specific refresh
             }
         }
-        Ok(())
     }
 
+    /// Periodic work: start fetches that are due for any visible view, let
+    /// the visible views update and expire status messages. Fetch results arrive later through
+    /// `DataTasks`.
     async fn update(&mut self) -> Result<()> {
         let now = Instant::now();
 
@@ -879,24 +1719,33 @@ impl DoraApp {
         let refreshed_from_stream = self.try_apply_metrics_from_cache();
         #[cfg(not(feature = "protocol"))]
         let refreshed_from_stream = false;
-
-        if matches!(
-            self.current_view,
-            ViewType::Dashboard
-                | ViewType::DataflowManager
-                | ViewType::DataflowExplorer
-                | ViewType::NodeInspector { .. }
-        ) {
+        self.dirty |= refreshed_from_stream;
+
+        let dataflows_visible = self.workspaces.visible_views().any(|view| {
+            matches!(
+                view,
+                ViewType::Dashboard
+                    | ViewType::DataflowManager
+                    | ViewType::DataflowExplorer
+                    | ViewType::NodeInspector { .. }
+            )
+        });
+        if dataflows_visible {
             let needs_refresh = self.state.dataflow_last_refresh.is_none_or(|last| {
                 now.duration_since(last) > Self::DATAFLOW_REFRESH_INTERVAL
             });
 
             if needs_refresh {
-                self.refresh_dataflow_list().await?;
+                self.tasks
+                    .fetch_dataflows(Arc::clone(&self.coordinator_client));
             }
         }
 
-        let metrics_interval = if matches!(self.current_view, ViewType::SystemMonitor) {
+        let monitor_visible = self
+            .workspaces
+            .visible_views()
+            .any(|view| matches!(view, ViewType::SystemMonitor));
+        let metrics_interval = if monitor_visible {
             Duration::from_secs(1)
         } else {
             self.state.user_config.auto_refresh_interval
@@ -909,117 +1758,326 @@ impl DoraApp {
             .is_none_or(|last| now.duration_since(last) > metrics_interval);
 
         if needs_poll && !refreshed_from_stream {
-            self.update_system_metrics().await?;
+            self.tasks
+                .fetch_system_metrics(Arc::clone(&self.telemetry_service));
+        }
+
+        self.dirty |= self.workspaces.update(&mut self.state).await?;
+
+        // A closed log viewer's stream is no longer anyone's concern.
+        let logs_visible = self
+            .workspaces
+            .visible_views()
+            .any(|view| matches!(view, ViewType::LogViewer { .. }));
+        if !logs_visible {
+            self.state.connection.forget(Link::LogStream);
         }
 
-        // Clear old status messages
-        self.state
-            .status_messages
-            .retain(|msg| now.duration_since(msg.timestamp) < Duration::from_secs(5));
+        // Redraw when a toast expires
+        self.dirty |= self.state.toasts(now).count() != self.drawn_toasts;
 
         Ok(())
     }
 
     fn view_title(&self) -> String {
-        match &self.current_view {
-            ViewType::Dashboard => "Dashboard".to_string(),
-            ViewType::DataflowManager => "Dataflow Manager".to_string(),
-            ViewType::DataflowExplorer => "Dataflow Explorer".to_string(),
-            ViewType::NodeInspector {
-                dataflow_id,
-                node_id,
-            } => format!("Node Inspector: {node_id} @ {dataflow_id}"),
-            ViewType::SystemMonitor => "System Monitor".to_string(),
-            ViewType::LogViewer { target } => format!("Logs: {target}"),
-            ViewType::RecordingAnalyzer { recording_id } => format!("Recording: {recording_id}"),
-            ViewType::DebugSession { dataflow_id } => format!("Debug: {dataflow_id}"),
-            ViewType::SettingsManager => "Settings".to_string(),
-            ViewType::Help => "Help".to_string(),
-        }
+        self.workspaces.focused().current().title()
     }
 
     pub fn switch_view(&mut self, view_type: ViewType) {
-        self.current_view = view_type;
+        self.workspaces.focused_mut().switch(view_type);
     }
 
     pub fn push_view(&mut self, view_type: ViewType) {
-        self.view_stack
-            .push(std::mem::replace(&mut self.current_view, view_type));
+        self.workspaces.focused_mut().push(view_type);
     }
 
     pub fn pop_view(&mut self) {
-        if let Some(previous_view) = self.view_stack.pop() {
-            self.current_view = previous_view;
-        }
+        self.workspaces.focused_mut().pop();
     }
 
     pub fn user_config(&self) -> &UserConfig {
         &self.state.user_config
     }
 
-    async fn refresh_dataflow_list(&mut self) -> Result<()> {
-        let client = Arc::clone(&self.coordinator_client);
-        match tokio::task::spawn_blocking(move || client.list_dataflows()).await {
-            Ok(Ok(dataflows)) => {
+    fn refresh_dataflow_list(&mut self) {
+        self.tasks
+            .fetch_dataflows(Arc::clone(&self.coordinator_client));
+    }
+
+    fn apply_dataflows(
+        &mut self,
+        result: std::result::Result<Vec<DataflowInfo>, String>,
+        latency: Duration,
+    ) {
+        let mut changed;
+        match result {
+            Ok(dataflows) => {
+                changed = self.record_connection(Link::Gateway, "coordinator", Ok(latency));
+                self.notify_transitions(&dataflows);
+                changed |= self.state.dataflows != dataflows;
                 self.state.dataflows = dataflows;
-                self.state.last_error = None;
-            }
-            Ok(Err(err)) => {
-                let message = format!("failed to fetch dataflows: {err}");
-                self.show_status_message(format!("❌ {message}"), MessageLevel::Error);
-                self.state.last_error = Some(message);
+                changed |= self.state.last_error.take().is_some();
+                let events = self
+                    .state
+                    .alerts
+                    .evaluate_dataflows(&self.state.dataflows, Instant::now());
+                self.announce_alerts(events);
+                if std::mem::take(&mut self.check_restored_views) {
+                    for dropped in self.workspaces.drop_stale_views(&self.state) {
+                        self.notify(StatusMessage::new(
+                            "session",
+                            format!("⚠️ {dropped} from the last session no longer exists"),
+                            MessageLevel::Warning,
+                        ));
+                    }
+                }
             }
-            Err(err) => {
-                let message = format!("coordinator query task failed: {err}");
-                self.show_status_message(format!("❌ {message}"), MessageLevel::Error);
+            Err(message) => {
+                changed = self.record_connection(Link::Gateway, "coordinator", Err(&message));
+                changed |= self.state.last_error.as_ref() != Some(&message);
                 self.state.last_error = Some(message);
             }
         }
         self.state.dataflow_last_refresh = Some(Instant::now());
-
-        Ok(())
+        self.dirty |= changed;
     }
 
-    async fn update_system_metrics(&mut self) -> Result<()> {
+    fn update_system_metrics(&mut self) {
         #[cfg(feature = "protocol")]
         if self.try_apply_metrics_from_cache() {
-            return Ok(());
+            return;
         }
 
-        let service = Arc::clone(&self.telemetry_service);
-        match tokio::task::spawn_blocking(move || service.latest_metrics()).await {
-            Ok(Ok(metrics)) => {
+        self.tasks
+            .fetch_system_metrics(Arc::clone(&self.telemetry_service));
+    }
+
+    fn apply_system_metrics(
+        &mut self,
+        result: std::result::Result<SystemMetrics, String>,
+        latency: Duration,
+    ) {
+        let changed = match result {
+            Ok(metrics) => {
+                let mut changed = self.record_connection(Link::Telemetry, "telemetry", Ok(latency));
+                // Every sample has a new collection time; only the readings
+                // show.
+                changed |= SystemMetrics {
+                    last_update: self.state.system_metrics.last_update,
+                    ..metrics.clone()
+                } != self.state.system_metrics;
                 self.state.system_metrics = metrics.clone();
                 self.state.record_system_metrics(&metrics);
-                self.state.last_error = None;
+                changed |= self.state.last_error.take().is_some();
+                self.check_metric_alerts();
+                changed
             }
-            Ok(Err(err)) => {
-                self.show_status_message(
-                    format!("❌ failed to collect system metrics: {err}"),
-                    MessageLevel::Error,
+            Err(message) => self.record_connection(Link::Telemetry, "telemetry", Err(&message)),
+        };
+        self.dirty |= changed;
+    }
+
+    /// Send notifications for dataflows that failed and nodes that came
+    /// back since the last refresh. Dataflows seen for the first time are
+    /// not compared.
+    fn notify_transitions(&mut self, dataflows: &[DataflowInfo]) {
+        let now = Instant::now();
+        for dataflow in dataflows {
+            let Some(previous) = self.state.dataflows.iter().find(|df| df.id == dataflow.id) else {
+                continue;
+            };
+            if dataflow.status.is_failed() && !previous.status.is_failed() {
+                self.notifier.notify(
+                    NotifyEvent::DataflowFailed,
+                    &format!("dataflow {} failed", dataflow.name),
+                    now,
                 );
             }
-            Err(err) => {
-                self.show_status_message(
-                    format!("❌ telemetry task failed: {err}"),
-                    MessageLevel::Error,
+            for node in &dataflow.nodes {
+                let was_down = previous
+                    .nodes
+                    .iter()
+                    .find(|prev| prev.id == node.id)
+                    .is_some_and(|prev| {
+                        matches!(
+                            prev.status,
+                            NodeStatus::Failed | NodeStatus::Exited | NodeStatus::Stopped
+                        )
+                    });
+                if was_down && node.status.is_running() {
+                    self.notifier.notify(
+                        NotifyEvent::NodeRestarted,
+                        &format!("node {} of {} restarted", node.id, dataflow.name),
+                        now,
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_metric_alerts(&mut self) {
+        let readings = alerts::readings(&self.state);
+        let events = self
+            .state
+            .alerts
+            .evaluate_metrics(&readings, Instant::now());
+        self.announce_alerts(events);
+    }
+
+    fn announce_alerts(&mut self, events: Vec<AlertEvent>) {
+        for event in events {
+            if let AlertEvent::Raised(alert) = &event {
+                self.notifier.notify(
+                    NotifyEvent::AlertRaised,
+                    &format!("{}: {}", alert.title, alert.message),
+                    Instant::now(),
                 );
             }
+            let message = match event {
+                AlertEvent::Raised(alert) => StatusMessage::new(
+                    "alerts",
+                    format!("{} {}: {}", alert.level.icon(), alert.title, alert.message),
+                    match alert.level {
+                        AlertLevel::Info => MessageLevel::Info,
+                        AlertLevel::Warning => MessageLevel::Warning,
+                        AlertLevel::Error | AlertLevel::Critical => MessageLevel::Error,
+                    },
+                ),
+                AlertEvent::Resolved(alert) => StatusMessage::new(
+                    "alerts",
+                    format!("✅ resolved {}: {}", alert.title, alert.message),
+                    MessageLevel::Success,
+                ),
+            };
+            self.notify(message);
         }
+    }
 
-        Ok(())
+    /// Record a request against `link`. Only the first failure of an outage
+    /// and the recovery are announced; the banner covers the retries in
+    /// between. Returns whether the link's entry in the status bar changed.
+    fn record_connection(
+        &mut self,
+        link: Link,
+        source: &str,
+        outcome: std::result::Result<Duration, &str>,
+    ) -> bool {
+        let shown = |app: &Self| {
+            app.state.connection.health(link).map(|health| {
+                (
+                    health.status(),
+                    health.latency.map(|latency| latency.as_millis()),
+                    health.consecutive_failures,
+                    health.last_error.clone(),
+                )
+            })
+        };
+        let before = shown(self);
+        let transition = match outcome {
+            Ok(latency) => {
+                self.state
+                    .connection
+                    .record_success(link, Some(latency), Instant::now())
+            }
+            Err(message) => self.state.connection.record_failure(link, message),
+        };
+        let message = match (transition, outcome) {
+            (Transition::Lost, Err(message)) => {
+                StatusMessage::new(source, format!("❌ {message}"), MessageLevel::Error)
+            }
+            (Transition::Restored, _) => StatusMessage::new(
+                source,
+                format!("✅ {link} reachable again"),
+                MessageLevel::Success,
+            ),
+            _ => return shown(self) != before,
+        };
+        self.notify(message);
+        true
+    }
+
+    /// Submit a lifecycle command in the background. The outcome arrives
+    /// through `DataTasks`.
+    pub fn control_dataflow(&mut self, command: DataflowCommand) {
+        let Some(controller) = self.dataflow_controller.clone() else {
+            self.show_status_message(
+                "❌ dataflow control is not available in this session".to_string(),
+                MessageLevel::Error,
+            );
+            return;
+        };
+        self.tasks.control_dataflow(controller, command);
+    }
+
+    /// Report a finished lifecycle command and refresh the dataflow list
+    /// once the backend has accepted it.
+    fn apply_control_result(
+        &mut self,
+        command: DataflowCommand,
+        result: std::result::Result<OperationHandle, String>,
+    ) {
+        let verb = command.verb();
+        let target = command.target();
+        // A start without a name targets a descriptor, not a dataflow.
+        let dataflow = match &command {
+            DataflowCommand::Start(request) => request.name.clone(),
+            DataflowCommand::Stop(dataflow) | DataflowCommand::Destroy(dataflow) => {
+                Some(dataflow.clone())
+            }
+        }
+        .map(MessageTarget::Dataflow);
+
+        let (message, level) = match result {
+            Ok(handle) => (
+                format!("✅ {verb} requested for {target} ({})", handle.id),
+                MessageLevel::Success,
+            ),
+            Err(err) => (format!("❌ {err}"), MessageLevel::Error),
+        };
+        let accepted = level == MessageLevel::Success;
+        self.notify(StatusMessage::new("control", message, level).with_target(dataflow));
+        if accepted {
+            self.refresh_dataflow_list();
+        }
+    }
+
+    /// Record a message; it shows as a toast and stays in the
+    /// notification center.
+    pub fn notify(&mut self, message: StatusMessage) {
+        self.state.push_status_message(message);
+        self.dirty = true;
     }
 
     pub fn show_status_message(&mut self, message: String, level: MessageLevel) {
-        self.state.status_messages.push_back(StatusMessage {
-            message,
-            level,
-            timestamp: Instant::now(),
-        });
+        self.notify(StatusMessage::new("app", message, level));
+    }
+
+    fn command_error(&mut self, message: String) {
+        self.notify(StatusMessage::new("command", message, MessageLevel::Error));
+    }
 
-        // Keep only the last 10 messages
-        while self.state.status_messages.len() > 10 {
-            self.state.status_messages.pop_front();
+    /// A message reported by the active view, about what it is showing.
+    fn notify_from_view(&mut self, message: String, level: MessageLevel) {
+        let view = self.workspaces.focused().current();
+        let target = MessageTarget::for_view(view);
+        let source = self.view_title();
+        self.notify(StatusMessage::new(source, message, level).with_target(target));
+    }
+
+    /// Open the explorer with `dataflow` selected.
+    fn show_dataflow(&mut self, dataflow: &str) {
+        if self.workspaces.focused().current() != &ViewType::DataflowExplorer {
+            self.push_view(ViewType::DataflowExplorer);
+        }
+        if !self
+            .workspaces
+            .focused_mut()
+            .select_dataflow(dataflow, &self.state)
+        {
+            self.show_status_message(
+                format!("dataflow {dataflow} is no longer listed"),
+                MessageLevel::Warning,
+            );
         }
     }
 
@@ -1030,7 +2088,7 @@ impl DoraApp {
     // Test helper methods
     #[cfg(test)]
     pub fn current_view(&self) -> &ViewType {
-        &self.current_view
+        self.workspaces.focused().current()
     }
 
     #[cfg(test)]
@@ -1040,7 +2098,17 @@ impl DoraApp {
 
     #[cfg(test)]
     pub fn view_stack_len(&self) -> usize {
-        self.view_stack.len()
+        self.workspaces.focused().stack_len()
+    }
+
+    #[cfg(test)]
+    pub fn workspaces(&self) -> &Workspaces {
+        &self.workspaces
+    }
+
+    #[cfg(test)]
+    pub fn has_modal(&self) -> bool {
+        self.modal.is_some()
     }
 
     #[cfg(test)]
@@ -1053,9 +2121,64 @@ impl DoraApp {
         self.state.dataflow_last_refresh
     }
 
+    #[cfg(test)]
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    #[cfg(test)]
+    pub fn take_notifications(&mut self) -> Vec<String> {
+        self.notifier.take_pending()
+    }
+
+    #[cfg(test)]
+    pub fn last_status_message(&self) -> Option<&StatusMessage> {
+        self.state.status_messages.back()
+    }
+
+    /// The dataflow selected in the active view, if it lists dataflows.
+    #[cfg(test)]
+    pub fn selected_dataflow(&mut self) -> Option<String> {
+        self.workspaces.focused_mut().selected_dataflow(&self.state)
+    }
+
+    #[cfg(test)]
+    pub fn test_save_session(&self) {
+        self.save_session();
+    }
+
+    #[cfg(test)]
+    pub fn test_draw(&mut self, f: &mut Frame) {
+        self.ui(f);
+    }
+
+    #[cfg(test)]
+    pub fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    #[cfg(test)]
+    pub fn mark_drawn(&mut self) {
+        self.dirty = false;
+    }
+
+    #[cfg(test)]
+    pub async fn test_tick(&mut self) -> Result<()> {
+        self.update().await
+    }
+
+    /// Wait until every background fetch has reported back.
+    #[cfg(test)]
+    pub async fn test_finish_fetches(&mut self) {
+        while !self.tasks.is_idle() {
+            let update = self.tasks.recv().await;
+            self.apply_data_update(update);
+        }
+    }
+
     #[cfg(feature = "protocol")]
     fn try_apply_metrics_from_cache(&mut self) -> bool {
-        if let Some(cache) = &self.metrics_cache {
+        if let Some(cache) = self.metrics_cache.clone() {
             if let Ok(guard) = cache.lock() {
                 if let Some(latest) = guard.as_ref() {
                     if AppState::should_apply_metrics(
@@ -1067,6 +2190,12 @@ impl DoraApp {
                         self.state.system_metrics = snapshot.clone();
                         self.state.record_system_metrics(&snapshot);
                         self.state.last_error = None;
+                        self.state.connection.record_success(
+                            Link::MetricsStream,
+                            None,
+                            Instant::now(),
+                        );
+                        self.check_metric_alerts();
                         return true;
                     }
                 }
@@ -1075,24 +2204,3 @@ impl DoraApp {
         false
     }
 }
-
-// Helper function to create a centered rectangle
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
@@ -1,14 +1,24 @@
 use std::{sync::Arc, time::Instant};
 
 #[cfg(feature = "protocol")]
-use std::{
-    sync::{Mutex, mpsc},
-    thread,
+use std::{sync::Mutex, thread};
+
+use tui_interface::{
+    CoordinatorClient, DataflowController, LegacyCliService, LogService, PreferencesStore,
+    TelemetryService,
+};
+#[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
+use tui_interface::{
+    InterfaceError, OperationHandle, OperationState, OperationStatus, StartDataflowRequest,
+    UserPreferencesSnapshot,
 };
 
-use tui_interface::{CoordinatorClient, LegacyCliService, PreferencesStore, TelemetryService};
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
-use tui_interface::{InterfaceError, UserPreferencesSnapshot};
+use std::{
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
 use crate::{
@@ -16,7 +26,7 @@ use crate::{
     common::{connect_to_coordinator, query_running_dataflows},
     config::preferences::UserPreferences,
     execute_legacy_command,
-    tui::{app::dataflow_from_entry, metrics::MetricsCollector},
+    tui::{app::dataflow_from_entry, log_files::LocalLogService, metrics::MetricsCollector},
 };
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
@@ -25,19 +35,23 @@ use dora_core::topics::DORA_COORDINATOR_PORT_CONTROL_DEFAULT;
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
 use tui_interface::DataflowSummary;
 
+#[cfg(any(feature = "tui-cli-services", feature = "protocol"))]
+use crate::tui::preferences::{LayeredPreferencesStore, PreferencesOverrides};
+#[cfg(feature = "protocol")]
+use crate::tui::telemetry::CompositeTelemetryService;
 #[cfg(feature = "protocol")]
 use dora_protocol::SystemMetrics as ProtocolSystemMetrics;
 #[cfg(feature = "protocol")]
 use dora_protocol_client::ProtocolClients;
 #[cfg(feature = "protocol")]
-use tracing::{error, warn};
-#[cfg(feature = "protocol")]
-use uuid::Uuid;
+use tracing::warn;
 
 #[cfg(any(feature = "tui-cli-services", feature = "protocol"))]
 pub struct ServiceBundle {
     pub preferences_store: Arc<dyn PreferencesStore>,
     pub coordinator_client: Arc<dyn CoordinatorClient>,
+    pub dataflow_controller: Arc<dyn DataflowController>,
+    pub log_service: Arc<dyn LogService>,
     pub telemetry_service: Arc<dyn TelemetryService>,
     pub legacy_cli_service: Arc<dyn LegacyCliService>,
     #[cfg(feature = "protocol")]
@@ -46,34 +60,61 @@ pub struct ServiceBundle {
     pub metrics_cache: Arc<Mutex<Option<tui_interface::SystemMetrics>>>,
 }
 
+#[cfg(any(feature = "tui-cli-services", feature = "protocol"))]
+pub fn default_service_bundle() -> Result<ServiceBundle, String> {
+    service_bundle(PreferencesOverrides::default())
+}
+
+/// Services for a session, with preferences given on the command line
+/// taking priority over every stored layer.
 #[cfg(all(not(feature = "protocol"), feature = "tui-cli-services"))]
-pub fn default_service_bundle() -> ServiceBundle {
-    ServiceBundle {
-        preferences_store: Arc::new(CliPreferencesStore),
+pub fn service_bundle(cli_preferences: PreferencesOverrides) -> Result<ServiceBundle, String> {
+    Ok(ServiceBundle {
+        preferences_store: Arc::new(
+            LayeredPreferencesStore::new(Some(Arc::new(CliPreferencesStore)))
+                .with_cli_overrides(cli_preferences),
+        ),
         coordinator_client: Arc::new(CliCoordinatorClient),
+        dataflow_controller: Arc::new(CliDataflowController::default()),
+        log_service: Arc::new(LocalLogService::new(
+            std::env::current_dir().unwrap_or_default().join("out"),
+        )),
         telemetry_service: Arc::new(CliTelemetryService::default()),
         legacy_cli_service: Arc::new(CliLegacyCliService),
-    }
+    })
 }
 
+/// Services for a session, with preferences given on the command line
+/// taking priority over every stored layer. Fails if the gateway URL is
+/// invalid.
 #[cfg(feature = "protocol")]
-pub fn default_service_bundle() -> ServiceBundle {
+pub fn service_bundle(cli_preferences: PreferencesOverrides) -> Result<ServiceBundle, String> {
     let base_url =
         std::env::var("DORA_PROTOCOL_URL").unwrap_or_else(|_| "http://127.0.0.1:7267".to_string());
-    let clients = Arc::new(ProtocolClients::new(&base_url).unwrap_or_else(|err| {
-        panic!("failed to initialize protocol clients for {base_url}: {err}")
-    }));
+    let clients = Arc::new(
+        ProtocolClients::new(&base_url)
+            .map_err(|err| format!("invalid protocol gateway URL {base_url}: {err}"))?,
+    );
 
-    let metrics_cache = spawn_metrics_stream(Arc::clone(&clients));
+    let telemetry = Arc::new(CompositeTelemetryService::for_gateway(
+        clients.telemetry_service(),
+        &base_url,
+    ));
+    let metrics_cache = spawn_metrics_stream(Arc::clone(&clients), Arc::clone(&telemetry));
 
-    ServiceBundle {
-        preferences_store: clients.preferences_store(),
+    Ok(ServiceBundle {
+        preferences_store: Arc::new(
+            LayeredPreferencesStore::new(Some(clients.preferences_store()))
+                .with_cli_overrides(cli_preferences),
+        ),
         coordinator_client: clients.coordinator_client(),
-        telemetry_service: clients.telemetry_service(),
+        dataflow_controller: clients.dataflow_controller(),
+        log_service: clients.log_service(),
+        telemetry_service: telemetry,
         legacy_cli_service: clients.legacy_cli_service(),
         protocol_clients: clients,
         metrics_cache,
-    }
+    })
 }
 
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
@@ -91,6 +132,10 @@ impl PreferencesStore for CliPreferencesStore {
             auto_refresh_interval_secs: prefs.interface.tui.auto_refresh_interval.as_secs(),
             show_system_info: prefs.interface.hints.show_hints,
             default_view: Some(prefs.interface.tui.default_view.clone()),
+            ui_mode: None,
+            mouse_capture: true,
+            accessible: false,
+            notify_on: Vec::new(),
         })
     }
 
@@ -130,6 +175,99 @@ impl CoordinatorClient for CliCoordinatorClient {
     }
 }
 
+/// Lifecycle control through the legacy CLI commands.
+///
+/// CLI commands return once the coordinator has acknowledged them, so every
+/// operation has completed by the time its handle is returned. Nothing is
+/// kept per operation; any handle this controller issued reports completed.
+#[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
+#[derive(Default, Debug)]
+struct CliDataflowController {
+    next_operation: AtomicU64,
+}
+
+#[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
+impl CliDataflowController {
+    fn run(&self, argv: &[String], working_dir: &Path) -> Result<OperationHandle, InterfaceError> {
+        execute_legacy_command(argv.iter().map(|s| s.as_str()), Some(working_dir))
+            .map_err(|err| InterfaceError::from(err.to_string()))?;
+
+        Ok(OperationHandle {
+            id: format!(
+                "cli-{}",
+                self.next_operation.fetch_add(1, Ordering::Relaxed)
+            ),
+        })
+    }
+}
+
+#[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
+impl DataflowController for CliDataflowController {
+    fn start(&self, request: &StartDataflowRequest) -> Result<OperationHandle, InterfaceError> {
+        let mut argv = vec![
+            "start".to_string(),
+            request.descriptor_path.display().to_string(),
+            "--detach".to_string(),
+        ];
+        if let Some(name) = &request.name {
+            argv.push("--name".to_string());
+            argv.push(name.clone());
+        }
+        if request.uv {
+            argv.push("--uv".to_string());
+        }
+
+        let working_dir = request
+            .descriptor_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+        self.run(&argv, &working_dir)
+    }
+
+    fn stop(&self, dataflow: &str) -> Result<OperationHandle, InterfaceError> {
+        let argv = if uuid::Uuid::parse_str(dataflow).is_ok() {
+            vec!["stop".to_string(), dataflow.to_string()]
+        } else {
+            vec![
+                "stop".to_string(),
+                "--name".to_string(),
+                dataflow.to_string(),
+            ]
+        };
+        self.run(&argv, &std::env::current_dir().unwrap_or_default())
+    }
+
+    fn destroy(&self, _dataflow: &str) -> Result<OperationHandle, InterfaceError> {
+        // `dora destroy` tears down the whole coordinator and daemons; there is
+        // no per-dataflow equivalent in the legacy CLI.
+        Err(InterfaceError::Unimplemented)
+    }
+
+    fn operation_status(
+        &self,
+        handle: &OperationHandle,
+    ) -> Result<OperationStatus, InterfaceError> {
+        let issued = handle
+            .id
+            .strip_prefix("cli-")
+            .and_then(|number| number.parse::<u64>().ok())
+            .is_some_and(|number| number < self.next_operation.load(Ordering::Relaxed));
+        if !issued {
+            return Err(InterfaceError::from(format!(
+                "unknown operation {}",
+                handle.id
+            )));
+        }
+        Ok(OperationStatus {
+            handle: handle.clone(),
+            state: OperationState::Completed,
+            message: None,
+        })
+    }
+}
+
 #[cfg(all(feature = "tui-cli-services", not(feature = "protocol")))]
 #[derive(Default, Debug)]
 pub struct CliTelemetryService;
@@ -163,6 +301,7 @@ impl LegacyCliService for CliLegacyCliService {
 #[cfg(feature = "protocol")]
 fn spawn_metrics_stream(
     clients: Arc<ProtocolClients>,
+    telemetry: Arc<CompositeTelemetryService>,
 ) -> Arc<Mutex<Option<tui_interface::SystemMetrics>>> {
     let cache = Arc::new(Mutex::new(None));
     let cache_clone = Arc::clone(&cache);
@@ -172,7 +311,7 @@ fn spawn_metrics_stream(
             for next in stream {
                 match next {
                     Ok(raw) => {
-                        let metrics = convert_metrics(&raw);
+                        let metrics = telemetry.complete(convert_metrics(&raw));
                         if let Ok(mut guard) = cache_clone.lock() {
                             *guard = Some(metrics);
                         }
@@ -214,38 +353,7 @@ fn convert_metrics(protocol: &ProtocolSystemMetrics) -> tui_interface::SystemMet
         },
         load_average,
         last_update: Some(Instant::now()),
+        source: tui_interface::MetricsSource::Gateway,
         ..Default::default()
     }
 }
-
-#[cfg(feature = "protocol")]
-pub fn spawn_protocol_log_stream(
-    clients: Arc<ProtocolClients>,
-    dataflow_id: Uuid,
-) -> Option<mpsc::Receiver<dora_protocol::LogEvent>> {
-    match clients.log_stream(&dataflow_id) {
-        Ok(stream) => {
-            let (tx, rx) = mpsc::channel();
-            thread::spawn(move || {
-                for item in stream {
-                    match item {
-                        Ok(event) => {
-                            if tx.send(event).is_err() {
-                                break;
-                            }
-                        }
-                        Err(err) => {
-                            warn!(target: "tui", "log stream terminated: {err}");
-                            break;
-                        }
-                    }
-                }
-            });
-            Some(rx)
-        }
-        Err(err) => {
-            error!(target: "tui", "failed to open log stream: {err}");
-            None
-        }
-    }
-}
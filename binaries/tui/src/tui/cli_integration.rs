@@ -2,7 +2,13 @@
 
 use std::{collections::HashMap, path::PathBuf};
 
-use super::app::UserConfig;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{
+    app::{DataflowInfo, UserConfig},
+    command_executor,
+    modal::TextInput,
+};
 
 #[derive(Debug, Clone)]
 pub struct CliContext {
@@ -29,8 +35,253 @@ impl Default for CliContext {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum CommandMode {
     #[default]
     Normal,
+    /// The `:` command line has focus.
+    Command,
+}
+
+const HISTORY_LIMIT: usize = 100;
+
+/// What a key did to the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandLineEvent {
+    Editing,
+    Submitted(String),
+    Cancelled,
+}
+
+/// The `:` command line: text input, history and tab completion.
+#[derive(Debug, Clone, Default)]
+pub struct CommandLine {
+    input: TextInput,
+    history: Vec<String>,
+    /// Position while browsing history with Up/Down.
+    history_index: Option<usize>,
+    /// What was typed before browsing history.
+    draft: String,
+    completion: Option<Completion>,
+}
+
+#[derive(Debug, Clone)]
+struct Completion {
+    /// The line without the word being completed.
+    head: String,
+    candidates: Vec<String>,
+    /// Candidate shown by the last Tab; `None` until Tab cycles.
+    index: Option<usize>,
+}
+
+impl CommandLine {
+    /// Start a new, empty line. History is kept.
+    pub fn open(&mut self) {
+        self.input = TextInput::default();
+        self.history_index = None;
+        self.completion = None;
+    }
+
+    pub fn input(&self) -> &TextInput {
+        &self.input
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Candidates offered by the last Tab, if there was more than one.
+    pub fn candidates(&self) -> &[String] {
+        self.completion
+            .as_ref()
+            .map_or(&[], |completion| &completion.candidates)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, dataflows: &[DataflowInfo]) -> CommandLineEvent {
+        if key.code != KeyCode::Tab {
+            self.completion = None;
+        }
+
+        match key.code {
+            KeyCode::Esc => return CommandLineEvent::Cancelled,
+            KeyCode::Enter => {
+                let line = self.input.value().trim().to_string();
+                if line.is_empty() {
+                    return CommandLineEvent::Cancelled;
+                }
+                if self.history.last() != Some(&line) {
+                    self.history.push(line.clone());
+                    if self.history.len() > HISTORY_LIMIT {
+                        self.history.remove(0);
+                    }
+                }
+                return CommandLineEvent::Submitted(line);
+            }
+            // Backspace on an empty line leaves command mode, as in vim.
+            KeyCode::Backspace if self.input.value().is_empty() => {
+                return CommandLineEvent::Cancelled;
+            }
+            KeyCode::Tab => self.complete(dataflows),
+            KeyCode::Up => self.browse_history(true),
+            KeyCode::Down => self.browse_history(false),
+            _ => {
+                if self.input.handle_key(key) {
+                    self.history_index = None;
+                }
+            }
+        }
+        CommandLineEvent::Editing
+    }
+
+    fn complete(&mut self, dataflows: &[DataflowInfo]) {
+        if let Some(completion) = self.completion.as_mut() {
+            let next = completion
+                .index
+                .map_or(0, |index| (index + 1) % completion.candidates.len());
+            completion.index = Some(next);
+            let line = format!(
+                "{}{}",
+                completion.head,
+                shell_words::quote(&completion.candidates[next])
+            );
+            self.input.set_value(line);
+            return;
+        }
+
+        let line = self.input.value();
+        let candidates = command_executor::complete(line, dataflows);
+        let mut words = shell_words::split(line)
+            .or_else(|_| shell_words::split(&format!("{line}\"")))
+            .unwrap_or_default();
+        if !line.is_empty() && !line.ends_with(char::is_whitespace) {
+            words.pop();
+        }
+        let head = if words.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", shell_words::join(&words))
+        };
+
+        match candidates.as_slice() {
+            [] => {}
+            [only] => self
+                .input
+                .set_value(format!("{head}{} ", shell_words::quote(only))),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.as_str(), |common, candidate| {
+                    let len = common
+                        .char_indices()
+                        .zip(candidate.chars())
+                        .take_while(|((_, a), b)| a == b)
+                        .last()
+                        .map_or(0, |((index, c), _)| index + c.len_utf8());
+                    &common[..len]
+                });
+                self.input
+                    .set_value(format!("{head}{}", shell_words::quote(common)));
+                self.completion = Some(Completion {
+                    head,
+                    candidates,
+                    index: None,
+                });
+            }
+        }
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, older) {
+            (None, true) => {
+                self.draft = self.input.value().to_string();
+                Some(self.history.len() - 1)
+            }
+            (None, false) => return,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < self.history.len() => Some(index + 1),
+            (Some(_), false) => None,
+        };
+
+        self.history_index = index;
+        match index {
+            Some(index) => self.input.set_value(self.history[index].clone()),
+            None => self.input.set_value(std::mem::take(&mut self.draft)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use tui_interface::DataflowStatus;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_line(line: &mut CommandLine, text: &str) {
+        for c in text.chars() {
+            line.handle_key(key(KeyCode::Char(c)), &[]);
+        }
+    }
+
+    #[test]
+    fn tab_completes_and_cycles() {
+        let dataflows = vec![
+            DataflowInfo {
+                id: "df-1".to_string(),
+                name: "detector".to_string(),
+                status: DataflowStatus::Running,
+                ..Default::default()
+            },
+            DataflowInfo {
+                id: "df-2".to_string(),
+                name: "depth camera".to_string(),
+                status: DataflowStatus::Running,
+                ..Default::default()
+            },
+        ];
+        let mut line = CommandLine::default();
+
+        type_line(&mut line, "sto");
+        line.handle_key(key(KeyCode::Tab), &dataflows);
+        assert_eq!(line.input().value(), "stop ");
+
+        type_line(&mut line, "de");
+        line.handle_key(key(KeyCode::Tab), &dataflows);
+        assert_eq!(line.input().value(), "stop de");
+        assert_eq!(line.candidates().len(), 2);
+
+        line.handle_key(key(KeyCode::Tab), &dataflows);
+        assert_eq!(line.input().value(), "stop 'depth camera'");
+        line.handle_key(key(KeyCode::Tab), &dataflows);
+        assert_eq!(line.input().value(), "stop detector");
+    }
+
+    #[test]
+    fn history_recalls_submitted_lines() {
+        let mut line = CommandLine::default();
+        for command in ["view logs", "theme light", "theme light"] {
+            line.open();
+            type_line(&mut line, command);
+            assert_eq!(
+                line.handle_key(key(KeyCode::Enter), &[]),
+                CommandLineEvent::Submitted(command.to_string())
+            );
+        }
+        assert_eq!(line.history().len(), 2);
+
+        line.open();
+        type_line(&mut line, "dr");
+        line.handle_key(key(KeyCode::Up), &[]);
+        assert_eq!(line.input().value(), "theme light");
+        line.handle_key(key(KeyCode::Up), &[]);
+        line.handle_key(key(KeyCode::Up), &[]);
+        assert_eq!(line.input().value(), "view logs");
+        line.handle_key(key(KeyCode::Down), &[]);
+        line.handle_key(key(KeyCode::Down), &[]);
+        assert_eq!(line.input().value(), "dr");
+    }
 }
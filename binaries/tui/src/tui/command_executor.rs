@@ -1,17 +1,333 @@
+use super::{notifier::NotifyEvent, theme::ThemeConfig, workspace::SplitDirection};
+
 #[derive(Debug, Clone)]
 pub enum StateUpdate {
     DataflowAdded(super::app::DataflowInfo),
     DataflowRemoved(String),
     DataflowStatusChanged {
         name: String,
-        new_status: String,
+        new_status: tui_interface::DataflowStatus,
     },
     NodeStatusChanged {
         dataflow: String,
         node: String,
-        status: String,
+        status: tui_interface::NodeStatus,
     },
     SystemMetricsUpdated,
     ConfigurationChanged,
     RefreshRequired,
 }
+
+/// Commands accepted on the `:` command line, with their usage.
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("start", "start <descriptor> [--name <name>]"),
+    ("stop", "stop <dataflow>"),
+    ("logs", "logs <dataflow> [node]"),
+    ("inspect", "inspect <dataflow> <node>"),
+    ("view", "view <name>"),
+    ("theme", "theme <name>"),
+    ("set", "set <key> <value>"),
+    ("split", "split <right|down> [view]"),
+    ("tab", "tab <new [name]|rename <name>|close>"),
+    ("help", "help"),
+    ("quit", "quit"),
+];
+
+/// View names accepted by `view`.
+pub const VIEW_NAMES: &[&str] = &[
+    "dashboard",
+    "dataflow",
+    "explorer",
+    "monitor",
+    "performance",
+    "logs",
+    "notifications",
+    "settings",
+    "help",
+];
+
+/// Preferences accepted by `set`.
+pub const PREFERENCE_KEYS: &[&str] = &[
+    "accessible",
+    "default_view",
+    "mouse_capture",
+    "notify_on",
+    "refresh_interval",
+    "show_system_info",
+    "theme",
+];
+
+/// A parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Start the dataflow described by a descriptor file.
+    Start {
+        descriptor: String,
+        name: Option<String>,
+    },
+    Stop(String),
+    Logs {
+        dataflow: String,
+        node: Option<String>,
+    },
+    Inspect {
+        dataflow: String,
+        node: String,
+    },
+    View(String),
+    Theme(String),
+    /// Save a preference, e.g. `set accessible true`.
+    Set {
+        key: String,
+        value: String,
+    },
+    /// Split the focused pane; the new pane shows `view`, or the focused
+    /// view.
+    Split {
+        direction: SplitDirection,
+        view: Option<String>,
+    },
+    NewTab(Option<String>),
+    RenameTab(String),
+    CloseTab,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// Parse a command line using shell quoting rules. Errors are meant to
+    /// be shown to the user as is.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let words = shell_words::split(line).map_err(|err| format!("invalid command: {err}"))?;
+        let Some((name, args)) = words.split_first() else {
+            return Err("empty command".to_string());
+        };
+
+        let command = match (name.as_str(), args) {
+            ("start", [descriptor]) => Self::Start {
+                descriptor: descriptor.clone(),
+                name: None,
+            },
+            ("start", [descriptor, flag, name] | [flag, name, descriptor])
+                if flag == "--name" || flag == "-n" =>
+            {
+                Self::Start {
+                    descriptor: descriptor.clone(),
+                    name: Some(name.clone()),
+                }
+            }
+            ("stop", [dataflow]) => Self::Stop(dataflow.clone()),
+            ("logs" | "log", [dataflow]) => Self::Logs {
+                dataflow: dataflow.clone(),
+                node: None,
+            },
+            ("logs" | "log", [dataflow, node]) => Self::Logs {
+                dataflow: dataflow.clone(),
+                node: Some(node.clone()),
+            },
+            ("inspect", [dataflow, node]) => Self::Inspect {
+                dataflow: dataflow.clone(),
+                node: node.clone(),
+            },
+            ("view", [view]) => Self::View(view.clone()),
+            ("theme", [theme]) => Self::Theme(theme.clone()),
+            ("set", [key, value]) => Self::Set {
+                key: key.clone(),
+                value: value.clone(),
+            },
+            ("split", [direction] | [direction, _])
+                if direction == "right" || direction == "down" =>
+            {
+                Self::Split {
+                    direction: if direction == "right" {
+                        SplitDirection::Horizontal
+                    } else {
+                        SplitDirection::Vertical
+                    },
+                    view: args.get(1).cloned(),
+                }
+            }
+            ("tab", [new] | [new, _]) if new == "new" => Self::NewTab(args.get(1).cloned()),
+            ("tab", [rename, name]) if rename == "rename" => Self::RenameTab(name.clone()),
+            ("tab", [close]) if close == "close" => Self::CloseTab,
+            ("help", []) => Self::Help,
+            ("quit" | "q", []) => Self::Quit,
+            (name, _) => {
+                return Err(match usage(name) {
+                    Some(usage) => format!("usage: {usage}"),
+                    None => format!("unknown command `{name}`"),
+                });
+            }
+        };
+        Ok(command)
+    }
+}
+
+fn usage(command: &str) -> Option<&'static str> {
+    COMMANDS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, usage)| *usage)
+}
+
+/// Completion candidates for the last word of `line`. Dataflow and node
+/// names come from `dataflows`.
+pub fn complete(line: &str, dataflows: &[super::app::DataflowInfo]) -> Vec<String> {
+    // An unterminated quote is still being typed; complete what we have.
+    let mut words = shell_words::split(line)
+        .or_else(|_| shell_words::split(&format!("{line}\"")))
+        .unwrap_or_default();
+    if line.is_empty() || line.ends_with(char::is_whitespace) {
+        words.push(String::new());
+    }
+    let Some((prefix, previous)) = words.split_last() else {
+        return Vec::new();
+    };
+
+    let dataflow_names = || dataflows.iter().map(|df| df.name.clone()).collect();
+    let candidates: Vec<String> = match previous {
+        [] => COMMANDS.iter().map(|(name, _)| name.to_string()).collect(),
+        [command] => match command.as_str() {
+            "stop" => dataflows
+                .iter()
+                .filter(|df| df.status.is_running())
+                .map(|df| df.name.clone())
+                .collect(),
+            "logs" | "log" | "inspect" => dataflow_names(),
+            "view" => VIEW_NAMES.iter().map(|name| name.to_string()).collect(),
+            "theme" => ThemeConfig::available(ThemeConfig::themes_dir().as_deref()),
+            "set" => PREFERENCE_KEYS.iter().map(|key| key.to_string()).collect(),
+            "split" => vec!["right".to_string(), "down".to_string()],
+            "tab" => ["new", "rename", "close"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        },
+        [command, _] if command == "split" => {
+            VIEW_NAMES.iter().map(|name| name.to_string()).collect()
+        }
+        [command, key] if command == "set" => match key.as_str() {
+            "accessible" | "mouse_capture" | "show_system_info" => {
+                vec!["true".to_string(), "false".to_string()]
+            }
+            "theme" => ThemeConfig::available(ThemeConfig::themes_dir().as_deref()),
+            "default_view" => VIEW_NAMES.iter().map(|name| name.to_string()).collect(),
+            "notify_on" => NotifyEvent::ALL
+                .iter()
+                .map(|event| event.to_string())
+                .chain(["none".to_string()])
+                .collect(),
+            _ => Vec::new(),
+        },
+        [command, dataflow] if matches!(command.as_str(), "logs" | "log" | "inspect") => dataflows
+            .iter()
+            .find(|df| &df.name == dataflow || &df.id == dataflow)
+            .map(|df| df.nodes.iter().map(|node| node.id.clone()).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix.as_str()))
+        .collect();
+    matches.sort();
+    matches.dedup();
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::{DataflowInfo, NodeInfo};
+    use tui_interface::DataflowStatus;
+
+    #[test]
+    fn parses_commands_with_quoting() {
+        assert_eq!(
+            Command::parse("start 'my flow.yml' --name demo"),
+            Ok(Command::Start {
+                descriptor: "my flow.yml".to_string(),
+                name: Some("demo".to_string()),
+            })
+        );
+        assert_eq!(
+            Command::parse("logs demo camera"),
+            Ok(Command::Logs {
+                dataflow: "demo".to_string(),
+                node: Some("camera".to_string()),
+            })
+        );
+        assert_eq!(
+            Command::parse("inspect demo"),
+            Err("usage: inspect <dataflow> <node>".to_string())
+        );
+        assert_eq!(
+            Command::parse("launch demo"),
+            Err("unknown command `launch`".to_string())
+        );
+        assert!(Command::parse("stop 'demo").is_err());
+        assert_eq!(
+            Command::parse("split down logs"),
+            Ok(Command::Split {
+                direction: SplitDirection::Vertical,
+                view: Some("logs".to_string()),
+            })
+        );
+        assert_eq!(
+            Command::parse("split left"),
+            Err("usage: split <right|down> [view]".to_string())
+        );
+        assert_eq!(
+            Command::parse("tab new 'camera debug'"),
+            Ok(Command::NewTab(Some("camera debug".to_string())))
+        );
+        assert_eq!(Command::parse("tab close"), Ok(Command::CloseTab));
+        assert_eq!(
+            Command::parse("set accessible true"),
+            Ok(Command::Set {
+                key: "accessible".to_string(),
+                value: "true".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn completes_from_app_state() {
+        let dataflows = vec![
+            DataflowInfo {
+                id: "df-1".to_string(),
+                name: "demo".to_string(),
+                status: DataflowStatus::Running,
+                nodes: vec![
+                    NodeInfo {
+                        id: "camera".to_string(),
+                        ..Default::default()
+                    },
+                    NodeInfo {
+                        id: "cam-rear".to_string(),
+                        ..Default::default()
+                    },
+                ],
+            },
+            DataflowInfo {
+                id: "df-2".to_string(),
+                name: "detector".to_string(),
+                status: DataflowStatus::Finished,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(complete("st", &dataflows), vec!["start", "stop"]);
+        assert_eq!(complete("stop ", &dataflows), vec!["demo"]);
+        assert_eq!(complete("logs de", &dataflows), vec!["demo", "detector"]);
+        assert_eq!(
+            complete("inspect demo cam", &dataflows),
+            vec!["cam-rear", "camera"]
+        );
+        assert_eq!(complete("theme l", &dataflows), vec!["light"]);
+        assert_eq!(complete("set acc", &dataflows), vec!["accessible"]);
+        assert_eq!(complete("set accessible t", &dataflows), vec!["true"]);
+    }
+}
@@ -154,9 +154,12 @@ impl Component for SystemOverviewComponent {
         })
     }
 
-    fn render(&self, frame: &mut Frame, area: Rect, theme: &ThemeConfig, _app_state: &AppState) {
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &ThemeConfig, app_state: &AppState) {
         let block = Block::default()
-            .title("System Overview")
+            .title(format!(
+                "System Overview ({})",
+                app_state.system_metrics.source.label()
+            ))
             .borders(Borders::ALL)
             .border_style(if self.focused {
                 theme.focused_border_style()
@@ -6,6 +6,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState},
 };
 use std::time::Instant;
+use tui_interface::DataflowStatus;
 
 use crate::tui::{
     Result,
@@ -67,14 +68,14 @@ impl DataflowListComponent {
         dataflow: &DataflowInfo,
         selected: bool,
         theme: &ThemeConfig,
-    ) -> ListItem {
-        let status_icon = match dataflow.status.as_str() {
-            "running" => "🟢",
-            "stopped" => "🔴",
-            "starting" => "🟡",
-            "stopping" => "🟠",
-            "failed" => "❌",
-            _ => "⚪",
+    ) -> ListItem<'static> {
+        let status_icon = match dataflow.status {
+            DataflowStatus::Running => "🟢",
+            DataflowStatus::Stopped | DataflowStatus::Destroyed => "🔴",
+            DataflowStatus::Pending => "🟡",
+            DataflowStatus::Finished => "🔵",
+            DataflowStatus::Failed => "❌",
+            DataflowStatus::Unknown => "⚪",
         };
 
         let style = if selected {
@@ -110,17 +111,17 @@ impl DataflowListComponent {
             .iter()
             .filter(|df| match self.filter {
                 DataflowFilter::All => true,
-                DataflowFilter::Running => df.status.eq_ignore_ascii_case("running"),
-                DataflowFilter::Stopped => df.status.eq_ignore_ascii_case("stopped"),
-                DataflowFilter::Failed => df.status.eq_ignore_ascii_case("failed"),
+                DataflowFilter::Running => df.status.is_active(),
+                DataflowFilter::Stopped => df.status.is_inactive(),
+                DataflowFilter::Failed => df.status.is_failed(),
             })
             .collect();
 
         match self.sort_by {
             DataflowSortBy::Name => filtered.sort_by(|a, b| a.name.cmp(&b.name)),
-            DataflowSortBy::Status => filtered.sort_by(|a, b| a.status.cmp(&b.status)),
+            DataflowSortBy::Status => filtered.sort_by_key(|df| df.status.as_str()),
             DataflowSortBy::NodeCount => filtered.sort_by_key(|df| df.nodes.len()),
-            DataflowSortBy::Uptime => filtered.sort_by_key(|df| df.status.clone()),
+            DataflowSortBy::Uptime => filtered.sort_by_key(|df| df.status.as_str()),
         }
 
         filtered
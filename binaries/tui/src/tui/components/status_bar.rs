@@ -2,7 +2,6 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
-    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
@@ -48,13 +47,10 @@ impl Component for StatusBarComponent {
 
         // Show latest status message if any
         if let Some(latest_msg) = app_state.status_messages.back() {
-            let style = match latest_msg.level {
-                crate::tui::app::MessageLevel::Info => Style::default().fg(theme.colors.primary),
-                crate::tui::app::MessageLevel::Success => Style::default().fg(theme.colors.success),
-                crate::tui::app::MessageLevel::Warning => Style::default().fg(theme.colors.warning),
-                crate::tui::app::MessageLevel::Error => Style::default().fg(theme.colors.error),
-            };
-            spans.push(Span::styled(&latest_msg.message, style));
+            spans.push(Span::styled(
+                &latest_msg.message,
+                theme.message_style(latest_msg.level),
+            ));
         }
 
         // Add keybindings if enabled
@@ -0,0 +1,209 @@
+//! Connection health.
+//!
+//! Every request to a backend and every stream event is recorded against
+//! its link. The monitor keeps the last success, the latency of the last
+//! request and the number of failures since, which drive the status bar
+//! indicator and the reconnecting banner. It also decides which failures
+//! are worth a message: the first one of an outage and the recovery, not
+//! every retry in between.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use tui_interface::StatusTone;
+
+/// A connection the app depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Link {
+    /// Coordinator queries through the gateway
+    Gateway,
+    /// System metrics polling
+    Telemetry,
+    /// Live system metrics pushed by the gateway
+    MetricsStream,
+    /// Live logs of the log viewer
+    LogStream,
+}
+
+impl Link {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gateway => "gateway",
+            Self::Telemetry => "telemetry",
+            Self::MetricsStream => "metrics stream",
+            Self::LogStream => "log stream",
+        }
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkStatus {
+    /// Nothing has come back yet
+    Connecting,
+    Connected,
+    /// The last attempt failed; the app keeps retrying
+    Reconnecting,
+}
+
+impl From<LinkStatus> for StatusTone {
+    fn from(status: LinkStatus) -> Self {
+        match status {
+            LinkStatus::Connecting => StatusTone::Pending,
+            LinkStatus::Connected => StatusTone::Healthy,
+            LinkStatus::Reconnecting => StatusTone::Failed,
+        }
+    }
+}
+
+/// What is known about one link.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkHealth {
+    pub last_success: Option<Instant>,
+    /// How long the last successful request took; streams have none
+    pub latency: Option<Duration>,
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+impl LinkHealth {
+    pub fn status(&self) -> LinkStatus {
+        if self.consecutive_failures > 0 {
+            LinkStatus::Reconnecting
+        } else if self.last_success.is_some() {
+            LinkStatus::Connected
+        } else {
+            LinkStatus::Connecting
+        }
+    }
+}
+
+/// What a recorded event changed, for deciding whether to tell the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// The link was working, or had not been tried, and now failed
+    Lost,
+    /// The link works again after failing
+    Restored,
+    /// Nothing worth a message
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionMonitor {
+    links: BTreeMap<Link, LinkHealth>,
+}
+
+impl ConnectionMonitor {
+    pub fn record_success(
+        &mut self,
+        link: Link,
+        latency: Option<Duration>,
+        now: Instant,
+    ) -> Transition {
+        let health = self.links.entry(link).or_default();
+        let transition = if health.consecutive_failures > 0 {
+            Transition::Restored
+        } else {
+            Transition::Unchanged
+        };
+        health.last_success = Some(now);
+        health.latency = latency.or(health.latency);
+        health.consecutive_failures = 0;
+        health.last_error = None;
+        transition
+    }
+
+    pub fn record_failure(&mut self, link: Link, error: impl Into<String>) -> Transition {
+        let health = self.links.entry(link).or_default();
+        health.consecutive_failures += 1;
+        health.last_error = Some(error.into());
+        if health.consecutive_failures == 1 {
+            Transition::Lost
+        } else {
+            Transition::Unchanged
+        }
+    }
+
+    /// Stop tracking `link`, e.g. once no view uses it.
+    pub fn forget(&mut self, link: Link) {
+        self.links.remove(&link);
+    }
+
+    pub fn health(&self, link: Link) -> Option<&LinkHealth> {
+        self.links.get(&link)
+    }
+
+    pub fn status(&self, link: Link) -> LinkStatus {
+        self.health(link)
+            .map_or(LinkStatus::Connecting, LinkHealth::status)
+    }
+
+    /// Tracked links in a fixed order.
+    pub fn links(&self) -> impl Iterator<Item = (Link, &LinkHealth)> {
+        self.links.iter().map(|(link, health)| (*link, health))
+    }
+
+    /// The first link that is down, for the reconnecting banner.
+    pub fn outage(&self) -> Option<(Link, &LinkHealth)> {
+        self.links()
+            .find(|(_, health)| health.status() == LinkStatus::Reconnecting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_first_failure_and_the_recovery_are_reported() {
+        let mut monitor = ConnectionMonitor::default();
+        let start = Instant::now();
+        assert_eq!(monitor.status(Link::Gateway), LinkStatus::Connecting);
+
+        let latency = Some(Duration::from_millis(12));
+        assert_eq!(
+            monitor.record_success(Link::Gateway, latency, start),
+            Transition::Unchanged
+        );
+        assert_eq!(monitor.status(Link::Gateway), LinkStatus::Connected);
+        assert!(monitor.outage().is_none());
+
+        assert_eq!(
+            monitor.record_failure(Link::Gateway, "connection refused"),
+            Transition::Lost
+        );
+        assert_eq!(
+            monitor.record_failure(Link::Gateway, "connection refused"),
+            Transition::Unchanged
+        );
+        let (link, health) = monitor.outage().unwrap();
+        assert_eq!(link, Link::Gateway);
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.last_success, Some(start));
+        assert_eq!(health.latency, latency);
+
+        let later = start + Duration::from_secs(4);
+        assert_eq!(
+            monitor.record_success(Link::Gateway, None, later),
+            Transition::Restored
+        );
+        let health = monitor.health(Link::Gateway).unwrap();
+        assert_eq!(health.status(), LinkStatus::Connected);
+        assert_eq!(health.last_error, None);
+
+        monitor.record_failure(Link::LogStream, "stream closed");
+        assert_eq!(monitor.outage().unwrap().0, Link::LogStream);
+        monitor.forget(Link::LogStream);
+        assert!(monitor.outage().is_none());
+    }
+}
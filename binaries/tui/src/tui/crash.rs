@@ -0,0 +1,246 @@
+//! Terminal restoration and crash reports.
+//!
+//! The TUI puts the terminal in raw mode on the alternate screen. A
+//! `TerminalGuard` undoes that when it is dropped, and the panic hook undoes
+//! it before anything is printed, so a panic leaves a usable shell behind.
+//! The hook also writes a crash report with the backtrace and what the app
+//! last recorded about itself (see `record_context`) and prints its path.
+//!
+//! Only panics on the main thread, which runs the event loop, are treated as
+//! crashes. Background fetches run on other threads; their panics come back
+//! as task errors and are shown like any other backend error.
+
+use std::{
+    backtrace::Backtrace,
+    fmt::Write as _,
+    fs, io,
+    panic::PanicHookInfo,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use chrono::{DateTime, Local};
+use crossterm::{
+    event::DisableMouseCapture,
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+
+use crate::tui::app::StatusMessage;
+
+/// Messages kept for the report.
+const RECENT_MESSAGES: usize = 10;
+
+/// Whether the terminal is currently set up for the TUI.
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// What the app last recorded about itself.
+static CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+/// Raw mode on the alternate screen for as long as the guard lives.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        // From here on dropping the guard restores the terminal, even if
+        // entering the alternate screen fails.
+        let guard = Self { _private: () };
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leave raw mode, mouse capture and the alternate screen, if the TUI is
+/// running. Safe to call more than once.
+pub fn restore_terminal() {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    // Nothing useful can be done if these fail; carry on with the rest.
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        crossterm::cursor::Show
+    );
+}
+
+/// The parts of the app state worth putting in a crash report.
+#[derive(Debug, Clone, Default)]
+pub struct CrashContext {
+    pub view: String,
+    /// Most recent last
+    pub messages: Vec<StatusMessage>,
+}
+
+impl CrashContext {
+    pub fn new<'a>(
+        view: String,
+        messages: impl DoubleEndedIterator<Item = &'a StatusMessage>,
+    ) -> Self {
+        let mut messages: Vec<_> = messages.rev().take(RECENT_MESSAGES).cloned().collect();
+        messages.reverse();
+        Self { view, messages }
+    }
+}
+
+/// Remember `context` for a crash report.
+pub fn record_context(context: CrashContext) {
+    if let Ok(mut current) = CONTEXT.lock() {
+        *current = Some(context);
+    }
+}
+
+/// `<local data dir>/dora/crash-reports`, or the temp dir if the platform
+/// has no data dir.
+pub fn reports_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|dir| dir.join("dora").join("crash-reports"))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Restore the terminal and write a crash report on panics of the main
+/// thread, then hand over to the previous hook.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            restore_terminal();
+            // The lock may be held by the panicking code itself.
+            let context = CONTEXT.try_lock().ok().and_then(|context| context.clone());
+            let report = CrashReport::new(info, context);
+            match report.write_to(&reports_dir()) {
+                Ok(path) => eprintln!(
+                    "dora-tui crashed; a crash report was written to {}",
+                    path.display()
+                ),
+                Err(err) => eprintln!("dora-tui crashed and the crash report failed: {err}"),
+            }
+        }
+        previous(info);
+    }));
+}
+
+pub struct CrashReport {
+    time: DateTime<Local>,
+    panic: String,
+    backtrace: String,
+    context: Option<CrashContext>,
+}
+
+impl CrashReport {
+    fn new(info: &PanicHookInfo<'_>, context: Option<CrashContext>) -> Self {
+        let payload = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        let panic = match info.location() {
+            Some(location) => format!("{payload} at {location}"),
+            None => payload,
+        };
+        Self {
+            time: Local::now(),
+            panic,
+            backtrace: Backtrace::force_capture().to_string(),
+            context,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut report = String::new();
+        let _ = writeln!(report, "dora-tui {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(report, "time: {}", self.time.to_rfc3339());
+        let _ = writeln!(report, "panic: {}", self.panic);
+        match &self.context {
+            Some(context) => {
+                let _ = writeln!(report, "view: {}", context.view);
+                let _ = writeln!(report, "\nrecent messages:");
+                if context.messages.is_empty() {
+                    let _ = writeln!(report, "  (none)");
+                }
+                for message in &context.messages {
+                    let _ = writeln!(
+                        report,
+                        "  {} {:<7} {}: {}",
+                        message.time.format("%H:%M:%S"),
+                        message.level.label(),
+                        message.source,
+                        message.message
+                    );
+                }
+            }
+            None => {
+                let _ = writeln!(report, "view: (the TUI had not started)");
+            }
+        }
+        let _ = writeln!(report, "\nbacktrace:\n{}", self.backtrace);
+        report
+    }
+
+    /// Write the report to a new file in `dir` and return its path.
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "crash-{}-{}.txt",
+            self.time.format("%Y%m%d-%H%M%S"),
+            std::process::id()
+        ));
+        fs::write(&path, self.render())?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::{app::MessageLevel, test_support::scratch_dir};
+
+    #[test]
+    fn reports_name_the_view_and_recent_messages() {
+        let messages: Vec<_> = (0..15)
+            .map(|index| {
+                StatusMessage::new(
+                    "coordinator",
+                    format!("message {index}"),
+                    MessageLevel::Error,
+                )
+            })
+            .collect();
+        let report = CrashReport {
+            time: Local::now(),
+            panic: "index out of bounds at src/tui/app.rs:1:1".to_string(),
+            backtrace: "0: dora_tui::main".to_string(),
+            context: Some(CrashContext::new("Dashboard".to_string(), messages.iter())),
+        };
+
+        let text = report.render();
+        assert!(text.starts_with(&format!("dora-tui {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(text.contains("panic: index out of bounds at src/tui/app.rs:1:1\n"));
+        assert!(text.contains("view: Dashboard\n"));
+        assert!(!text.contains("message 4\n"));
+        assert!(text.contains("error   coordinator: message 5\n"));
+        assert!(text.contains("error   coordinator: message 14\n"));
+        assert!(text.contains("backtrace:\n0: dora_tui::main"));
+
+        let dir = scratch_dir("crash");
+        let path = report.write_to(&dir).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), text);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,442 @@
+//! Views opened from the command line.
+//!
+//! `--view` names a view and the other flags fill in what it needs, e.g.
+//! `--view inspector --dataflow camera --node detector`. Dataflows may be
+//! given by name or id; names are resolved against the coordinator's list
+//! so the view refers to the id like any other, and a link that does not
+//! match anything is reported before the TUI starts.
+
+use tui_interface::DataflowSummary;
+
+use crate::tui::{
+    app::ViewType,
+    command_executor::VIEW_NAMES,
+    session::{ExplorerSession, SavedView},
+    views::{ExplorerTab, InspectorTab, LogFilter, LogLevel, ViewMode},
+};
+
+/// Views that take parameters, on top of [`VIEW_NAMES`].
+const PARAMETERIZED_VIEWS: &[&str] = &["inspector", "debug", "recording"];
+
+/// The view flags given on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeepLink {
+    pub view: String,
+    /// Dataflow name or id
+    pub dataflow: Option<String>,
+    pub node: Option<String>,
+    /// Least severe log level to show
+    pub level: Option<String>,
+    pub tab: Option<String>,
+    pub recording: Option<String>,
+}
+
+/// What a view does with each flag.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Use {
+    Required,
+    Optional,
+    Unused,
+}
+
+impl DeepLink {
+    /// Whether resolving needs the coordinator's dataflow list.
+    pub fn needs_dataflows(&self) -> bool {
+        self.dataflow.is_some()
+    }
+
+    /// The view the link opens, with its state set from the flags.
+    pub fn resolve(&self, dataflows: &[DataflowSummary]) -> Result<SavedView, String> {
+        let name = self.view.to_lowercase();
+        match name.as_str() {
+            "inspector" | "node" | "node_inspector" => {
+                self.check(
+                    &name,
+                    Use::Required,
+                    Use::Required,
+                    Use::Unused,
+                    Use::Optional,
+                )?;
+                let dataflow = self.dataflow(dataflows)?.expect("checked above");
+                let node = self.node(dataflow)?.expect("checked above");
+                let mut saved = SavedView::new(ViewType::NodeInspector {
+                    dataflow_id: dataflow.id.clone(),
+                    node_id: node,
+                });
+                saved.inspector_tab =
+                    self.tab(InspectorTab::all(), |tab| tab.name().to_string())?;
+                Ok(saved)
+            }
+            "debug" | "debug_session" => {
+                self.check(&name, Use::Required, Use::Unused, Use::Unused, Use::Unused)?;
+                let dataflow = self.dataflow(dataflows)?.expect("checked above");
+                Ok(SavedView::new(ViewType::DebugSession {
+                    dataflow_id: dataflow.id.clone(),
+                }))
+            }
+            "recording" | "recording_analyzer" => {
+                self.check(&name, Use::Unused, Use::Unused, Use::Unused, Use::Unused)?;
+                let recording_id = self
+                    .recording
+                    .clone()
+                    .ok_or_else(|| format!("the {name} view needs --recording <id>"))?;
+                Ok(SavedView::new(ViewType::RecordingAnalyzer { recording_id }))
+            }
+            _ => {
+                let view = ViewType::from_name(&name).ok_or_else(|| {
+                    format!(
+                        "unknown view `{}`; expected one of: {}",
+                        self.view,
+                        VIEW_NAMES
+                            .iter()
+                            .chain(PARAMETERIZED_VIEWS)
+                            .copied()
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+                self.resolve_plain(&name, view, dataflows)
+            }
+        }
+    }
+
+    fn resolve_plain(
+        &self,
+        name: &str,
+        view: ViewType,
+        dataflows: &[DataflowSummary],
+    ) -> Result<SavedView, String> {
+        match view {
+            ViewType::LogViewer { .. } => {
+                self.check(
+                    name,
+                    Use::Optional,
+                    Use::Optional,
+                    Use::Optional,
+                    Use::Unused,
+                )?;
+                if self.node.is_some() && self.dataflow.is_none() {
+                    return Err("--node needs --dataflow to say which dataflow it is in".into());
+                }
+                let target = match self.dataflow(dataflows)? {
+                    Some(dataflow) => match self.node(dataflow)? {
+                        Some(node) => format!("{}/{node}", dataflow.id),
+                        None => dataflow.id.clone(),
+                    },
+                    None => "system".to_string(),
+                };
+                let mut saved = SavedView::new(ViewType::LogViewer { target });
+                if let Some(level) = &self.level {
+                    let level = parse_level(level)?;
+                    saved.log_filter = Some(LogFilter {
+                        enabled_levels: LogLevel::all()
+                            .into_iter()
+                            .filter(|enabled| *enabled <= level)
+                            .collect(),
+                        ..LogFilter::new()
+                    });
+                }
+                Ok(saved)
+            }
+            ViewType::DataflowExplorer => {
+                self.check(name, Use::Optional, Use::Unused, Use::Unused, Use::Optional)?;
+                let selected_dataflow = self.dataflow(dataflows)?.map(|df| df.id.clone());
+                let tab = self.tab(ExplorerTab::all(), |tab| tab.name().to_string())?;
+                let mut saved = SavedView::new(ViewType::DataflowExplorer);
+                if tab.is_some() || selected_dataflow.is_some() {
+                    saved.explorer = Some(ExplorerSession {
+                        tab: tab.unwrap_or(ExplorerTab::Overview),
+                        view_mode: ViewMode::ListGrouped,
+                        show_stopped: true,
+                        selected_dataflow,
+                    });
+                }
+                Ok(saved)
+            }
+            view => {
+                self.check(name, Use::Unused, Use::Unused, Use::Unused, Use::Unused)?;
+                Ok(SavedView::new(view))
+            }
+        }
+    }
+
+    /// Reject flags the view has no use for and missing required ones.
+    /// `--recording` is checked by the recording view itself.
+    fn check(
+        &self,
+        view: &str,
+        dataflow: Use,
+        node: Use,
+        level: Use,
+        tab: Use,
+    ) -> Result<(), String> {
+        let flags = [
+            ("--dataflow", self.dataflow.is_some(), dataflow),
+            ("--node", self.node.is_some(), node),
+            ("--level", self.level.is_some(), level),
+            ("--tab", self.tab.is_some(), tab),
+        ];
+        for (flag, given, use_) in flags {
+            match (given, use_) {
+                (true, Use::Unused) => {
+                    return Err(format!("{flag} does not apply to the {view} view"));
+                }
+                (false, Use::Required) => {
+                    return Err(format!("the {view} view needs {flag}"));
+                }
+                _ => {}
+            }
+        }
+        let recording_view = matches!(view, "recording" | "recording_analyzer");
+        if self.recording.is_some() && !recording_view {
+            return Err(format!("--recording does not apply to the {view} view"));
+        }
+        Ok(())
+    }
+
+    /// The dataflow named by `--dataflow`, matched by id first, then name.
+    fn dataflow<'a>(
+        &self,
+        dataflows: &'a [DataflowSummary],
+    ) -> Result<Option<&'a DataflowSummary>, String> {
+        let Some(wanted) = self.dataflow.as_deref() else {
+            return Ok(None);
+        };
+        if let Some(dataflow) = dataflows.iter().find(|df| df.id == wanted) {
+            return Ok(Some(dataflow));
+        }
+        let named: Vec<_> = dataflows.iter().filter(|df| df.name == wanted).collect();
+        match named.as_slice() {
+            [dataflow] => Ok(Some(dataflow)),
+            [] if dataflows.is_empty() => Err(format!(
+                "no dataflow `{wanted}`: the coordinator has no dataflows"
+            )),
+            [] => Err(format!(
+                "no dataflow `{wanted}`; known dataflows: {}",
+                dataflows
+                    .iter()
+                    .map(|df| format!("{} ({})", df.name, df.id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            _ => Err(format!(
+                "{} dataflows are named `{wanted}`; pass the id of one of: {}",
+                named.len(),
+                named
+                    .iter()
+                    .map(|df| format!("{} ({})", df.id, df.status))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    fn node(&self, dataflow: &DataflowSummary) -> Result<Option<String>, String> {
+        let Some(wanted) = self.node.as_deref() else {
+            return Ok(None);
+        };
+        if dataflow.nodes.iter().any(|node| node.id == wanted) {
+            return Ok(Some(wanted.to_string()));
+        }
+        let nodes: Vec<_> = dataflow.nodes.iter().map(|node| node.id.as_str()).collect();
+        Err(format!(
+            "dataflow `{}` has no node `{wanted}`; its nodes are: {}",
+            dataflow.name,
+            if nodes.is_empty() {
+                "(none)".to_string()
+            } else {
+                nodes.join(", ")
+            }
+        ))
+    }
+
+    /// The tab named by `--tab`, out of `tabs`.
+    fn tab<T: Copy>(&self, tabs: Vec<T>, name: impl Fn(&T) -> String) -> Result<Option<T>, String> {
+        let Some(wanted) = self.tab.as_deref() else {
+            return Ok(None);
+        };
+        tabs.iter()
+            .find(|tab| name(tab).eq_ignore_ascii_case(wanted))
+            .copied()
+            .map(Some)
+            .ok_or_else(|| {
+                format!(
+                    "unknown tab `{wanted}` for the {} view; expected one of: {}",
+                    self.view,
+                    tabs.iter()
+                        .map(|tab| name(tab).to_lowercase())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+fn parse_level(level: &str) -> Result<LogLevel, String> {
+    let level = match level.to_lowercase().as_str() {
+        "warning" => "warn".to_string(),
+        other => other.to_string(),
+    };
+    LogLevel::all()
+        .into_iter()
+        .find(|candidate| candidate.name().eq_ignore_ascii_case(&level))
+        .ok_or_else(|| {
+            format!(
+                "unknown log level `{level}`; expected one of: {}",
+                LogLevel::all()
+                    .iter()
+                    .map(|level| level.name().to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_interface::{DataflowStatus, NodeSummary};
+
+    fn dataflow(id: &str, name: &str, nodes: &[&str]) -> DataflowSummary {
+        DataflowSummary {
+            id: id.to_string(),
+            name: name.to_string(),
+            status: DataflowStatus::Running,
+            nodes: nodes
+                .iter()
+                .map(|id| NodeSummary {
+                    id: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn link(view: &str) -> DeepLink {
+        DeepLink {
+            view: view.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn names_resolve_to_ids() {
+        let dataflows = [
+            dataflow("1111", "camera", &["source", "detector"]),
+            dataflow("2222", "lidar", &["scan"]),
+        ];
+
+        let saved = DeepLink {
+            dataflow: Some("camera".to_string()),
+            node: Some("detector".to_string()),
+            tab: Some("connections".to_string()),
+            ..link("inspector")
+        }
+        .resolve(&dataflows)
+        .unwrap();
+        assert_eq!(
+            saved.view,
+            ViewType::NodeInspector {
+                dataflow_id: "1111".to_string(),
+                node_id: "detector".to_string(),
+            }
+        );
+        assert_eq!(saved.inspector_tab, Some(InspectorTab::Connections));
+
+        let saved = DeepLink {
+            dataflow: Some("2222".to_string()),
+            node: Some("scan".to_string()),
+            level: Some("warn".to_string()),
+            ..link("logs")
+        }
+        .resolve(&dataflows)
+        .unwrap();
+        assert_eq!(
+            saved.view,
+            ViewType::LogViewer {
+                target: "2222/scan".to_string()
+            }
+        );
+        assert_eq!(
+            saved.log_filter.unwrap().enabled_levels,
+            vec![LogLevel::Error, LogLevel::Warn]
+        );
+
+        let saved = DeepLink {
+            tab: Some("Performance".to_string()),
+            ..link("explorer")
+        }
+        .resolve(&[])
+        .unwrap();
+        assert_eq!(saved.explorer.unwrap().tab, ExplorerTab::Performance);
+
+        assert_eq!(
+            link("monitor").resolve(&[]).unwrap(),
+            SavedView::new(ViewType::SystemMonitor)
+        );
+    }
+
+    #[test]
+    fn bad_links_say_what_would_work() {
+        let dataflows = [
+            dataflow("1111", "camera", &["source", "detector"]),
+            dataflow("2222", "camera", &[]),
+            dataflow("3333", "lidar", &["scan"]),
+        ];
+        let error = |link: DeepLink| link.resolve(&dataflows).unwrap_err();
+
+        assert!(error(link("graph")).starts_with("unknown view `graph`; expected one of: "));
+        assert!(error(link("graph")).ends_with("inspector, debug, recording"));
+        assert_eq!(
+            error(DeepLink {
+                dataflow: Some("radar".to_string()),
+                ..link("logs")
+            }),
+            "no dataflow `radar`; known dataflows: camera (1111), camera (2222), lidar (3333)"
+        );
+        assert!(
+            error(DeepLink {
+                dataflow: Some("camera".to_string()),
+                ..link("debug")
+            })
+            .starts_with("2 dataflows are named `camera`; pass the id of one of: 1111 (")
+        );
+        assert_eq!(
+            error(DeepLink {
+                dataflow: Some("lidar".to_string()),
+                node: Some("detector".to_string()),
+                ..link("inspector")
+            }),
+            "dataflow `lidar` has no node `detector`; its nodes are: scan"
+        );
+        assert_eq!(
+            error(DeepLink {
+                dataflow: Some("lidar".to_string()),
+                ..link("inspector")
+            }),
+            "the inspector view needs --node"
+        );
+        assert_eq!(
+            error(DeepLink {
+                level: Some("warn".to_string()),
+                ..link("explorer")
+            }),
+            "--level does not apply to the explorer view"
+        );
+        assert_eq!(
+            error(DeepLink {
+                tab: Some("graph".to_string()),
+                ..link("explorer")
+            }),
+            "unknown tab `graph` for the explorer view; expected one of: \
+             overview, nodes, performance, configuration, logs, debug"
+        );
+        assert_eq!(
+            error(DeepLink {
+                level: Some("loud".to_string()),
+                ..link("logs")
+            }),
+            "unknown log level `loud`; expected one of: error, warn, info, debug, trace"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Writing the files the TUI keeps between runs.
+
+use std::{ffi::OsString, fs, io, path::Path};
+
+/// Replace the file at `path` with `contents`, creating its directory if
+/// needed. The contents go to a sibling file that is then renamed over
+/// `path`, so a crash never leaves a truncated file behind.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path.file_name().map(OsString::from).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
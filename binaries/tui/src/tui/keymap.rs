@@ -0,0 +1,850 @@
+//! Key bindings.
+//!
+//! Every key the application reacts to is bound to a named action in a
+//! context: `global` for application-wide keys and one context per view.
+//! The active view's context is consulted first, then `global`. A view only
+//! sees keys bound in its own context, translated to the key its handler
+//! understands, so rebinding an action never requires touching the view.
+//!
+//! Users override bindings in `<config dir>/dora/keymap.toml`:
+//!
+//! ```toml
+//! [global]
+//! quit = ["q", "ctrl+q"]
+//!
+//! [log_viewer]
+//! search = "ctrl+f"
+//! ```
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::tui::app::ViewType;
+
+const KEYMAP_FILE: &str = "keymap.toml";
+
+/// A key together with its modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already part of an upper-case character or of BackTab,
+        // and terminals disagree on whether they report it.
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn to_event(self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    /// Parse chords such as `q`, `F1`, `ctrl+r`, `shift+tab` or `pgdn`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid key `{s}`");
+        // A lone `+`, or a chord ending in `++`, binds the plus key.
+        let (modifier_names, key) = match s.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.strip_suffix('+').unwrap_or(rest), "+")
+            }
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                other => match other.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Where a binding applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Global,
+    Dashboard,
+    Explorer,
+    NodeInspector,
+    LogViewer,
+    Notifications,
+    Help,
+}
+
+impl KeyContext {
+    pub const ALL: [Self; 7] = [
+        Self::Global,
+        Self::Dashboard,
+        Self::Explorer,
+        Self::NodeInspector,
+        Self::LogViewer,
+        Self::Notifications,
+        Self::Help,
+    ];
+
+    /// Section name in the keymap file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Dashboard => "dashboard",
+            Self::Explorer => "explorer",
+            Self::NodeInspector => "node_inspector",
+            Self::LogViewer => "log_viewer",
+            Self::Notifications => "notifications",
+            Self::Help => "help",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Global => "Global",
+            Self::Dashboard => "Dashboard",
+            Self::Explorer => "Dataflow Explorer",
+            Self::NodeInspector => "Node Inspector",
+            Self::LogViewer => "Log Viewer",
+            Self::Notifications => "Notifications",
+            Self::Help => "Help",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|context| context.name() == name)
+    }
+
+    /// Context of a view; views without bindings of their own get `None`.
+    pub fn for_view(view: &ViewType) -> Option<Self> {
+        match view {
+            ViewType::Dashboard => Some(Self::Dashboard),
+            ViewType::DataflowExplorer => Some(Self::Explorer),
+            ViewType::NodeInspector { .. } => Some(Self::NodeInspector),
+            ViewType::LogViewer { .. } => Some(Self::LogViewer),
+            ViewType::Notifications => Some(Self::Notifications),
+            ViewType::Help => Some(Self::Help),
+            _ => None,
+        }
+    }
+}
+
+/// Actions handled by the application itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalAction {
+    Quit,
+    Help,
+    Back,
+    CommandLine,
+    ToggleMouse,
+    Dashboard,
+    Dataflows,
+    Monitor,
+    Logs,
+    Settings,
+    Explorer,
+    Notifications,
+    SplitRight,
+    SplitDown,
+    ClosePane,
+    FocusLeft,
+    FocusRight,
+    FocusUp,
+    FocusDown,
+    GrowPane,
+    ShrinkPane,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+}
+
+impl GlobalAction {
+    /// The view this action switches to, if it is a view shortcut.
+    pub fn view(self) -> Option<ViewType> {
+        match self {
+            Self::Dashboard => Some(ViewType::Dashboard),
+            Self::Dataflows => Some(ViewType::DataflowManager),
+            Self::Monitor => Some(ViewType::SystemMonitor),
+            Self::Logs => Some(ViewType::LogViewer {
+                target: "system".to_string(),
+            }),
+            Self::Settings => Some(ViewType::SettingsManager),
+            Self::Explorer => Some(ViewType::DataflowExplorer),
+            Self::Notifications => Some(ViewType::Notifications),
+            _ => None,
+        }
+    }
+
+    /// Whether this action arranges panes or tabs. These apply before the
+    /// focused view sees the key.
+    pub fn is_workspace(self) -> bool {
+        matches!(
+            self,
+            Self::SplitRight
+                | Self::SplitDown
+                | Self::ClosePane
+                | Self::FocusLeft
+                | Self::FocusRight
+                | Self::FocusUp
+                | Self::FocusDown
+                | Self::GrowPane
+                | Self::ShrinkPane
+                | Self::NewTab
+                | Self::CloseTab
+                | Self::NextTab
+                | Self::PreviousTab
+        )
+    }
+}
+
+/// What a binding does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyTarget {
+    Global(GlobalAction),
+    /// Send this key to the active view. It is the key the view's own
+    /// handler matches, which may differ from the keys the user presses.
+    View(&'static str),
+}
+
+struct ActionSpec {
+    context: KeyContext,
+    name: &'static str,
+    description: &'static str,
+    target: KeyTarget,
+    defaults: &'static [&'static str],
+}
+
+const fn global(
+    name: &'static str,
+    description: &'static str,
+    action: GlobalAction,
+    defaults: &'static [&'static str],
+) -> ActionSpec {
+    ActionSpec {
+        context: KeyContext::Global,
+        name,
+        description,
+        target: KeyTarget::Global(action),
+        defaults,
+    }
+}
+
+/// A view action whose handler matches `defaults[0]`.
+const fn view(
+    context: KeyContext,
+    name: &'static str,
+    description: &'static str,
+    defaults: &'static [&'static str],
+) -> ActionSpec {
+    ActionSpec {
+        context,
+        name,
+        description,
+        target: KeyTarget::View(defaults[0]),
+        defaults,
+    }
+}
+
+/// A view action whose handler matches `key`, bound to other keys by
+/// default.
+const fn remapped(
+    context: KeyContext,
+    name: &'static str,
+    description: &'static str,
+    key: &'static str,
+    defaults: &'static [&'static str],
+) -> ActionSpec {
+    ActionSpec {
+        context,
+        name,
+        description,
+        target: KeyTarget::View(key),
+        defaults,
+    }
+}
+
+use KeyContext::{Dashboard, Explorer, Help, LogViewer, NodeInspector, Notifications};
+
+const ACTIONS: &[ActionSpec] = &[
+    global("quit", "Quit application", GlobalAction::Quit, &["q"]),
+    global("help", "Show help", GlobalAction::Help, &["f1"]),
+    global("back", "Back / cancel", GlobalAction::Back, &["esc"]),
+    global("command", "Command line", GlobalAction::CommandLine, &[":"]),
+    global(
+        "mouse",
+        "Toggle mouse capture",
+        GlobalAction::ToggleMouse,
+        &["f2"],
+    ),
+    global(
+        "view.dashboard",
+        "Dashboard",
+        GlobalAction::Dashboard,
+        &["1"],
+    ),
+    global(
+        "view.dataflows",
+        "Dataflow manager",
+        GlobalAction::Dataflows,
+        &["2"],
+    ),
+    global(
+        "view.monitor",
+        "System monitor",
+        GlobalAction::Monitor,
+        &["3"],
+    ),
+    global("view.logs", "Log viewer", GlobalAction::Logs, &["4"]),
+    global("view.settings", "Settings", GlobalAction::Settings, &["5"]),
+    global(
+        "view.explorer",
+        "Dataflow explorer",
+        GlobalAction::Explorer,
+        &["e"],
+    ),
+    global(
+        "view.notifications",
+        "Notification center",
+        GlobalAction::Notifications,
+        &["6"],
+    ),
+    global(
+        "pane.split_right",
+        "Split pane side by side",
+        GlobalAction::SplitRight,
+        &["alt+v"],
+    ),
+    global(
+        "pane.split_down",
+        "Split pane top and bottom",
+        GlobalAction::SplitDown,
+        &["alt+s"],
+    ),
+    global(
+        "pane.close",
+        "Close pane",
+        GlobalAction::ClosePane,
+        &["alt+x"],
+    ),
+    global(
+        "pane.focus_left",
+        "Focus pane to the left",
+        GlobalAction::FocusLeft,
+        &["alt+left"],
+    ),
+    global(
+        "pane.focus_right",
+        "Focus pane to the right",
+        GlobalAction::FocusRight,
+        &["alt+right"],
+    ),
+    global(
+        "pane.focus_up",
+        "Focus pane above",
+        GlobalAction::FocusUp,
+        &["alt+up"],
+    ),
+    global(
+        "pane.focus_down",
+        "Focus pane below",
+        GlobalAction::FocusDown,
+        &["alt+down"],
+    ),
+    global("pane.grow", "Grow pane", GlobalAction::GrowPane, &["alt+="]),
+    global(
+        "pane.shrink",
+        "Shrink pane",
+        GlobalAction::ShrinkPane,
+        &["alt+-"],
+    ),
+    global("tab.new", "New tab", GlobalAction::NewTab, &["alt+t"]),
+    global("tab.close", "Close tab", GlobalAction::CloseTab, &["alt+w"]),
+    global("tab.next", "Next tab", GlobalAction::NextTab, &["alt+n"]),
+    global(
+        "tab.previous",
+        "Previous tab",
+        GlobalAction::PreviousTab,
+        &["alt+p"],
+    ),
+    view(Dashboard, "up", "Move up", &["up", "k"]),
+    view(Dashboard, "down", "Move down", &["down", "j"]),
+    view(Dashboard, "inspect", "Inspect a node", &["enter"]),
+    view(Dashboard, "toggle", "Start/stop dataflow", &["space"]),
+    view(Dashboard, "logs", "View logs", &["l"]),
+    view(Dashboard, "refresh", "Refresh dataflows", &["r", "f5"]),
+    view(Dashboard, "system_info", "Toggle system info", &["s"]),
+    view(Dashboard, "new", "Start a new dataflow", &["n"]),
+    view(Dashboard, "help", "Show help", &["?"]),
+    view(Dashboard, "dataflows", "Dataflow manager", &["d"]),
+    view(Dashboard, "monitor", "Performance monitor", &["p"]),
+    view(Explorer, "tab.overview", "Overview tab", &["1"]),
+    view(Explorer, "tab.nodes", "Nodes tab", &["2"]),
+    view(Explorer, "tab.performance", "Performance tab", &["3"]),
+    view(Explorer, "tab.configuration", "Configuration tab", &["4"]),
+    view(Explorer, "tab.logs", "Logs tab", &["5"]),
+    view(Explorer, "tab.debug", "Debug tab", &["6"]),
+    view(Explorer, "next_tab", "Next tab", &["tab"]),
+    view(Explorer, "prev_tab", "Previous tab", &["backtab"]),
+    view(Explorer, "up", "Move up", &["up", "k"]),
+    view(Explorer, "down", "Move down", &["down", "j"]),
+    view(Explorer, "inspect", "Inspect node", &["enter"]),
+    view(Explorer, "view_mode", "Cycle view mode", &["v"]),
+    view(Explorer, "inspection", "Toggle inspection panel", &["i"]),
+    view(Explorer, "show_stopped", "Toggle stopped dataflows", &["h"]),
+    view(Explorer, "toggle", "Start/stop dataflow", &["s"]),
+    view(Explorer, "logs", "View logs", &["l"]),
+    view(Explorer, "refresh", "Refresh dataflows", &["f5"]),
+    view(Explorer, "help", "Show help", &["?"]),
+    view(Explorer, "back", "Back", &["esc"]),
+    view(NodeInspector, "back", "Back", &["esc"]),
+    view(NodeInspector, "prev_tab", "Previous tab", &["left", "h"]),
+    view(NodeInspector, "next_tab", "Next tab", &["right", "l"]),
+    view(NodeInspector, "tab.overview", "Overview tab", &["1"]),
+    view(NodeInspector, "tab.connections", "Connections tab", &["2"]),
+    view(NodeInspector, "tab.performance", "Performance tab", &["3"]),
+    view(
+        NodeInspector,
+        "tab.configuration",
+        "Configuration tab",
+        &["4"],
+    ),
+    view(NodeInspector, "tab.debug", "Debug tab", &["5"]),
+    view(NodeInspector, "up", "Scroll up", &["up", "k"]),
+    view(NodeInspector, "down", "Scroll down", &["down", "j"]),
+    view(NodeInspector, "details", "Toggle detailed metrics", &["d"]),
+    view(NodeInspector, "refresh", "Refresh", &["ctrl+r"]),
+    view(NodeInspector, "edit", "Toggle edit mode", &["e"]),
+    view(LogViewer, "back", "Back", &["esc"]),
+    view(LogViewer, "up", "Move up", &["up", "k"]),
+    view(LogViewer, "down", "Move down", &["down", "j"]),
+    view(LogViewer, "page_up", "Page up", &["pageup"]),
+    view(LogViewer, "page_down", "Page down", &["pagedown"]),
+    view(LogViewer, "top", "Jump to start", &["home"]),
+    view(LogViewer, "bottom", "Jump to end", &["end"]),
+    view(LogViewer, "pause", "Pause/resume", &["p", "space"]),
+    // Ctrl+C always quits, so clearing is bound elsewhere.
+    remapped(LogViewer, "clear", "Clear logs", "ctrl+c", &["ctrl+l"]),
+    view(LogViewer, "search", "Search", &["/"]),
+    view(LogViewer, "clear_search", "Clear search", &["ctrl+n"]),
+    view(LogViewer, "level.error", "Toggle errors", &["1"]),
+    view(LogViewer, "level.warn", "Toggle warnings", &["2"]),
+    view(LogViewer, "level.info", "Toggle info", &["3"]),
+    view(LogViewer, "level.debug", "Toggle debug", &["4"]),
+    view(LogViewer, "level.trace", "Toggle trace", &["5"]),
+    view(LogViewer, "all_levels", "Toggle all levels", &["a"]),
+    view(LogViewer, "refresh", "Refresh", &["ctrl+r"]),
+    view(Notifications, "back", "Clear filter / back", &["esc"]),
+    view(Notifications, "up", "Move up", &["up", "k"]),
+    view(Notifications, "down", "Move down", &["down", "j"]),
+    view(Notifications, "page_up", "Page up", &["pageup"]),
+    view(Notifications, "page_down", "Page down", &["pagedown"]),
+    view(Notifications, "top", "Newest", &["home"]),
+    view(Notifications, "bottom", "Oldest", &["end"]),
+    view(Notifications, "open", "Go to dataflow or node", &["enter"]),
+    view(Notifications, "filter", "Filter by text", &["/"]),
+    view(Notifications, "level", "Cycle level filter", &["f"]),
+    view(Help, "back", "Back", &["esc"]),
+    view(Help, "up", "Scroll up", &["up", "k"]),
+    view(Help, "down", "Scroll down", &["down", "j"]),
+    view(Help, "page_up", "Page up", &["pageup"]),
+    view(Help, "page_down", "Page down", &["pagedown"]),
+    view(Help, "top", "Go to top", &["home"]),
+];
+
+/// Global actions a view binding must not hide, or the user could get
+/// stuck in a view.
+const RESERVED: &[&str] = &["quit", "help", "command"];
+
+/// An action and the keys bound to it.
+#[derive(Debug, Clone)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub action: &'static str,
+    pub description: &'static str,
+    pub target: KeyTarget,
+    pub chords: Vec<KeyChord>,
+}
+
+impl KeyBinding {
+    /// The bound keys as shown in help, e.g. `↑/k`.
+    pub fn keys_label(&self) -> String {
+        self.chords
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Two bindings that claim the same key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub chord: KeyChord,
+    pub first: (KeyContext, &'static str),
+    pub second: (KeyContext, &'static str),
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first_context, first_action) = self.first;
+        let (second_context, second_action) = self.second;
+        if first_context == second_context {
+            write!(
+                f,
+                "`{}` is bound to both {first_action} and {second_action} in [{}]",
+                self.chord,
+                first_context.name()
+            )
+        } else {
+            write!(
+                f,
+                "`{}` for {second_action} in [{}] hides {first_action} in [{}]",
+                self.chord,
+                second_context.name(),
+                first_context.name()
+            )
+        }
+    }
+}
+
+/// The active key bindings.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .map(|spec| KeyBinding {
+                context: spec.context,
+                action: spec.name,
+                description: spec.description,
+                target: spec.target,
+                chords: spec
+                    .defaults
+                    .iter()
+                    .map(|chord| chord.parse().expect("default key bindings parse"))
+                    .collect(),
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// `<config dir>/dora/keymap.toml`, if the platform has a config dir.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("dora").join(KEYMAP_FILE))
+    }
+
+    /// Default bindings with the user's file applied. Problems with the
+    /// file, and conflicts it introduces, are returned as messages.
+    pub fn load(path: Option<&Path>) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let Some(path) = path else {
+            return (keymap, Vec::new());
+        };
+
+        let mut problems = match std::fs::read_to_string(path) {
+            Ok(contents) => keymap.apply_toml(&contents).unwrap_or_else(|err| vec![err]),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => vec![err.to_string()],
+        };
+        problems.extend(keymap.conflicts().iter().map(ToString::to_string));
+        let problems = problems
+            .into_iter()
+            .map(|problem| format!("{}: {problem}", path.display()))
+            .collect();
+        (keymap, problems)
+    }
+
+    /// Rebind actions from a keymap file. Each listed action replaces its
+    /// default keys; an empty list unbinds it. Unknown sections, actions
+    /// and keys are skipped and reported.
+    pub fn apply_toml(&mut self, contents: &str) -> Result<Vec<String>, String> {
+        let table: toml::Table = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let mut problems = Vec::new();
+
+        for (section, actions) in &table {
+            let Some(context) = KeyContext::from_name(section) else {
+                problems.push(format!("unknown section [{section}]"));
+                continue;
+            };
+            let Some(actions) = actions.as_table() else {
+                problems.push(format!("[{section}] must be a table"));
+                continue;
+            };
+
+            for (action, keys) in actions {
+                let Some(binding) = self
+                    .bindings
+                    .iter_mut()
+                    .find(|binding| binding.context == context && binding.action == action)
+                else {
+                    problems.push(format!("unknown action `{action}` in [{section}]"));
+                    continue;
+                };
+
+                let keys = match keys {
+                    toml::Value::String(key) => vec![key.as_str()],
+                    toml::Value::Array(keys) => {
+                        keys.iter().filter_map(|key| key.as_str()).collect()
+                    }
+                    _ => {
+                        problems.push(format!(
+                            "{section}.{action} must be a key or a list of keys"
+                        ));
+                        continue;
+                    }
+                };
+
+                binding.chords.clear();
+                for key in keys {
+                    match key.parse() {
+                        Ok(chord) => binding.chords.push(chord),
+                        Err(err) => problems.push(format!("{section}.{action}: {err}")),
+                    }
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Keys bound twice in one context, and view keys that hide reserved
+    /// global actions.
+    pub fn conflicts(&self) -> Vec<KeyConflict> {
+        let mut conflicts = Vec::new();
+        for (index, binding) in self.bindings.iter().enumerate() {
+            for chord in &binding.chords {
+                let earlier = self.bindings[..index].iter().find(|other| {
+                    other.chords.contains(chord)
+                        && (other.context == binding.context
+                            || (other.context == KeyContext::Global
+                                && RESERVED.contains(&other.action)
+                                && other.action != binding.action))
+                });
+                if let Some(other) = earlier {
+                    conflicts.push(KeyConflict {
+                        chord: *chord,
+                        first: (other.context, other.action),
+                        second: (binding.context, binding.action),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// The binding for `chord` in `context`. Within a context the first
+    /// binding listed wins.
+    pub fn resolve(&self, context: KeyContext, chord: KeyChord) -> Option<&KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.context == context && binding.chords.contains(&chord))
+    }
+
+    /// The key to hand to a view in `context` for `chord`, if the chord is
+    /// bound to one of the view's actions.
+    pub fn translate(&self, context: KeyContext, chord: KeyChord) -> Option<KeyEvent> {
+        match self.resolve(context, chord)?.target {
+            KeyTarget::View(key) => {
+                Some(key.parse::<KeyChord>().expect("view keys parse").to_event())
+            }
+            KeyTarget::Global(_) => None,
+        }
+    }
+
+    /// The keys bound to `action` in `context`, e.g. `F1`.
+    pub fn keys_label(&self, context: KeyContext, action: &str) -> Option<String> {
+        self.bindings(context)
+            .find(|binding| binding.action == action)
+            .map(KeyBinding::keys_label)
+    }
+
+    /// Bindings of `context` that have at least one key, in display order.
+    pub fn bindings(&self, context: KeyContext) -> impl Iterator<Item = &KeyBinding> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.context == context && !binding.chords.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(s: &str) -> KeyChord {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints_chords() {
+        assert_eq!(
+            chord("ctrl+r"),
+            KeyChord::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(chord("shift+tab").code, KeyCode::BackTab);
+        assert_eq!(chord("+").code, KeyCode::Char('+'));
+        assert_eq!(chord("alt++").modifiers, KeyModifiers::ALT);
+        assert_eq!(chord("F5").code, KeyCode::F(5));
+        assert!("hyper+x".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+
+        assert_eq!(chord("ctrl+r").to_string(), "Ctrl+R");
+        assert_eq!(chord("space").to_string(), "Space");
+        // Terminals report upper-case letters with or without Shift.
+        assert_eq!(
+            KeyChord::from(KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT)),
+            chord("Q")
+        );
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.conflicts(), Vec::new());
+
+        // The explorer's back key no longer shadows the global quit key.
+        assert!(keymap.resolve(KeyContext::Explorer, chord("q")).is_none());
+        assert_eq!(
+            keymap
+                .resolve(KeyContext::Global, chord("q"))
+                .map(|binding| binding.action),
+            Some("quit")
+        );
+    }
+
+    #[test]
+    fn user_file_rebinds_and_reports_problems() {
+        let mut keymap = Keymap::default();
+        let problems = keymap
+            .apply_toml(
+                r#"
+                [log_viewer]
+                search = "ctrl+f"
+                clear = ["ctrl+k", "bogus+key"]
+
+                [explorer]
+                back = ["esc", "q"]
+                launch = "x"
+
+                [nowhere]
+                "#,
+            )
+            .unwrap();
+        assert_eq!(problems.len(), 3, "{problems:?}");
+
+        assert_eq!(
+            keymap.translate(KeyContext::LogViewer, chord("ctrl+f")),
+            Some(chord("/").to_event())
+        );
+        assert!(
+            keymap
+                .translate(KeyContext::LogViewer, chord("/"))
+                .is_none()
+        );
+        assert_eq!(
+            keymap.translate(KeyContext::LogViewer, chord("ctrl+k")),
+            Some(chord("ctrl+c").to_event())
+        );
+
+        let conflicts = keymap.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].to_string(),
+            "`q` for back in [explorer] hides quit in [global]"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Log access backed by the files the Dora daemon writes to disk.
+//!
+//! The daemon stores one file per node under
+//! `<root>/<dataflow-uuid>/log_<node>.txt`, where `<root>` is usually the
+//! `out` directory next to the dataflow descriptor.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, mpsc},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use tui_interface::{InterfaceError, LogLevel, LogQuery, LogRecord, LogService, LogSubscription};
+
+#[derive(Debug, Clone)]
+pub struct LocalLogService {
+    root: PathBuf,
+    poll_interval: Duration,
+}
+
+impl LocalLogService {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    fn dataflow_dir(&self, query: &LogQuery) -> Result<PathBuf, InterfaceError> {
+        let dir = self.root.join(&query.dataflow);
+        if dir.is_dir() {
+            Ok(dir)
+        } else {
+            Err(InterfaceError::from(format!(
+                "no logs for dataflow `{}` under {}",
+                query.dataflow,
+                self.root.display()
+            )))
+        }
+    }
+}
+
+impl LogService for LocalLogService {
+    fn history(&self, query: &LogQuery, limit: usize) -> Result<Vec<LogRecord>, InterfaceError> {
+        let dir = self.dataflow_dir(query)?;
+
+        let mut records = Vec::new();
+        for (node, path) in log_files(&dir, query) {
+            let contents = fs::read_to_string(&path)
+                .map_err(|err| InterfaceError::from(format!("{}: {err}", path.display())))?;
+            // A line without its own timestamp belongs with the line before
+            // it; lines before the first timestamp are left unset.
+            let mut timestamp = SystemTime::UNIX_EPOCH;
+            for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                timestamp = line_timestamp(line).unwrap_or(timestamp);
+                records.push(record_from_line(&node, line, timestamp));
+            }
+        }
+
+        // Lines within a file keep their order; files are interleaved by
+        // the timestamps their lines start with.
+        records.sort_by_key(|record| record.timestamp);
+        let skip = records.len().saturating_sub(limit);
+        Ok(records.split_off(skip))
+    }
+
+    fn subscribe(&self, query: &LogQuery) -> Result<LogSubscription, InterfaceError> {
+        let dir = self.dataflow_dir(query)?;
+
+        // Only lines written after subscribing are delivered.
+        let mut offsets: HashMap<PathBuf, u64> = log_files(&dir, query)
+            .into_iter()
+            .map(|(_, path)| {
+                let len = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+                (path, len)
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::channel();
+        let subscription = LogSubscription::new(rx);
+        // A quiet file never sends, so the thread would not notice the
+        // subscription going away without the flag.
+        let closed = subscription.closed_flag();
+        let query = query.clone();
+        let poll_interval = self.poll_interval;
+        thread::spawn(move || {
+            loop {
+                thread::sleep(poll_interval);
+                if closed.load(Ordering::Relaxed) || !dir.is_dir() {
+                    break;
+                }
+
+                for (node, path) in log_files(&dir, &query) {
+                    let offset = offsets.entry(path.clone()).or_insert(0);
+                    let Ok(lines) = read_new_lines(&path, offset) else {
+                        continue;
+                    };
+                    for line in lines {
+                        let record = record_from_line(&node, &line, SystemTime::now());
+                        if tx.send(record).is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(subscription)
+    }
+}
+
+/// `(node, path)` pairs for the log files matching `query`, sorted by node.
+fn log_files(dir: &Path, query: &LogQuery) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let node = node_from_file_name(file_name.to_str()?)?.to_string();
+            query
+                .matches_node(Some(&node))
+                .then(|| (node, entry.path()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn node_from_file_name(file_name: &str) -> Option<&str> {
+    file_name.strip_prefix("log_")?.strip_suffix(".txt")
+}
+
+/// Read complete lines appended since `offset`, advancing it past them.
+/// A trailing partial line is left for the next poll.
+fn read_new_lines(path: &Path, offset: &mut u64) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len < *offset {
+        // Truncated or replaced; start over.
+        *offset = 0;
+    }
+    if len == *offset {
+        return Ok(Vec::new());
+    }
+
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut buffer = Vec::new();
+    file.take(len - *offset).read_to_end(&mut buffer)?;
+
+    let Some(last_newline) = buffer.iter().rposition(|byte| *byte == b'\n') else {
+        return Ok(Vec::new());
+    };
+    *offset += last_newline as u64 + 1;
+
+    Ok(String::from_utf8_lossy(&buffer[..last_newline])
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn record_from_line(node: &str, line: &str, timestamp: SystemTime) -> LogRecord {
+    LogRecord {
+        timestamp,
+        level: detect_level(line),
+        node: Some(node.to_string()),
+        message: line.to_string(),
+    }
+}
+
+/// The RFC 3339 timestamp a line starts with, as `tracing` writes them.
+fn line_timestamp(line: &str) -> Option<SystemTime> {
+    let word = line.split_whitespace().next()?;
+    let word = word.trim_start_matches('[').trim_end_matches(']');
+    chrono::DateTime::parse_from_rfc3339(word)
+        .ok()
+        .map(SystemTime::from)
+}
+
+/// Best-effort level detection from the first few words of a line.
+fn detect_level(line: &str) -> LogLevel {
+    for word in line.split_whitespace().take(4) {
+        let word = word.trim_matches(|c: char| !c.is_ascii_alphabetic());
+        match word.to_ascii_uppercase().as_str() {
+            "ERROR" | "ERR" => return LogLevel::Error,
+            "WARN" | "WARNING" => return LogLevel::Warn,
+            "INFO" => return LogLevel::Info,
+            "DEBUG" => return LogLevel::Debug,
+            "TRACE" => return LogLevel::Trace,
+            _ => {}
+        }
+    }
+    LogLevel::Info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_support;
+    use std::io::Write;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = test_support::scratch_dir(name);
+        fs::create_dir_all(dir.join("df-1")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn history_reads_node_files() {
+        let root = scratch_dir("history");
+        fs::write(
+            root.join("df-1/log_camera.txt"),
+            "INFO camera ready\n[WARN] frame dropped\n",
+        )
+        .unwrap();
+        fs::write(root.join("df-1/log_planner.txt"), "ERROR: no path\n").unwrap();
+        fs::write(root.join("df-1/unrelated.txt"), "ignored\n").unwrap();
+
+        let service = LocalLogService::new(&root);
+        let all = service.history(&LogQuery::dataflow("df-1"), 10).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let camera = service
+            .history(&LogQuery::dataflow("df-1").with_node("camera"), 1)
+            .unwrap();
+        assert_eq!(camera.len(), 1);
+        assert_eq!(camera[0].level, LogLevel::Warn);
+        assert_eq!(camera[0].message, "[WARN] frame dropped");
+
+        assert!(service.history(&LogQuery::dataflow("missing"), 10).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn history_orders_lines_by_their_timestamps() {
+        let root = scratch_dir("history-timestamps");
+        fs::write(
+            root.join("df-1/log_camera.txt"),
+            "2024-05-01T10:00:00Z INFO camera ready\n  continued\n2024-05-01T10:00:02Z INFO frame\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("df-1/log_planner.txt"),
+            "[2024-05-01T10:00:01.500Z] WARN slow plan\n",
+        )
+        .unwrap();
+
+        let history = LocalLogService::new(&root)
+            .history(&LogQuery::dataflow("df-1"), 10)
+            .unwrap();
+        let messages: Vec<_> = history
+            .iter()
+            .map(|record| record.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "2024-05-01T10:00:00Z INFO camera ready",
+                "  continued",
+                "[2024-05-01T10:00:01.500Z] WARN slow plan",
+                "2024-05-01T10:00:02Z INFO frame",
+            ]
+        );
+        assert_eq!(history[0].timestamp, history[1].timestamp);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn subscription_follows_appended_lines() {
+        let root = scratch_dir("follow");
+        let path = root.join("df-1/log_camera.txt");
+        fs::write(&path, "old line\n").unwrap();
+
+        let service = LocalLogService::new(&root).with_poll_interval(Duration::from_millis(10));
+        let rx = service.subscribe(&LogQuery::dataflow("df-1")).unwrap();
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "DEBUG new line").unwrap();
+
+        let record = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(record.message, "DEBUG new line");
+        assert_eq!(record.level, LogLevel::Debug);
+        assert_eq!(record.node.as_deref(), Some("camera"));
+
+        drop(rx);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -3,6 +3,8 @@ use std::time::{Duration, Instant};
 use eyre::Result;
 use sysinfo::{CpuExt, DiskExt, NetworkExt, NetworksExt, System, SystemExt};
 
+use tui_interface::MetricsSource;
+
 use crate::tui::app::{DiskMetrics, LoadAverages, MemoryMetrics, NetworkMetrics, SystemMetrics};
 
 #[derive(Debug)]
@@ -151,6 +153,7 @@ impl MetricsCollector {
             uptime: Duration::from_secs(self.system.uptime()),
             process_count: self.system.processes().len(),
             last_update: Some(now),
+            source: MetricsSource::Local,
         })
     }
 }
@@ -1,12 +1,36 @@
+pub mod accessibility;
+pub mod alerts;
 pub mod app;
 pub mod bridge;
 mod cli_integration;
 mod command_executor;
 pub mod components;
+pub mod connection;
+pub mod crash;
+pub mod deep_link;
+pub mod files;
+pub mod keymap;
+pub mod log_files;
 pub mod metrics;
+pub mod modal;
+pub mod mouse;
+pub mod notifier;
+pub mod palette;
+pub mod preferences;
+pub mod responsive;
+pub mod session;
+pub mod snapshot;
+pub mod tasks;
+pub mod telemetry;
 pub mod theme;
+pub mod view_manager;
 pub mod views;
+pub mod workspace;
 
+#[cfg(test)]
+mod render_tests;
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,460 @@
+//! Modal dialogs shown on top of the active view.
+//!
+//! While a dialog is open it receives every key. When it closes, the
+//! [`ModalResult`] is handed to the view that opened it through
+//! [`View::on_modal_result`](crate::tui::views::View::on_modal_result),
+//! together with the id the view chose when it asked for the dialog.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::tui::{app::ViewType, theme::ThemeConfig};
+
+/// How a dialog was closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModalResult {
+    Confirmed,
+    Cancelled,
+    Input(String),
+    Selected { index: usize, value: String },
+}
+
+/// An open dialog and the view waiting for its result.
+#[derive(Debug, Clone)]
+pub struct Modal {
+    id: String,
+    origin: ViewType,
+    kind: ModalKind,
+}
+
+#[derive(Debug, Clone)]
+enum ModalKind {
+    Confirm {
+        message: String,
+        confirm_selected: bool,
+    },
+    Input {
+        prompt: String,
+        input: TextInput,
+    },
+    Select {
+        title: String,
+        options: Vec<String>,
+        selected: usize,
+    },
+}
+
+impl Modal {
+    /// Yes/no question. `No` is selected initially so a stray Enter does
+    /// not confirm.
+    pub fn confirm(id: impl Into<String>, message: impl Into<String>, origin: ViewType) -> Self {
+        Self {
+            id: id.into(),
+            origin,
+            kind: ModalKind::Confirm {
+                message: message.into(),
+                confirm_selected: false,
+            },
+        }
+    }
+
+    pub fn input(
+        id: impl Into<String>,
+        prompt: impl Into<String>,
+        default: Option<String>,
+        origin: ViewType,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            origin,
+            kind: ModalKind::Input {
+                prompt: prompt.into(),
+                input: TextInput::new(default.unwrap_or_default()),
+            },
+        }
+    }
+
+    pub fn select(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        options: Vec<String>,
+        origin: ViewType,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            origin,
+            kind: ModalKind::Select {
+                title: title.into(),
+                options,
+                selected: 0,
+            },
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The view that opened the dialog.
+    pub fn origin(&self) -> &ViewType {
+        &self.origin
+    }
+
+    /// Handle a key. Returns the result once the dialog closes.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<ModalResult> {
+        if key.code == KeyCode::Esc {
+            return Some(ModalResult::Cancelled);
+        }
+
+        match &mut self.kind {
+            ModalKind::Confirm {
+                confirm_selected, ..
+            } => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Some(ModalResult::Confirmed),
+                KeyCode::Char('n') | KeyCode::Char('N') => Some(ModalResult::Cancelled),
+                KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Tab
+                | KeyCode::BackTab
+                | KeyCode::Char('h')
+                | KeyCode::Char('l') => {
+                    *confirm_selected = !*confirm_selected;
+                    None
+                }
+                KeyCode::Enter if *confirm_selected => Some(ModalResult::Confirmed),
+                KeyCode::Enter => Some(ModalResult::Cancelled),
+                _ => None,
+            },
+            ModalKind::Input { input, .. } => match key.code {
+                KeyCode::Enter => Some(ModalResult::Input(input.value().to_string())),
+                _ => {
+                    input.handle_key(key);
+                    None
+                }
+            },
+            ModalKind::Select {
+                options, selected, ..
+            } => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    *selected = selected.saturating_sub(1);
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if *selected + 1 < options.len() {
+                        *selected += 1;
+                    }
+                    None
+                }
+                KeyCode::Home => {
+                    *selected = 0;
+                    None
+                }
+                KeyCode::End => {
+                    *selected = options.len().saturating_sub(1);
+                    None
+                }
+                KeyCode::Enter => match options.get(*selected) {
+                    Some(value) => Some(ModalResult::Selected {
+                        index: *selected,
+                        value: value.clone(),
+                    }),
+                    None => Some(ModalResult::Cancelled),
+                },
+                _ => None,
+            },
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, theme: &ThemeConfig) {
+        match &self.kind {
+            ModalKind::Confirm {
+                message,
+                confirm_selected,
+            } => {
+                let popup = popup_area(area, 50, 7);
+                f.render_widget(Clear, popup);
+                let block = theme.styled_block("Confirm").borders(Borders::ALL);
+                let inner = block.inner(popup);
+                f.render_widget(block, popup);
+
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(1), Constraint::Length(1)])
+                    .split(inner);
+
+                f.render_widget(
+                    Paragraph::new(message.as_str())
+                        .style(Style::default().fg(theme.colors.text))
+                        .alignment(Alignment::Center)
+                        .wrap(Wrap { trim: true }),
+                    rows[0],
+                );
+
+                let button = |label: &'static str, active: bool| {
+                    if active {
+                        Span::styled(label, theme.styles.selection_style)
+                    } else {
+                        Span::styled(label, Style::default().fg(theme.colors.muted))
+                    }
+                };
+                let buttons = Line::from(vec![
+                    button(" Yes ", *confirm_selected),
+                    Span::raw("   "),
+                    button(" No ", !*confirm_selected),
+                ]);
+                f.render_widget(
+                    Paragraph::new(buttons).alignment(Alignment::Center),
+                    rows[1],
+                );
+            }
+            ModalKind::Input { prompt, input } => {
+                let popup = popup_area(area, 60, 5);
+                f.render_widget(Clear, popup);
+                let block = theme.styled_block(prompt).borders(Borders::ALL);
+                let inner = block.inner(popup);
+                f.render_widget(block, popup);
+
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(1), Constraint::Length(1)])
+                    .split(inner);
+
+                // Scroll the text so the cursor stays visible.
+                let width = usize::from(rows[0].width.max(1));
+                let offset = input.cursor().saturating_sub(width - 1);
+                let visible: String = input.value().chars().skip(offset).take(width).collect();
+                f.render_widget(
+                    Paragraph::new(visible).style(Style::default().fg(theme.colors.text)),
+                    rows[0],
+                );
+                f.set_cursor(rows[0].x + (input.cursor() - offset) as u16, rows[0].y);
+
+                f.render_widget(
+                    Paragraph::new("Enter to accept, Esc to cancel")
+                        .style(Style::default().fg(theme.colors.muted)),
+                    rows[1],
+                );
+            }
+            ModalKind::Select {
+                title,
+                options,
+                selected,
+            } => {
+                let height = u16::try_from(options.len())
+                    .unwrap_or(u16::MAX)
+                    .saturating_add(2);
+                let popup = popup_area(area, 50, height);
+                f.render_widget(Clear, popup);
+
+                let items: Vec<ListItem> = options
+                    .iter()
+                    .map(|option| ListItem::new(option.as_str()))
+                    .collect();
+                let list = List::new(items)
+                    .block(theme.styled_block(title).borders(Borders::ALL))
+                    .style(Style::default().fg(theme.colors.text))
+                    .highlight_style(theme.styles.selection_style.add_modifier(Modifier::BOLD))
+                    .highlight_symbol("▶ ");
+                let mut state = ListState::default().with_selected(Some(*selected));
+                f.render_stateful_widget(list, popup, &mut state);
+            }
+        }
+    }
+}
+
+/// Single-line text field with a cursor.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Cursor position in characters.
+    cursor: usize,
+}
+
+impl TextInput {
+    /// A field holding `value` with the cursor at the end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        *self = Self::new(value);
+    }
+
+    /// Apply an editing key. Returns `false` for keys that do not edit.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.len(),
+            KeyCode::Char('u') if ctrl => {
+                self.value = self.value.chars().skip(self.cursor).collect();
+                self.cursor = 0;
+            }
+            KeyCode::Char('k') if ctrl => {
+                self.value = self.value.chars().take(self.cursor).collect();
+            }
+            KeyCode::Char('w') if ctrl => self.delete_word_before_cursor(),
+            KeyCode::Char(c) if !ctrl => {
+                let at = self.byte_index(self.cursor);
+                self.value.insert(at, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_index(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Delete if self.cursor < self.len() => {
+                let at = self.byte_index(self.cursor);
+                self.value.remove(at);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            KeyCode::Backspace | KeyCode::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.value.len(), |(index, _)| index)
+    }
+
+    fn delete_word_before_cursor(&mut self) {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut start = self.cursor;
+        while start > 0 && chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.value = chars[..start].iter().chain(&chars[self.cursor..]).collect();
+        self.cursor = start;
+    }
+}
+
+/// Centered rectangle `percent_x` wide and `height` rows tall, clamped to
+/// `area`.
+fn popup_area(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let width = (area.width * percent_x / 100).max(30).min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn text_input_editing_keys() {
+        let mut input = TextInput::new("héllo");
+        assert_eq!(input.cursor(), 5);
+
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Delete));
+        input.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(input.value(), "hello");
+
+        input.handle_key(key(KeyCode::End));
+        for c in " world".chars() {
+            input.handle_key(key(KeyCode::Char(c)));
+        }
+        input.handle_key(ctrl('w'));
+        assert_eq!(input.value(), "hello ");
+
+        input.handle_key(key(KeyCode::Backspace));
+        input.handle_key(ctrl('a'));
+        input.handle_key(ctrl('k'));
+        assert_eq!(input.value(), "");
+        assert!(!input.handle_key(key(KeyCode::Up)));
+    }
+
+    #[test]
+    fn confirm_defaults_to_no() {
+        let mut modal = Modal::confirm("stop", "Stop?", ViewType::Dashboard);
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Enter)),
+            Some(ModalResult::Cancelled)
+        );
+
+        assert_eq!(modal.handle_key(key(KeyCode::Tab)), None);
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Enter)),
+            Some(ModalResult::Confirmed)
+        );
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Char('y'))),
+            Some(ModalResult::Confirmed)
+        );
+    }
+
+    #[test]
+    fn input_and_selection_results() {
+        let mut modal = Modal::input("path", "Path", Some("a".into()), ViewType::Dashboard);
+        assert_eq!(modal.handle_key(key(KeyCode::Char('b'))), None);
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Enter)),
+            Some(ModalResult::Input("ab".into()))
+        );
+
+        let mut modal = Modal::select(
+            "node",
+            "Node",
+            vec!["camera".into(), "detector".into()],
+            ViewType::Dashboard,
+        );
+        modal.handle_key(key(KeyCode::Down));
+        modal.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Enter)),
+            Some(ModalResult::Selected {
+                index: 1,
+                value: "detector".into()
+            })
+        );
+        assert_eq!(
+            modal.handle_key(key(KeyCode::Esc)),
+            Some(ModalResult::Cancelled)
+        );
+    }
+}
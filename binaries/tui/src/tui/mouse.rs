@@ -0,0 +1,120 @@
+//! Mouse hit testing.
+//!
+//! Views remember where they drew their lists and tab bars on the last frame
+//! and use these helpers to map clicks and wheel events back to rows and
+//! tabs. All helpers assume the widget was drawn inside a bordered block.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
+
+/// Rows moved per wheel notch.
+pub const SCROLL_STEP: usize = 3;
+
+/// The gesture a mouse event represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseGesture {
+    Click,
+    ScrollUp,
+    ScrollDown,
+}
+
+impl MouseGesture {
+    /// Left clicks and wheel notches; drags, releases and other buttons are
+    /// ignored.
+    pub fn from_event(event: &MouseEvent) -> Option<Self> {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Self::Click),
+            MouseEventKind::ScrollUp => Some(Self::ScrollUp),
+            MouseEventKind::ScrollDown => Some(Self::ScrollDown),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the event happened inside `area`.
+pub fn hits(area: Rect, event: &MouseEvent) -> bool {
+    area.contains(Position::new(event.column, event.row))
+}
+
+fn inner(area: Rect) -> Rect {
+    area.inner(Margin::new(1, 1))
+}
+
+/// Index of the list or table row under the event. `skip` is the number of
+/// lines above the first row, such as a table header and its margin.
+pub fn row_at(area: Rect, skip: u16, event: &MouseEvent) -> Option<usize> {
+    let inner = inner(area);
+    if !hits(inner, event) {
+        return None;
+    }
+    (event.row - inner.y).checked_sub(skip).map(usize::from)
+}
+
+/// Index of the tab under the event in a `Tabs` widget whose titles are
+/// `widths` columns wide, separated by dividers `divider_width` wide.
+pub fn tab_at(
+    area: Rect,
+    widths: impl IntoIterator<Item = usize>,
+    divider_width: usize,
+    event: &MouseEvent,
+) -> Option<usize> {
+    let inner = inner(area);
+    if !hits(inner, event) || event.row != inner.y {
+        return None;
+    }
+
+    let column = usize::from(event.column - inner.x);
+    let mut start = 0;
+    for (index, width) in widths.into_iter().enumerate() {
+        // `Tabs` pads every title with a space on each side.
+        let end = start + width + 2;
+        if column < end {
+            return Some(index);
+        }
+        start = end + divider_width;
+        if column < start {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn rows_skip_borders_and_headers() {
+        let area = Rect::new(10, 5, 20, 10);
+        assert_eq!(row_at(area, 0, &click(11, 6)), Some(0));
+        assert_eq!(row_at(area, 2, &click(11, 9)), Some(1));
+        assert_eq!(row_at(area, 2, &click(11, 7)), None);
+        // Borders and the outside are not rows.
+        assert_eq!(row_at(area, 0, &click(10, 6)), None);
+        assert_eq!(row_at(area, 0, &click(11, 14)), None);
+    }
+
+    #[test]
+    fn tabs_are_hit_by_title_and_padding() {
+        // " ab │ cde " inside a border starting at column 0.
+        let area = Rect::new(0, 0, 30, 3);
+        let tab = |column| tab_at(area, [2, 3], 1, &click(column, 1));
+        assert_eq!(tab(1), Some(0));
+        assert_eq!(tab(4), Some(0));
+        assert_eq!(tab(5), None);
+        assert_eq!(tab(6), Some(1));
+        assert_eq!(tab(10), Some(1));
+        assert_eq!(tab(11), None);
+        assert_eq!(tab_at(area, [2, 3], 1, &click(2, 2)), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! Terminal notifications.
+//!
+//! Events the user opted into are announced to the terminal itself, so they
+//! are noticed while the TUI sits in a background tab or tmux window. Each
+//! notification rings the bell, which every terminal and multiplexer
+//! understands (tmux flags the window), and where the terminal supports one
+//! sends a desktop notification: OSC 9 (iTerm2, WezTerm, ghostty) or OSC 777
+//! (foot, urxvt, VTE terminals). Inside tmux or screen the escape sequence is
+//! wrapped in their passthrough so it reaches the outer terminal; tmux 3.3
+//! and later also needs `set -g allow-passthrough on`.
+//!
+//! Each event is sent at most once per `RATE_LIMIT`; what is held back in
+//! between is counted in the next notification.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+const TITLE: &str = "dora";
+
+/// Shortest time between two notifications of one event.
+pub const RATE_LIMIT: Duration = Duration::from_secs(30);
+
+/// screen drops longer passthrough strings.
+const SCREEN_MAX_BYTES: usize = 700;
+
+/// Something worth interrupting the user for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotifyEvent {
+    DataflowFailed,
+    NodeRestarted,
+    AlertRaised,
+}
+
+impl NotifyEvent {
+    pub const ALL: [NotifyEvent; 3] =
+        [Self::DataflowFailed, Self::NodeRestarted, Self::AlertRaised];
+
+    /// Name in the `notify_on` preference.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DataflowFailed => "dataflow_failed",
+            Self::NodeRestarted => "node_restarted",
+            Self::AlertRaised => "alert_raised",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.name() == name)
+    }
+
+    /// The events in a `notify_on` preference, and the names that are not
+    /// events.
+    pub fn parse_list(names: &[String]) -> (Vec<Self>, Vec<String>) {
+        let mut events = Vec::new();
+        let mut unknown = Vec::new();
+        for name in names.iter().map(|name| name.trim()) {
+            match Self::from_name(name) {
+                Some(event) if !events.contains(&event) => events.push(event),
+                Some(_) => {}
+                None if name.is_empty() || name == "none" => {}
+                None => unknown.push(name.to_string()),
+            }
+        }
+        (events, unknown)
+    }
+}
+
+impl fmt::Display for NotifyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Desktop notification escape sequence understood by the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Only the bell
+    Bell,
+    Osc9,
+    Osc777,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+/// What the notifications are written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalKind {
+    pub protocol: Protocol,
+    pub multiplexer: Multiplexer,
+}
+
+impl TerminalKind {
+    /// Detect the terminal from the environment. `DORA_TUI_NOTIFY_PROTOCOL`
+    /// (`bell`, `osc9` or `osc777`) overrides the guess.
+    pub fn detect() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let multiplexer = if var("TMUX").is_some_and(|value| !value.is_empty()) {
+            Multiplexer::Tmux
+        } else if var("STY").is_some() || term.starts_with("screen") {
+            Multiplexer::Screen
+        } else {
+            Multiplexer::None
+        };
+
+        let protocol = match var("DORA_TUI_NOTIFY_PROTOCOL").as_deref() {
+            Some("bell") => Protocol::Bell,
+            Some("osc9") => Protocol::Osc9,
+            Some("osc777") => Protocol::Osc777,
+            // Multiplexers replace TERM_PROGRAM with their own, but iTerm2
+            // also sets LC_TERMINAL, which ssh and tmux pass on.
+            _ => match (
+                var("TERM_PROGRAM").as_deref(),
+                var("LC_TERMINAL").as_deref(),
+            ) {
+                (Some("iTerm.app" | "WezTerm" | "ghostty"), _) | (_, Some("iTerm2")) => {
+                    Protocol::Osc9
+                }
+                _ if var("VTE_VERSION").is_some()
+                    || term.starts_with("foot")
+                    || term.starts_with("rxvt") =>
+                {
+                    Protocol::Osc777
+                }
+                _ => Protocol::Bell,
+            },
+        };
+        Self {
+            protocol,
+            multiplexer,
+        }
+    }
+
+    /// The bytes that announce `body`.
+    pub fn sequence(self, body: &str) -> String {
+        let body = sanitize(body);
+        let notification = match self.protocol {
+            Protocol::Bell => return "\x07".to_string(),
+            Protocol::Osc9 => format!("\x1b]9;{TITLE}: {body}\x07"),
+            Protocol::Osc777 => format!("\x1b]777;notify;{TITLE};{body}\x07"),
+        };
+        let notification = match self.multiplexer {
+            Multiplexer::None => notification,
+            // Escapes inside tmux passthrough are doubled.
+            Multiplexer::Tmux => format!(
+                "\x1bPtmux;{}\x1b\\",
+                notification.replace('\x1b', "\x1b\x1b")
+            ),
+            Multiplexer::Screen => {
+                let mut notification = notification;
+                if notification.len() > SCREEN_MAX_BYTES {
+                    let mut end = SCREEN_MAX_BYTES - 1;
+                    while !notification.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    notification.truncate(end);
+                    notification.push('\x07');
+                }
+                format!("\x1bP{notification}\x1b\\")
+            }
+        };
+        // The bell goes first and outside any passthrough, so the
+        // multiplexer itself sees it.
+        format!("\x07{notification}")
+    }
+}
+
+/// Drop control characters, which would end the sequence early, and the
+/// separator of OSC 777.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
+
+/// Sends the events the user opted into, rate limited.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    terminal: TerminalKind,
+    events: Vec<NotifyEvent>,
+    last_sent: HashMap<NotifyEvent, Instant>,
+    /// Held back by the rate limit since the last notification
+    suppressed: HashMap<NotifyEvent, u32>,
+    /// Written to the terminal after the next frame
+    pending: Vec<String>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new(TerminalKind::detect())
+    }
+}
+
+impl Notifier {
+    /// A notifier with no events enabled.
+    pub fn new(terminal: TerminalKind) -> Self {
+        Self {
+            terminal,
+            events: Vec::new(),
+            last_sent: HashMap::new(),
+            suppressed: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn set_events(&mut self, events: Vec<NotifyEvent>) {
+        self.events = events;
+    }
+
+    pub fn events(&self) -> &[NotifyEvent] {
+        &self.events
+    }
+
+    /// Queue a notification for `event` if it is enabled and not rate
+    /// limited. Returns whether one was queued.
+    pub fn notify(&mut self, event: NotifyEvent, body: &str, now: Instant) -> bool {
+        if !self.events.contains(&event) {
+            return false;
+        }
+        let limited = self
+            .last_sent
+            .get(&event)
+            .is_some_and(|last| now.duration_since(*last) < RATE_LIMIT);
+        if limited {
+            *self.suppressed.entry(event).or_default() += 1;
+            return false;
+        }
+        let body = match self.suppressed.remove(&event) {
+            Some(held_back) => format!("{body} (and {held_back} more)"),
+            None => body.to_string(),
+        };
+        self.last_sent.insert(event, now);
+        self.pending.push(self.terminal.sequence(&body));
+        true
+    }
+
+    /// The queued sequences, oldest first.
+    pub fn take_pending(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal(vars: &[(&str, &str)]) -> TerminalKind {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        TerminalKind::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn terminals_get_the_sequences_they_understand() {
+        let iterm = terminal(&[("TERM_PROGRAM", "iTerm.app")]);
+        assert_eq!(
+            iterm.sequence("camera failed"),
+            "\x07\x1b]9;dora: camera failed\x07"
+        );
+
+        let foot = terminal(&[("TERM", "foot")]);
+        assert_eq!(
+            foot.sequence("cpu; at 95%\x1b[2J"),
+            "\x07\x1b]777;notify;dora;cpu, at 95%[2J\x07"
+        );
+
+        let plain = terminal(&[("TERM", "xterm-256color")]);
+        assert_eq!(plain.sequence("camera failed"), "\x07");
+
+        let tmux = terminal(&[
+            ("TMUX", "/tmp/tmux-1000/default,1,0"),
+            ("TERM_PROGRAM", "tmux"),
+            ("LC_TERMINAL", "iTerm2"),
+        ]);
+        assert_eq!(
+            tmux.sequence("camera failed"),
+            "\x07\x1bPtmux;\x1b\x1b]9;dora: camera failed\x07\x1b\\"
+        );
+
+        let screen = terminal(&[("TERM", "screen"), ("DORA_TUI_NOTIFY_PROTOCOL", "osc777")]);
+        assert_eq!(
+            screen.sequence("camera failed"),
+            "\x07\x1bP\x1b]777;notify;dora;camera failed\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn events_are_opt_in_and_rate_limited() {
+        let mut notifier = Notifier::new(terminal(&[("TERM_PROGRAM", "WezTerm")]));
+        let start = Instant::now();
+        assert!(!notifier.notify(NotifyEvent::DataflowFailed, "camera failed", start));
+
+        let (events, unknown) = NotifyEvent::parse_list(&[
+            "dataflow_failed".to_string(),
+            " alert_raised".to_string(),
+            "node_exploded".to_string(),
+        ]);
+        assert_eq!(unknown, vec!["node_exploded".to_string()]);
+        notifier.set_events(events);
+
+        assert!(notifier.notify(NotifyEvent::DataflowFailed, "camera failed", start));
+        assert!(!notifier.notify(NotifyEvent::NodeRestarted, "detector restarted", start));
+        let later = start + Duration::from_secs(5);
+        assert!(!notifier.notify(NotifyEvent::DataflowFailed, "lidar failed", later));
+        assert!(!notifier.notify(NotifyEvent::DataflowFailed, "radar failed", later));
+        // Other events have their own limit.
+        assert!(notifier.notify(NotifyEvent::AlertRaised, "cpu at 95%", later));
+
+        let much_later = start + RATE_LIMIT;
+        assert!(notifier.notify(NotifyEvent::DataflowFailed, "gps failed", much_later));
+        assert_eq!(
+            notifier.take_pending(),
+            vec![
+                "\x07\x1b]9;dora: camera failed\x07".to_string(),
+                "\x07\x1b]9;dora: cpu at 95%\x07".to_string(),
+                "\x07\x1b]9;dora: gps failed (and 2 more)\x07".to_string(),
+            ]
+        );
+        assert!(notifier.take_pending().is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+//! Terminal color depth.
+//!
+//! Themes may use any RGB color. Terminals that can't show them get the
+//! nearest color of the 256-color palette, or of the 16 ANSI colors.
+
+use ratatui::style::{Color, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Ansi16,
+    Indexed256,
+    TrueColor,
+}
+
+/// The 16 ANSI colors with xterm's default values.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 color cube at indices 16-231.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorDepth {
+    /// `DORA_TUI_COLORS` (`16`, `256` or `truecolor`) if set, otherwise what
+    /// `COLORTERM` and `TERM` advertise.
+    pub fn detect() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(value) = var("DORA_TUI_COLORS") {
+            match Self::from_name(&value) {
+                Some(depth) => return depth,
+                None => tracing::warn!("ignoring unknown color depth `{value}`"),
+            }
+        }
+        if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+            Self::TrueColor
+        } else if var("TERM").is_some_and(|term| term.contains("256color")) {
+            Self::Indexed256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "16" | "ansi" => Some(Self::Ansi16),
+            "256" => Some(Self::Indexed256),
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            _ => None,
+        }
+    }
+
+    /// The closest color this depth can show.
+    pub fn convert(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Indexed256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed(r, g, b)),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi(r, g, b),
+            (Self::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_ansi(r, g, b)
+            }
+            _ => color,
+        }
+    }
+
+    pub fn convert_style(self, style: Style) -> Style {
+        Style {
+            fg: style.fg.map(|color| self.convert(color)),
+            bg: style.bg.map(|color| self.convert(color)),
+            ..style
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).unsigned_abs().pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn nearest_ansi(r: u8, g: u8, b: u8) -> Color {
+    ANSI.iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _)| *color)
+        .expect("the palette is not empty")
+}
+
+/// The nearest color of the cube or of the gray ramp at indices 232-255.
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .expect("the cube has levels") as u8
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if distance(indexed_rgb(gray), (r, g, b)) < distance(indexed_rgb(cube), (r, g, b)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[index as usize].1,
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_follows_the_environment() {
+        let detect = |vars: &[(&str, &str)]| {
+            ColorDepth::from_vars(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color")]),
+            ColorDepth::Indexed256
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(&[("COLORTERM", "truecolor"), ("DORA_TUI_COLORS", "16")]),
+            ColorDepth::Ansi16
+        );
+    }
+
+    #[test]
+    fn colors_are_downgraded_to_the_nearest() {
+        let orange = Color::Rgb(255, 165, 0);
+        assert_eq!(ColorDepth::TrueColor.convert(orange), orange);
+        assert_eq!(ColorDepth::Indexed256.convert(orange), Color::Indexed(214));
+        assert_eq!(ColorDepth::Ansi16.convert(orange), Color::Yellow);
+
+        // Grays use the ramp rather than the cube.
+        assert_eq!(
+            ColorDepth::Indexed256.convert(Color::Rgb(0x30, 0x30, 0x30)),
+            Color::Indexed(236)
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.convert(Color::Indexed(236)),
+            Color::Black
+        );
+        assert_eq!(ColorDepth::Ansi16.convert(Color::Cyan), Color::Cyan);
+    }
+}
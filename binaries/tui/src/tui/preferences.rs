@@ -0,0 +1,568 @@
+//! Layered user preferences.
+//!
+//! Preferences are resolved from, highest priority first: command-line
+//! flags, `DORA_TUI_*` environment variables, the local `tui.toml` file in
+//! the user config directory, and finally the gateway. Only the file keeps
+//! every field; the gateway stores the subset its API knows about.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use tui_interface::{InterfaceError, PreferencesStore, UiMode, UserPreferencesSnapshot};
+
+use crate::tui::{files::write_atomically, notifier::NotifyEvent};
+
+const PREFERENCES_FILE: &str = "tui.toml";
+
+/// A partial set of preferences; unset fields defer to lower layers.
+///
+/// `default_view` and `ui_mode` are optional in the snapshot too, so an
+/// empty string stands for "cleared" rather than "unset".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreferencesOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_refresh_interval_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_system_info: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_view: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse_capture: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accessible: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_on: Option<Vec<String>>,
+}
+
+impl PreferencesOverrides {
+    /// Read `DORA_TUI_THEME`, `DORA_TUI_REFRESH_INTERVAL`,
+    /// `DORA_TUI_SHOW_SYSTEM_INFO`, `DORA_TUI_DEFAULT_VIEW`,
+    /// `DORA_TUI_UI_MODE`, `DORA_TUI_MOUSE`, `DORA_TUI_ACCESSIBLE` and
+    /// `DORA_TUI_NOTIFY` (comma-separated events).
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            theme: var("DORA_TUI_THEME"),
+            auto_refresh_interval_secs: parse_var(&var, "DORA_TUI_REFRESH_INTERVAL"),
+            show_system_info: parse_var(&var, "DORA_TUI_SHOW_SYSTEM_INFO"),
+            default_view: var("DORA_TUI_DEFAULT_VIEW"),
+            ui_mode: var("DORA_TUI_UI_MODE"),
+            mouse_capture: parse_var(&var, "DORA_TUI_MOUSE"),
+            accessible: parse_var(&var, "DORA_TUI_ACCESSIBLE"),
+            notify_on: var("DORA_TUI_NOTIFY").map(|events| split_list(&events)),
+        }
+    }
+
+    pub fn apply(&self, snapshot: &mut UserPreferencesSnapshot) {
+        if let Some(theme) = &self.theme {
+            snapshot.theme = theme.clone();
+        }
+        if let Some(interval) = self.auto_refresh_interval_secs {
+            snapshot.auto_refresh_interval_secs = interval;
+        }
+        if let Some(show) = self.show_system_info {
+            snapshot.show_system_info = show;
+        }
+        if let Some(view) = &self.default_view {
+            snapshot.default_view = (!view.is_empty()).then(|| view.clone());
+        }
+        if let Some(mode) = &self.ui_mode {
+            if mode.is_empty() {
+                snapshot.ui_mode = None;
+            } else {
+                match UiMode::from_name(mode) {
+                    Some(mode) => snapshot.ui_mode = Some(mode),
+                    None => warn!("ignoring unknown ui mode `{mode}`"),
+                }
+            }
+        }
+        if let Some(capture) = self.mouse_capture {
+            snapshot.mouse_capture = capture;
+        }
+        if let Some(accessible) = self.accessible {
+            snapshot.accessible = accessible;
+        }
+        if let Some(events) = &self.notify_on {
+            snapshot.notify_on = events.clone();
+        }
+    }
+}
+
+impl PreferencesOverrides {
+    /// The fields of `to` that differ from `from`.
+    pub fn changes(from: &UserPreferencesSnapshot, to: &UserPreferencesSnapshot) -> Self {
+        fn changed<T: PartialEq>(from: Option<T>, to: Option<T>) -> Option<T> {
+            if from == to { None } else { to }
+        }
+        let (from, to) = (Self::from(from), Self::from(to));
+        Self {
+            theme: changed(from.theme, to.theme),
+            auto_refresh_interval_secs: changed(
+                from.auto_refresh_interval_secs,
+                to.auto_refresh_interval_secs,
+            ),
+            show_system_info: changed(from.show_system_info, to.show_system_info),
+            default_view: changed(from.default_view, to.default_view),
+            ui_mode: changed(from.ui_mode, to.ui_mode),
+            mouse_capture: changed(from.mouse_capture, to.mouse_capture),
+            accessible: changed(from.accessible, to.accessible),
+            notify_on: changed(from.notify_on, to.notify_on),
+        }
+    }
+
+    /// These overrides with the fields `other` sets replaced.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            theme: other.theme.or(self.theme),
+            auto_refresh_interval_secs: other
+                .auto_refresh_interval_secs
+                .or(self.auto_refresh_interval_secs),
+            show_system_info: other.show_system_info.or(self.show_system_info),
+            default_view: other.default_view.or(self.default_view),
+            ui_mode: other.ui_mode.or(self.ui_mode),
+            mouse_capture: other.mouse_capture.or(self.mouse_capture),
+            accessible: other.accessible.or(self.accessible),
+            notify_on: other.notify_on.or(self.notify_on),
+        }
+    }
+
+    /// These overrides without the fields `keys` sets.
+    pub fn without(self, keys: &Self) -> Self {
+        Self {
+            theme: self.theme.filter(|_| keys.theme.is_none()),
+            auto_refresh_interval_secs: self
+                .auto_refresh_interval_secs
+                .filter(|_| keys.auto_refresh_interval_secs.is_none()),
+            show_system_info: self
+                .show_system_info
+                .filter(|_| keys.show_system_info.is_none()),
+            default_view: self.default_view.filter(|_| keys.default_view.is_none()),
+            ui_mode: self.ui_mode.filter(|_| keys.ui_mode.is_none()),
+            mouse_capture: self.mouse_capture.filter(|_| keys.mouse_capture.is_none()),
+            accessible: self.accessible.filter(|_| keys.accessible.is_none()),
+            notify_on: self.notify_on.filter(|_| keys.notify_on.is_none()),
+        }
+    }
+}
+
+/// Set the preference named `key` from its string form, as given to
+/// `:set`. Returns false if the key is unknown or the value invalid.
+pub fn set_preference_by_name(
+    snapshot: &mut UserPreferencesSnapshot,
+    key: &str,
+    value: &str,
+) -> bool {
+    match key {
+        "theme" => {
+            snapshot.theme = value.to_string();
+            true
+        }
+        "auto_refresh_interval_secs" | "refresh_interval" => value
+            .parse()
+            .map(|secs| snapshot.auto_refresh_interval_secs = secs)
+            .is_ok(),
+        "show_system_info" => value
+            .parse()
+            .map(|show| snapshot.show_system_info = show)
+            .is_ok(),
+        "mouse_capture" | "mouse" => value
+            .parse()
+            .map(|capture| snapshot.mouse_capture = capture)
+            .is_ok(),
+        "accessible" => value
+            .parse()
+            .map(|accessible| snapshot.accessible = accessible)
+            .is_ok(),
+        "default_view" => {
+            snapshot.default_view = Some(value.to_string());
+            true
+        }
+        "notify_on" | "notify" => {
+            let (events, unknown) = NotifyEvent::parse_list(&split_list(value));
+            snapshot.notify_on = events.iter().map(|event| event.to_string()).collect();
+            unknown.is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// The items of a comma-separated list.
+pub fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_var<T: std::str::FromStr>(var: impl Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+    let value = var(name)?;
+    match value.trim().parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            warn!("ignoring {name}={value}: not a valid value");
+            None
+        }
+    }
+}
+
+impl From<&UserPreferencesSnapshot> for PreferencesOverrides {
+    fn from(snapshot: &UserPreferencesSnapshot) -> Self {
+        Self {
+            theme: Some(snapshot.theme.clone()),
+            auto_refresh_interval_secs: Some(snapshot.auto_refresh_interval_secs),
+            show_system_info: Some(snapshot.show_system_info),
+            default_view: Some(snapshot.default_view.clone().unwrap_or_default()),
+            ui_mode: Some(
+                snapshot
+                    .ui_mode
+                    .map(|mode| mode.as_str().to_string())
+                    .unwrap_or_default(),
+            ),
+            mouse_capture: Some(snapshot.mouse_capture),
+            accessible: Some(snapshot.accessible),
+            notify_on: Some(snapshot.notify_on.clone()),
+        }
+    }
+}
+
+/// Preferences kept in a TOML file.
+#[derive(Debug, Clone)]
+pub struct FilePreferencesStore {
+    path: PathBuf,
+}
+
+impl FilePreferencesStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `<config dir>/dora/tui.toml`, if the platform has a config dir.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("dora").join(PREFERENCES_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fields set in the file. A missing file sets nothing.
+    pub fn load_overrides(&self) -> Result<PreferencesOverrides, InterfaceError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(PreferencesOverrides::default());
+            }
+            Err(err) => return Err(self.error(err)),
+        };
+        toml::from_str(&contents).map_err(|err| self.error(err))
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> InterfaceError {
+        InterfaceError::from(format!("{}: {err}", self.path.display()))
+    }
+}
+
+impl PreferencesStore for FilePreferencesStore {
+    fn load(&self) -> Result<UserPreferencesSnapshot, InterfaceError> {
+        let mut snapshot = UserPreferencesSnapshot::default();
+        self.load_overrides()?.apply(&mut snapshot);
+        Ok(snapshot)
+    }
+
+    fn save(&self, prefs: &UserPreferencesSnapshot) -> Result<(), InterfaceError> {
+        self.save_overrides(&PreferencesOverrides::from(prefs))
+    }
+}
+
+impl FilePreferencesStore {
+    /// Replace the file with `overrides`; unset fields are left out.
+    pub fn save_overrides(&self, overrides: &PreferencesOverrides) -> Result<(), InterfaceError> {
+        let contents = toml::to_string_pretty(overrides).map_err(|err| self.error(err))?;
+        write_atomically(&self.path, &contents).map_err(|err| self.error(err))
+    }
+}
+
+/// Resolves preferences across CLI flags, environment, local file and
+/// gateway.
+///
+/// Loading never fails: a layer that cannot be read is skipped with a
+/// warning, and built-in defaults sit underneath everything. Saving writes
+/// only the fields that differ from what is loaded to the file, and mirrors
+/// them to the gateway on a best effort basis. Flags and environment
+/// variables are never saved; one the user changes is dropped for the rest
+/// of the session.
+pub struct LayeredPreferencesStore {
+    cli: Mutex<PreferencesOverrides>,
+    env: Mutex<PreferencesOverrides>,
+    file: Option<FilePreferencesStore>,
+    gateway: Option<Arc<dyn PreferencesStore>>,
+}
+
+impl LayeredPreferencesStore {
+    /// Store using the process environment and the default file location.
+    pub fn new(gateway: Option<Arc<dyn PreferencesStore>>) -> Self {
+        Self {
+            cli: Mutex::default(),
+            env: Mutex::new(PreferencesOverrides::from_env()),
+            file: FilePreferencesStore::default_path().map(FilePreferencesStore::new),
+            gateway,
+        }
+    }
+
+    pub fn with_cli_overrides(mut self, overrides: PreferencesOverrides) -> Self {
+        self.cli = Mutex::new(overrides);
+        self
+    }
+
+    pub fn with_env_overrides(mut self, overrides: PreferencesOverrides) -> Self {
+        self.env = Mutex::new(overrides);
+        self
+    }
+
+    pub fn with_file(mut self, file: Option<FilePreferencesStore>) -> Self {
+        self.file = file;
+        self
+    }
+}
+
+impl PreferencesStore for LayeredPreferencesStore {
+    fn load(&self) -> Result<UserPreferencesSnapshot, InterfaceError> {
+        let mut snapshot = match &self.gateway {
+            Some(gateway) => gateway.load().unwrap_or_else(|err| {
+                warn!("gateway preferences unavailable: {err}");
+                UserPreferencesSnapshot::default()
+            }),
+            None => UserPreferencesSnapshot::default(),
+        };
+
+        if let Some(file) = &self.file {
+            match file.load_overrides() {
+                Ok(overrides) => overrides.apply(&mut snapshot),
+                Err(err) => warn!("skipping preferences file: {err}"),
+            }
+        }
+        self.env.lock().unwrap().apply(&mut snapshot);
+        self.cli.lock().unwrap().apply(&mut snapshot);
+
+        Ok(snapshot)
+    }
+
+    fn save(&self, prefs: &UserPreferencesSnapshot) -> Result<(), InterfaceError> {
+        let changes = PreferencesOverrides::changes(&self.load()?, prefs);
+        if changes == PreferencesOverrides::default() {
+            return Ok(());
+        }
+        for layer in [&self.cli, &self.env] {
+            let mut layer = layer.lock().unwrap();
+            *layer = std::mem::take(&mut *layer).without(&changes);
+        }
+
+        let gateway_result = self.gateway.as_ref().map(|gateway| {
+            let mut remote = gateway.load()?;
+            changes.apply(&mut remote);
+            gateway.save(&remote)
+        });
+
+        match &self.file {
+            Some(file) => {
+                if let Some(Err(err)) = gateway_result {
+                    warn!("preferences saved locally only: {err}");
+                }
+                let overrides = file.load_overrides()?.merge(changes);
+                file.save_overrides(&overrides)
+            }
+            None => gateway_result.unwrap_or(Ok(())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_support::scratch_dir;
+    use std::collections::HashMap;
+    use tui_interface::MockPreferencesStore;
+
+    fn scratch_file(name: &str) -> PathBuf {
+        scratch_dir(name).join(PREFERENCES_FILE)
+    }
+
+    #[test]
+    fn file_round_trips_every_field() {
+        let path = scratch_file("prefs-round-trip");
+        let store = FilePreferencesStore::new(&path);
+        let prefs = UserPreferencesSnapshot {
+            theme: "light".to_string(),
+            auto_refresh_interval_secs: 12,
+            show_system_info: false,
+            default_view: Some("monitor".to_string()),
+            ui_mode: Some(UiMode::Minimal),
+            mouse_capture: false,
+            accessible: true,
+            notify_on: vec!["dataflow_failed".to_string()],
+        };
+
+        assert_eq!(store.load().unwrap(), UserPreferencesSnapshot::default());
+        store.save(&prefs).unwrap();
+        assert_eq!(store.load().unwrap(), prefs);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn layers_apply_in_priority_order() {
+        let path = scratch_file("prefs-layers");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "theme = \"file\"\nauto_refresh_interval_secs = 9\ndefault_view = \"logs\"\n",
+        )
+        .unwrap();
+
+        let gateway = Arc::new(MockPreferencesStore::new());
+        gateway.set_load_result(Ok(UserPreferencesSnapshot {
+            theme: "gateway".to_string(),
+            show_system_info: false,
+            ui_mode: Some(UiMode::Tui),
+            ..Default::default()
+        }));
+
+        let env: HashMap<_, _> = [
+            ("DORA_TUI_THEME", "env"),
+            ("DORA_TUI_REFRESH_INTERVAL", "3"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let store = LayeredPreferencesStore::new(Some(gateway))
+            .with_file(Some(FilePreferencesStore::new(&path)))
+            .with_env_overrides(PreferencesOverrides::from_vars(|name| {
+                env.get(name).cloned()
+            }))
+            .with_cli_overrides(PreferencesOverrides {
+                theme: Some("cli".to_string()),
+                ..Default::default()
+            });
+
+        let prefs = store.load().unwrap();
+        assert_eq!(prefs.theme, "cli");
+        assert_eq!(prefs.auto_refresh_interval_secs, 3);
+        assert_eq!(prefs.default_view.as_deref(), Some("logs"));
+        assert!(!prefs.show_system_info);
+        assert_eq!(prefs.ui_mode, Some(UiMode::Tui));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn saving_writes_only_the_changed_field() {
+        let path = scratch_file("prefs-changed-only");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "default_view = \"logs\"\n").unwrap();
+
+        let store = LayeredPreferencesStore::new(None)
+            .with_file(Some(FilePreferencesStore::new(&path)))
+            .with_env_overrides(PreferencesOverrides {
+                auto_refresh_interval_secs: Some(3),
+                ..Default::default()
+            })
+            .with_cli_overrides(PreferencesOverrides {
+                theme: Some("solarized-dark".to_string()),
+                accessible: Some(true),
+                ..Default::default()
+            });
+
+        let mut prefs = store.load().unwrap();
+        prefs.mouse_capture = false;
+        store.save(&prefs).unwrap();
+        let saved = FilePreferencesStore::new(&path).load_overrides().unwrap();
+        assert_eq!(
+            saved,
+            PreferencesOverrides {
+                default_view: Some("logs".to_string()),
+                mouse_capture: Some(false),
+                ..Default::default()
+            }
+        );
+        assert_eq!(store.load().unwrap(), prefs);
+
+        // Changing a key given on the command line replaces the flag.
+        prefs.theme = "light".to_string();
+        store.save(&prefs).unwrap();
+        let prefs = store.load().unwrap();
+        assert_eq!(prefs.theme, "light");
+        assert!(prefs.accessible);
+        assert_eq!(prefs.auto_refresh_interval_secs, 3);
+        let saved = FilePreferencesStore::new(&path).load_overrides().unwrap();
+        assert_eq!(saved.theme.as_deref(), Some("light"));
+        assert_eq!(saved.accessible, None);
+        assert_eq!(saved.auto_refresh_interval_secs, None);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn clearing_a_value_is_saved() {
+        let path = scratch_file("prefs-clear");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "default_view = \"logs\"\nui_mode = \"minimal\"\n").unwrap();
+
+        let gateway = Arc::new(MockPreferencesStore::new());
+        gateway.set_load_result(Ok(UserPreferencesSnapshot {
+            default_view: Some("monitor".to_string()),
+            ..Default::default()
+        }));
+        let store = LayeredPreferencesStore::new(Some(gateway))
+            .with_file(Some(FilePreferencesStore::new(&path)))
+            .with_env_overrides(PreferencesOverrides::default());
+
+        let mut prefs = store.load().unwrap();
+        assert_eq!(prefs.default_view.as_deref(), Some("logs"));
+        prefs.default_view = None;
+        prefs.ui_mode = None;
+        store.save(&prefs).unwrap();
+
+        // The cleared value also hides the gateway's.
+        let prefs = store.load().unwrap();
+        assert_eq!(prefs.default_view, None);
+        assert_eq!(prefs.ui_mode, None);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn gateway_outage_falls_back_to_file() {
+        let path = scratch_file("prefs-outage");
+        let gateway = Arc::new(MockPreferencesStore::new());
+        gateway.set_load_result(Err(InterfaceError::from("connection refused")));
+        gateway.set_save_result(Err(InterfaceError::from("connection refused")));
+
+        let store = LayeredPreferencesStore::new(Some(gateway))
+            .with_file(Some(FilePreferencesStore::new(&path)))
+            .with_env_overrides(PreferencesOverrides::default());
+
+        let prefs = UserPreferencesSnapshot {
+            theme: "light".to_string(),
+            ..Default::default()
+        };
+        store.save(&prefs).unwrap();
+        assert_eq!(store.load().unwrap(), prefs);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,309 @@
+//! Golden render tests.
+//!
+//! Every view is drawn from a fixed `AppState` at several terminal sizes and
+//! the text of the buffer is compared with `binaries/tui/tests/golden/`.
+//! After an intended layout change, regenerate the files with
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test -p dora-tui render_tests
+//! ```
+//!
+//! and review the diff. Only the characters are compared, not colors, and
+//! relative times such as `0.004s ago` are masked.
+
+use std::{
+    fmt::Write as _,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::{Local, NaiveDate, TimeZone};
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+use tui_interface::{
+    DataflowStatus, LoadAverages, LogLevel, LogRecord, MemoryMetrics, MetricsSource,
+    MockLogService, NodeKind, NodeStatus,
+};
+
+use crate::tui::{
+    app::{
+        AppState, DataflowInfo, DiskMetrics, MessageLevel, MessageTarget, NetworkMetrics, NodeInfo,
+        StatusMessage, SystemMetrics, ViewType,
+    },
+    connection::Link,
+    keymap::Keymap,
+    theme::ThemeConfig,
+    view_manager::ViewInstance,
+};
+
+/// Narrow, classic, roomy and ultrawide terminals.
+const SIZES: [(u16, u16); 4] = [(60, 20), (80, 24), (120, 40), (180, 40)];
+
+fn node(id: &str, status: NodeStatus, kind: NodeKind) -> NodeInfo {
+    NodeInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        status,
+        kind,
+        inputs: vec!["tick".to_string()],
+        outputs: vec!["image".to_string()],
+        ..Default::default()
+    }
+}
+
+fn fixture_state() -> AppState {
+    let mut state = AppState {
+        dataflows: vec![
+            DataflowInfo {
+                id: "6f1c2a7e-0000-4000-8000-000000000001".to_string(),
+                name: "camera-pipeline".to_string(),
+                status: DataflowStatus::Running,
+                nodes: vec![
+                    node("camera", NodeStatus::Running, NodeKind::Custom),
+                    node("detector", NodeStatus::Running, NodeKind::Operator),
+                    node("plotter", NodeStatus::Failed, NodeKind::Custom),
+                ],
+            },
+            DataflowInfo {
+                id: "6f1c2a7e-0000-4000-8000-000000000002".to_string(),
+                name: "batch-export".to_string(),
+                status: DataflowStatus::Finished,
+                nodes: vec![node("exporter", NodeStatus::Completed, NodeKind::Custom)],
+            },
+        ],
+        ..Default::default()
+    };
+
+    let metrics = SystemMetrics {
+        cpu_usage: 42.5,
+        memory_usage: 63.0,
+        network_io: (1_048_576, 524_288),
+        memory: MemoryMetrics {
+            total_bytes: 16 * 1024 * 1024 * 1024,
+            used_bytes: 10 * 1024 * 1024 * 1024,
+            free_bytes: 6 * 1024 * 1024 * 1024,
+            usage_percent: 63.0,
+            ..Default::default()
+        },
+        disk: DiskMetrics {
+            total_bytes: 512 * 1024 * 1024 * 1024,
+            used_bytes: 128 * 1024 * 1024 * 1024,
+            usage_percent: 25.0,
+        },
+        network: NetworkMetrics {
+            total_received: 1_048_576,
+            total_transmitted: 524_288,
+            received_per_second: 2048.0,
+            transmitted_per_second: 1024.0,
+        },
+        load_average: Some(LoadAverages {
+            one: 0.5,
+            five: 0.75,
+            fifteen: 1.0,
+        }),
+        uptime: Duration::from_secs(3 * 3600 + 25 * 60),
+        process_count: 212,
+        last_update: None,
+        source: MetricsSource::Local,
+    };
+    for step in 0..30 {
+        let sample = SystemMetrics {
+            cpu_usage: 20.0 + (step % 10) as f32 * 5.0,
+            memory_usage: 60.0 + (step % 4) as f32,
+            ..metrics.clone()
+        };
+        state.record_system_metrics(&sample);
+    }
+    state.system_metrics = metrics;
+    state.user_config.show_system_info = true;
+
+    let time = Local
+        .from_local_datetime(
+            &NaiveDate::from_ymd_opt(2024, 5, 1)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+        )
+        .unwrap();
+    let messages = [
+        (
+            "coordinator",
+            "❌ failed to fetch dataflows: timed out",
+            MessageLevel::Error,
+            None,
+        ),
+        (
+            "control",
+            "✅ stop requested for camera-pipeline (op-7)",
+            MessageLevel::Success,
+            Some(MessageTarget::Dataflow("camera-pipeline".to_string())),
+        ),
+    ];
+    for (index, (source, message, level, target)) in messages.into_iter().enumerate() {
+        let mut message =
+            StatusMessage::new(source, message.to_string(), level).with_target(target);
+        message.time = time + chrono::Duration::seconds(index as i64 * 42);
+        // Old enough that no toast is drawn over the view.
+        message.timestamp -= Duration::from_secs(60);
+        state.push_status_message(message);
+    }
+    state
+        .connection
+        .record_success(Link::Gateway, None, Instant::now());
+    state
+}
+
+fn log_service() -> Arc<MockLogService> {
+    let service = Arc::new(MockLogService::new());
+    let records = [
+        (LogLevel::Info, Some("camera"), "camera opened /dev/video0"),
+        (LogLevel::Debug, Some("detector"), "loaded model yolov8n"),
+        (
+            LogLevel::Warn,
+            Some("detector"),
+            "frame dropped: queue full",
+        ),
+        (LogLevel::Error, Some("plotter"), "display not available"),
+        (LogLevel::Info, None, "dataflow camera-pipeline running"),
+    ];
+    service.set_history(Ok(records
+        .into_iter()
+        .map(|(level, node, message)| LogRecord {
+            timestamp: SystemTime::UNIX_EPOCH,
+            level,
+            node: node.map(str::to_string),
+            message: message.to_string(),
+        })
+        .collect()));
+    service
+}
+
+/// Mount `view_type`, run one update like the first frame does, and draw
+/// it at every size.
+fn render_view(view_type: &ViewType) -> String {
+    let theme = ThemeConfig::default_dark();
+    let keymap = Arc::new(Keymap::default());
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let mut out = String::new();
+    for (width, height) in SIZES {
+        let mut state = fixture_state();
+        let mut view = ViewInstance::build(view_type, &theme, &keymap, Some(log_service()));
+        runtime.block_on(async {
+            view.on_mount(&mut state).await.unwrap();
+            view.update(&mut state).await.unwrap();
+            if let ViewInstance::LogViewer(viewer) = &mut view {
+                viewer.wait_for_connection(&mut state).await.unwrap();
+            }
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let frame = terminal.draw(|f| view.render(f, f.size(), &state)).unwrap();
+        let _ = writeln!(out, "=== {width}x{height} ===");
+        out.push_str(&buffer_text(frame.buffer));
+    }
+    mask_relative_times(&out)
+}
+
+/// Replace the digits of `<seconds>s ago` with `#`, since they depend on
+/// how long the test took.
+fn mask_relative_times(text: &str) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    let marker: Vec<char> = "s ago".chars().collect();
+    for end in 0..chars.len() {
+        if !chars[end..].starts_with(&marker) {
+            continue;
+        }
+        for c in chars[..end].iter_mut().rev() {
+            if !(c.is_ascii_digit() || *c == '.') {
+                break;
+            }
+            if c.is_ascii_digit() {
+                *c = '#';
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// The buffer's characters, one line per row, without trailing blanks.
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        for x in area.left()..area.right() {
+            line.push_str(buffer.get(x, y).symbol());
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.txt"))
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "cannot read {}: {err}; run with UPDATE_GOLDEN=1 to create it",
+            path.display()
+        )
+    });
+    if expected != actual {
+        let first_difference = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(expected, actual)| expected != actual)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{name} differs from {} at line {}; run with UPDATE_GOLDEN=1 to accept\n\
+             --- expected\n{expected}\n+++ actual\n{actual}",
+            path.display(),
+            first_difference + 1,
+        );
+    }
+}
+
+macro_rules! golden_tests {
+    ($($name:ident => $view:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_golden(stringify!($name), &render_view(&$view));
+            }
+        )*
+    };
+}
+
+golden_tests! {
+    dashboard => ViewType::Dashboard,
+    dataflow_manager => ViewType::DataflowManager,
+    dataflow_explorer => ViewType::DataflowExplorer,
+    node_inspector => ViewType::NodeInspector {
+        dataflow_id: "6f1c2a7e-0000-4000-8000-000000000001".to_string(),
+        node_id: "camera".to_string(),
+    },
+    system_monitor => ViewType::SystemMonitor,
+    log_viewer => ViewType::LogViewer {
+        target: "camera-pipeline".to_string(),
+    },
+    notifications => ViewType::Notifications,
+    help => ViewType::Help,
+    settings => ViewType::SettingsManager,
+}
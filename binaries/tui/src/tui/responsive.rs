@@ -0,0 +1,115 @@
+//! Size breakpoints for responsive layouts.
+//!
+//! Views classify the area they are given rather than the whole terminal, so
+//! a view keeps adapting when it is drawn into a smaller part of the screen.
+//! At the regular size the header and footer take six rows, so a 100x30
+//! terminal leaves a regular 100x24 body.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Areas narrower than this are compact.
+pub const COMPACT_WIDTH: u16 = 100;
+/// Areas shorter than this are compact.
+pub const COMPACT_HEIGHT: u16 = 24;
+/// Areas at least this wide get extra columns.
+pub const WIDE_WIDTH: u16 = 160;
+
+/// Side panels are stacked under the main content in compact areas at least
+/// this tall, and hidden in shorter ones.
+const STACKED_MIN_HEIGHT: u16 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Breakpoint {
+    /// Single column, one-line chrome, side panels collapsed.
+    Compact,
+    Regular,
+    /// Extra table columns and side-by-side panels.
+    Wide,
+}
+
+impl Breakpoint {
+    pub fn of(area: Rect) -> Self {
+        if area.width < COMPACT_WIDTH || area.height < COMPACT_HEIGHT {
+            Self::Compact
+        } else if area.width >= WIDE_WIDTH {
+            Self::Wide
+        } else {
+            Self::Regular
+        }
+    }
+
+    pub fn is_compact(self) -> bool {
+        self == Self::Compact
+    }
+
+    pub fn is_wide(self) -> bool {
+        self == Self::Wide
+    }
+}
+
+/// Split `area` into main content and a side panel taking `side_percent` of
+/// the width. Narrow areas stack the panel below the content when there is
+/// room for both, and drop it otherwise.
+pub fn with_side_panel(area: Rect, side_percent: u16) -> (Rect, Option<Rect>) {
+    let direction = if area.width >= COMPACT_WIDTH {
+        Direction::Horizontal
+    } else if area.height >= STACKED_MIN_HEIGHT {
+        Direction::Vertical
+    } else {
+        return (area, None);
+    };
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(100 - side_percent),
+            Constraint::Percentage(side_percent),
+        ])
+        .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+/// Split `area` into two halves, side by side unless it is narrow.
+pub fn halves(area: Rect) -> [Rect; 2] {
+    let direction = if area.width < COMPACT_WIDTH {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    [chunks[0], chunks[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakpoints_follow_the_area_size() {
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 80, 22)), Breakpoint::Compact);
+        assert_eq!(
+            Breakpoint::of(Rect::new(0, 0, 200, 20)),
+            Breakpoint::Compact
+        );
+        assert_eq!(
+            Breakpoint::of(Rect::new(0, 0, 100, 24)),
+            Breakpoint::Regular
+        );
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 160, 40)), Breakpoint::Wide);
+    }
+
+    #[test]
+    fn side_panels_stack_then_collapse() {
+        let (main, side) = with_side_panel(Rect::new(0, 0, 120, 12), 40);
+        assert_eq!((main.width, side.unwrap().width), (72, 48));
+
+        let (main, side) = with_side_panel(Rect::new(0, 0, 80, 30), 40);
+        assert_eq!((main.height, side.unwrap().height), (18, 12));
+
+        let (main, side) = with_side_panel(Rect::new(0, 0, 80, 16), 40);
+        assert_eq!(main, Rect::new(0, 0, 80, 16));
+        assert!(side.is_none());
+    }
+}
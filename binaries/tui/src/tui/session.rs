@@ -0,0 +1,207 @@
+//! Session persistence.
+//!
+//! On exit the open tabs, their pane layouts, the navigation stack of every
+//! pane and the state worth keeping of each view (explorer tab, mode and
+//! selection, log filters, inspector tab) are written to a session file, and
+//! restored on the next start unless `--fresh` is given. Views are saved by
+//! id, so a restored view may refer to a dataflow that has since gone away;
+//! the app checks them once the first dataflow list arrives.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tui::{
+    app::ViewType,
+    files::write_atomically,
+    views::{ExplorerTab, InspectorTab, LogFilter, ViewMode},
+    workspace::{PaneId, PaneTree},
+};
+
+const SESSION_FILE: &str = "tui-session.toml";
+
+/// The tabs open at exit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tabs: Vec<SavedWorkspace>,
+    pub active_tab: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedWorkspace {
+    pub name: String,
+    pub layout: PaneTree,
+    pub focused: PaneId,
+    pub panes: Vec<SavedPane>,
+}
+
+/// The navigation stack of a pane, bottom first; the last view was active.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPane {
+    pub id: PaneId,
+    pub views: Vec<SavedView>,
+}
+
+/// A view on the stack and the parts of its state that survive a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedView {
+    pub view: ViewType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explorer: Option<ExplorerSession>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_filter: Option<LogFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspector_tab: Option<InspectorTab>,
+}
+
+impl SavedView {
+    pub fn new(view: ViewType) -> Self {
+        Self {
+            view,
+            explorer: None,
+            log_filter: None,
+            inspector_tab: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorerSession {
+    pub tab: ExplorerTab,
+    pub view_mode: ViewMode,
+    pub show_stopped: bool,
+    /// Id of the selected dataflow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_dataflow: Option<String>,
+}
+
+/// Reads and writes the session file.
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    path: PathBuf,
+}
+
+impl SessionStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `<local data dir>/dora/tui-session.toml`, if the platform has one.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("dora").join(SESSION_FILE))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The saved session, or `None` if there is none yet.
+    pub fn load(&self) -> Result<Option<Session>, String> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(self.error(err)),
+        };
+        toml::from_str(&contents)
+            .map(Some)
+            .map_err(|err| self.error(err))
+    }
+
+    pub fn save(&self, session: &Session) -> Result<(), String> {
+        let contents = toml::to_string_pretty(session).map_err(|err| self.error(err))?;
+        write_atomically(&self.path, &contents).map_err(|err| self.error(err))
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> String {
+        format!("{}: {err}", self.path.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::{test_support::scratch_dir, views::LogLevel, workspace::SplitDirection};
+
+    fn session() -> Session {
+        let mut filter = LogFilter::new();
+        filter.toggle_level(LogLevel::Debug);
+        filter.set_search("timeout".to_string());
+        let views = vec![
+            SavedView {
+                explorer: Some(ExplorerSession {
+                    tab: ExplorerTab::Nodes,
+                    view_mode: ViewMode::ListFlat,
+                    show_stopped: false,
+                    selected_dataflow: Some("df-1".to_string()),
+                }),
+                ..SavedView::new(ViewType::DataflowExplorer)
+            },
+            SavedView {
+                inspector_tab: Some(InspectorTab::Connections),
+                ..SavedView::new(ViewType::NodeInspector {
+                    dataflow_id: "df-1".to_string(),
+                    node_id: "camera".to_string(),
+                })
+            },
+            SavedView {
+                log_filter: Some(filter),
+                ..SavedView::new(ViewType::LogViewer {
+                    target: "camera-pipeline".to_string(),
+                })
+            },
+        ];
+
+        Session {
+            tabs: vec![SavedWorkspace {
+                name: "main".to_string(),
+                layout: PaneTree::Split {
+                    direction: SplitDirection::Horizontal,
+                    percent: 60,
+                    first: Box::new(PaneTree::Pane { id: 0 }),
+                    second: Box::new(PaneTree::Pane { id: 1 }),
+                },
+                focused: 1,
+                panes: vec![
+                    SavedPane { id: 0, views },
+                    SavedPane {
+                        id: 1,
+                        views: vec![SavedView::new(ViewType::SystemMonitor)],
+                    },
+                ],
+            }],
+            active_tab: 0,
+        }
+    }
+
+    #[test]
+    fn sessions_round_trip_through_the_file() {
+        let dir = scratch_dir("session-round-trip");
+        let store = SessionStore::new(dir.join(SESSION_FILE));
+        assert_eq!(store.load().unwrap(), None);
+
+        store.save(&session()).unwrap();
+        assert_eq!(store.load().unwrap(), Some(session()));
+
+        let contents = fs::read_to_string(store.path()).unwrap();
+        assert!(contents.contains("kind = \"node_inspector\""));
+        assert!(contents.contains("inspector_tab = \"connections\""));
+        assert!(contents.contains("direction = \"horizontal\""));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_sessions_are_errors() {
+        let dir = scratch_dir("session-corrupt");
+        let store = SessionStore::new(dir.join(SESSION_FILE));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(store.path(), "tabs = 3").unwrap();
+        assert!(store.load().unwrap_err().contains(SESSION_FILE));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
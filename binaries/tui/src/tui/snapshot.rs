@@ -0,0 +1,182 @@
+//! Headless snapshots of what the dashboard would show.
+//!
+//! `dora-tui snapshot` loads the same data as the first frame of the TUI and
+//! prints it instead of drawing it, for scripts and bug reports.
+
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use tui_interface::{DataflowSummary, SystemMetrics};
+
+use super::views::utils::{format_bytes, format_duration};
+
+/// Output format of `dora-tui snapshot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SnapshotFormat {
+    Json,
+    #[default]
+    Table,
+}
+
+/// Dataflows and system metrics at one point in time.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Snapshot {
+    pub dataflows: Vec<DataflowSummary>,
+    pub system_metrics: SystemMetrics,
+    /// Fetches that failed; the matching fields hold defaults.
+    pub errors: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn render(&self, format: SnapshotFormat) -> String {
+        match format {
+            SnapshotFormat::Json => {
+                serde_json::to_string_pretty(self).expect("snapshots serialize to JSON")
+            }
+            SnapshotFormat::Table => self.to_table(),
+        }
+    }
+
+    fn to_table(&self) -> String {
+        let mut out = String::new();
+
+        let rows: Vec<[String; 4]> = self
+            .dataflows
+            .iter()
+            .map(|dataflow| {
+                [
+                    dataflow.id.clone(),
+                    dataflow.name.clone(),
+                    dataflow.status.to_string(),
+                    dataflow.nodes.len().to_string(),
+                ]
+            })
+            .collect();
+        out.push_str("DATAFLOWS\n");
+        if rows.is_empty() {
+            out.push_str("(none)\n");
+        } else {
+            write_table(&mut out, ["ID", "NAME", "STATUS", "NODES"], &rows);
+        }
+
+        let metrics = &self.system_metrics;
+        let mut system = vec![
+            ("cpu", format!("{:.1}%", metrics.cpu_usage)),
+            (
+                "memory",
+                format!(
+                    "{:.1}% ({} / {})",
+                    metrics.memory_usage,
+                    format_bytes(metrics.memory.used_bytes),
+                    format_bytes(metrics.memory.total_bytes)
+                ),
+            ),
+            (
+                "disk",
+                format!(
+                    "{:.1}% ({} / {})",
+                    metrics.disk.usage_percent,
+                    format_bytes(metrics.disk.used_bytes),
+                    format_bytes(metrics.disk.total_bytes)
+                ),
+            ),
+            (
+                "network",
+                format!(
+                    "rx {}/s, tx {}/s",
+                    format_bytes(metrics.network.received_per_second as u64),
+                    format_bytes(metrics.network.transmitted_per_second as u64)
+                ),
+            ),
+        ];
+        if let Some(load) = &metrics.load_average {
+            system.push((
+                "load",
+                format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen),
+            ));
+        }
+        system.extend([
+            ("uptime", format_duration(metrics.uptime)),
+            ("processes", metrics.process_count.to_string()),
+            ("source", metrics.source.label().to_string()),
+        ]);
+        out.push_str("\nSYSTEM\n");
+        for (label, value) in system {
+            let _ = writeln!(out, "{label:<10} {value}");
+        }
+
+        if !self.errors.is_empty() {
+            out.push_str("\nERRORS\n");
+            for error in &self.errors {
+                let _ = writeln!(out, "{error}");
+            }
+        }
+        out
+    }
+}
+
+/// Left-aligned columns separated by two spaces.
+fn write_table<const N: usize>(out: &mut String, header: [&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut write_row = |cells: [&str; N]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(out, "{}", line.trim_end());
+    };
+    write_row(header);
+    for row in rows {
+        write_row(row.each_ref().map(String::as_str));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_interface::{DataflowStatus, NodeSummary};
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            dataflows: vec![DataflowSummary {
+                id: "df-1".to_string(),
+                name: "camera-pipeline".to_string(),
+                status: DataflowStatus::Running,
+                nodes: vec![NodeSummary::default(); 3],
+            }],
+            system_metrics: SystemMetrics {
+                cpu_usage: 12.5,
+                ..Default::default()
+            },
+            errors: vec!["failed to collect system metrics: offline".to_string()],
+        }
+    }
+
+    #[test]
+    fn tables_align_columns() {
+        let table = snapshot().render(SnapshotFormat::Table);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[1], "ID    NAME             STATUS   NODES");
+        assert_eq!(lines[2], "df-1  camera-pipeline  running  3");
+        assert!(table.contains("\ncpu        12.5%\n"));
+        assert!(table.ends_with("ERRORS\nfailed to collect system metrics: offline\n"));
+    }
+
+    #[test]
+    fn json_uses_snake_case_statuses() {
+        let json: serde_json::Value =
+            serde_json::from_str(&snapshot().render(SnapshotFormat::Json)).unwrap();
+        assert_eq!(json["dataflows"][0]["status"], "running");
+        assert_eq!(json["system_metrics"]["cpu_usage"], 12.5);
+        assert!(json["system_metrics"].get("last_update").is_none());
+        assert_eq!(json["errors"].as_array().unwrap().len(), 1);
+    }
+}
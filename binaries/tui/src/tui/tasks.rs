@@ -0,0 +1,343 @@
+//! Background data fetching.
+//!
+//! Backend calls are blocking and may be slow, so the event loop never waits
+//! on them. `DataTasks` runs each fetch and lifecycle command on the blocking
+//! thread pool and hands the result back over a channel that the loop selects
+//! on next to terminal input. At most one fetch of each kind is in flight at a
+//! time; every command the user issues runs. Preference loads and changes run
+//! one after another, in the order they were asked for.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::mpsc, task::JoinHandle};
+use tui_interface::{
+    CoordinatorClient, DataflowCommand, DataflowController, DataflowSummary, OperationHandle,
+    PreferencesStore, SystemMetrics, TelemetryService, UserPreferencesSnapshot,
+};
+
+use crate::tui::preferences::set_preference_by_name;
+
+/// The result of a background fetch and how long it took. Errors are ready
+/// to show to the user.
+#[derive(Debug)]
+pub enum DataUpdate {
+    Dataflows(Result<Vec<DataflowSummary>, String>, Duration),
+    SystemMetrics(Result<SystemMetrics, String>, Duration),
+    /// A lifecycle command the backend accepted or rejected.
+    Control(DataflowCommand, Result<OperationHandle, String>),
+    /// Preferences loaded from the store.
+    Preferences(Result<UserPreferencesSnapshot, String>),
+    /// The key and value of a preference change, and the preferences as
+    /// saved.
+    PreferenceSet(String, String, Result<UserPreferencesSnapshot, String>),
+}
+
+impl DataUpdate {
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Dataflows(Err(err), _)
+            | Self::SystemMetrics(Err(err), _)
+            | Self::Control(_, Err(err))
+            | Self::Preferences(Err(err))
+            | Self::PreferenceSet(_, _, Err(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Query the coordinator for the dataflow list on the blocking pool.
+pub async fn fetch_dataflows(
+    client: Arc<dyn CoordinatorClient>,
+) -> Result<Vec<DataflowSummary>, String> {
+    match tokio::task::spawn_blocking(move || client.list_dataflows()).await {
+        Ok(Ok(dataflows)) => Ok(dataflows),
+        Ok(Err(err)) => Err(format!("failed to fetch dataflows: {err}")),
+        Err(err) => Err(format!("coordinator query task failed: {err}")),
+    }
+}
+
+/// Collect system metrics on the blocking pool.
+pub async fn fetch_system_metrics(
+    service: Arc<dyn TelemetryService>,
+) -> Result<SystemMetrics, String> {
+    match tokio::task::spawn_blocking(move || service.latest_metrics()).await {
+        Ok(Ok(metrics)) => Ok(metrics),
+        Ok(Err(err)) => Err(format!("failed to collect system metrics: {err}")),
+        Err(err) => Err(format!("telemetry task failed: {err}")),
+    }
+}
+
+/// Submit a lifecycle command to the controller on the blocking pool.
+pub async fn control_dataflow(
+    controller: Arc<dyn DataflowController>,
+    command: DataflowCommand,
+) -> Result<OperationHandle, String> {
+    let verb = command.verb();
+    let target = command.target();
+    match tokio::task::spawn_blocking(move || controller.submit(&command)).await {
+        Ok(Ok(handle)) => Ok(handle),
+        Ok(Err(err)) => Err(format!("failed to {verb} {target}: {err}")),
+        Err(err) => Err(format!("{verb} task failed: {err}")),
+    }
+}
+
+/// Load the stored preferences on the blocking pool.
+pub async fn load_preferences(
+    store: Arc<dyn PreferencesStore>,
+) -> Result<UserPreferencesSnapshot, String> {
+    match tokio::task::spawn_blocking(move || store.load()).await {
+        Ok(Ok(snapshot)) => Ok(snapshot),
+        Ok(Err(err)) => Err(format!("failed to load preferences: {err}")),
+        Err(err) => Err(format!("preferences task failed: {err}")),
+    }
+}
+
+/// Change one preference by name and save it on the blocking pool. Returns
+/// the preferences as loaded back from the store.
+pub async fn set_preference(
+    store: Arc<dyn PreferencesStore>,
+    key: String,
+    value: String,
+) -> Result<UserPreferencesSnapshot, String> {
+    let task = tokio::task::spawn_blocking(move || {
+        let mut snapshot = store
+            .load()
+            .map_err(|err| format!("failed to load preferences: {err}"))?;
+        if !set_preference_by_name(&mut snapshot, &key, &value) {
+            return Err(format!("invalid preference {key}={value}"));
+        }
+        store
+            .save(&snapshot)
+            .map_err(|err| format!("failed to save preferences: {err}"))?;
+        store
+            .load()
+            .map_err(|err| format!("failed to load preferences: {err}"))
+    });
+    match task.await {
+        Ok(result) => result,
+        Err(err) => Err(format!("preferences task failed: {err}")),
+    }
+}
+
+/// Fetches and commands running in the background and the channel they report on.
+pub struct DataTasks {
+    tx: mpsc::UnboundedSender<DataUpdate>,
+    rx: mpsc::UnboundedReceiver<DataUpdate>,
+    dataflows_pending: bool,
+    metrics_pending: bool,
+    controls_pending: usize,
+    preferences_pending: usize,
+    /// The latest preference task; the next one waits for it.
+    preferences_task: Option<JoinHandle<()>>,
+}
+
+impl Default for DataTasks {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            tx,
+            rx,
+            dataflows_pending: false,
+            metrics_pending: false,
+            controls_pending: 0,
+            preferences_pending: 0,
+            preferences_task: None,
+        }
+    }
+}
+
+impl DataTasks {
+    /// Start fetching the dataflow list unless a fetch is already running.
+    /// Must be called from within a Tokio runtime.
+    pub fn fetch_dataflows(&mut self, client: Arc<dyn CoordinatorClient>) {
+        if std::mem::replace(&mut self.dataflows_pending, true) {
+            return;
+        }
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let result = fetch_dataflows(client).await;
+            let _ = tx.send(DataUpdate::Dataflows(result, started.elapsed()));
+        });
+    }
+
+    /// Start collecting system metrics unless a fetch is already running.
+    /// Must be called from within a Tokio runtime.
+    pub fn fetch_system_metrics(&mut self, service: Arc<dyn TelemetryService>) {
+        if std::mem::replace(&mut self.metrics_pending, true) {
+            return;
+        }
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let result = fetch_system_metrics(service).await;
+            let _ = tx.send(DataUpdate::SystemMetrics(result, started.elapsed()));
+        });
+    }
+
+    /// Submit a lifecycle command. Unlike fetches, commands are never
+    /// dropped. Must be called from within a Tokio runtime.
+    pub fn control_dataflow(
+        &mut self,
+        controller: Arc<dyn DataflowController>,
+        command: DataflowCommand,
+    ) {
+        self.controls_pending += 1;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let result = control_dataflow(controller, command.clone()).await;
+            let _ = tx.send(DataUpdate::Control(command, result));
+        });
+    }
+
+    /// Reload the preferences. Must be called from within a Tokio runtime.
+    pub fn load_preferences(&mut self, store: Arc<dyn PreferencesStore>) {
+        let tx = self.tx.clone();
+        self.run_preference_task(async move {
+            let result = load_preferences(store).await;
+            let _ = tx.send(DataUpdate::Preferences(result));
+        });
+    }
+
+    /// Change and save one preference. Must be called from within a Tokio
+    /// runtime.
+    pub fn set_preference(&mut self, store: Arc<dyn PreferencesStore>, key: &str, value: &str) {
+        let tx = self.tx.clone();
+        let (key, value) = (key.to_string(), value.to_string());
+        self.run_preference_task(async move {
+            let result = set_preference(store, key.clone(), value.clone()).await;
+            let _ = tx.send(DataUpdate::PreferenceSet(key, value, result));
+        });
+    }
+
+    /// Run `task` once the preference task before it has finished, so a
+    /// change never races the load or save of another.
+    fn run_preference_task(&mut self, task: impl Future<Output = ()> + Send + 'static) {
+        self.preferences_pending += 1;
+        let previous = self.preferences_task.take();
+        self.preferences_task = Some(tokio::spawn(async move {
+            if let Some(previous) = previous {
+                let _ = previous.await;
+            }
+            task.await;
+        }));
+    }
+
+    /// Wait for the next fetch to finish. Never resolves while nothing is
+    /// in flight, so it is safe to select on.
+    pub async fn recv(&mut self) -> DataUpdate {
+        // `self` keeps a sender alive, so the channel never closes.
+        let update = self.rx.recv().await.expect("sender is owned by self");
+        self.finished(&update);
+        update
+    }
+
+    fn finished(&mut self, update: &DataUpdate) {
+        match update {
+            DataUpdate::Dataflows(..) => self.dataflows_pending = false,
+            DataUpdate::SystemMetrics(..) => self.metrics_pending = false,
+            DataUpdate::Control(..) => self.controls_pending -= 1,
+            DataUpdate::Preferences(..) | DataUpdate::PreferenceSet(..) => {
+                self.preferences_pending -= 1
+            }
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        !self.dataflows_pending
+            && !self.metrics_pending
+            && self.controls_pending == 0
+            && self.preferences_pending == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_interface::{InterfaceError, MockCoordinatorClient, MockTelemetryService};
+
+    #[test]
+    fn one_fetch_of_each_kind_runs_at_a_time() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        let telemetry = Arc::new(MockTelemetryService::new());
+        telemetry.set_response(Err(InterfaceError::from("sensor offline")));
+
+        rt.block_on(async {
+            let mut tasks = DataTasks::default();
+            tasks.fetch_dataflows(coordinator.clone());
+            tasks.fetch_dataflows(coordinator);
+            tasks.fetch_system_metrics(telemetry);
+            assert!(!tasks.is_idle());
+
+            let mut dataflows = 0;
+            let mut metrics_error = None;
+            for _ in 0..2 {
+                match tasks.recv().await {
+                    DataUpdate::Dataflows(result, _) => {
+                        assert!(result.is_ok());
+                        dataflows += 1;
+                    }
+                    DataUpdate::SystemMetrics(result, _) => metrics_error = result.err(),
+                    _ => unreachable!("only fetches were started"),
+                }
+            }
+            assert_eq!(dataflows, 1);
+            assert_eq!(
+                metrics_error.as_deref(),
+                Some("failed to collect system metrics: sensor offline")
+            );
+            assert!(tasks.is_idle());
+            assert!(tasks.rx.try_recv().is_err());
+        });
+    }
+
+    #[test]
+    fn preference_changes_apply_in_order() {
+        use crate::tui::{preferences::FilePreferencesStore, test_support::scratch_dir};
+
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let dir = scratch_dir("tasks-preferences");
+        let store = Arc::new(FilePreferencesStore::new(dir.join("tui.toml")));
+
+        rt.block_on(async {
+            let mut tasks = DataTasks::default();
+            tasks.set_preference(store.clone(), "theme", "light");
+            tasks.set_preference(store.clone(), "refresh_interval", "3");
+            tasks.set_preference(store.clone(), "refresh_interval", "soon");
+            tasks.load_preferences(store.clone());
+
+            let mut updates = Vec::new();
+            while !tasks.is_idle() {
+                updates.push(tasks.recv().await);
+            }
+            let [
+                DataUpdate::PreferenceSet(_, _, Ok(first)),
+                DataUpdate::PreferenceSet(_, _, Ok(second)),
+                DataUpdate::PreferenceSet(_, _, Err(err)),
+                DataUpdate::Preferences(Ok(loaded)),
+            ] = &updates[..]
+            else {
+                panic!("unexpected updates: {updates:?}");
+            };
+            assert_eq!(first.theme, "light");
+            assert_eq!(second.auto_refresh_interval_secs, 3);
+            assert_eq!(err, "invalid preference refresh_interval=soon");
+            assert_eq!(
+                (loaded.theme.as_str(), loaded.auto_refresh_interval_secs),
+                ("light", 3)
+            );
+        });
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
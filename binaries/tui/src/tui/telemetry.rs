@@ -0,0 +1,174 @@
+//! Telemetry that combines gateway metrics with a local sysinfo collector.
+//!
+//! The gateway only reports CPU, memory and load. When it runs on the same
+//! machine as the TUI, the remaining fields (swap, disk, network, process
+//! count, uptime) are filled in from [`MetricsCollector`]. When the gateway
+//! cannot be reached, local metrics are shown on their own so the monitor
+//! does not drop to zero.
+
+use std::sync::{Arc, Mutex};
+
+use tui_interface::{InterfaceError, MetricsSource, SystemMetrics, TelemetryService};
+
+use crate::tui::metrics::MetricsCollector;
+
+pub struct CompositeTelemetryService {
+    gateway: Arc<dyn TelemetryService>,
+    merge_local: bool,
+    // Created on first use; `System::new_all` is expensive.
+    collector: Mutex<Option<MetricsCollector>>,
+}
+
+impl CompositeTelemetryService {
+    /// `merge_local` should be set when the gateway runs on this machine, so
+    /// local figures describe the same host.
+    pub fn new(gateway: Arc<dyn TelemetryService>, merge_local: bool) -> Self {
+        Self {
+            gateway,
+            merge_local,
+            collector: Mutex::new(None),
+        }
+    }
+
+    /// Build the service for a gateway URL, merging local data when the URL
+    /// points at this machine.
+    pub fn for_gateway(gateway: Arc<dyn TelemetryService>, base_url: &str) -> Self {
+        Self::new(gateway, is_local_url(base_url))
+    }
+
+    /// Complete a gateway sample with local data if the gateway is local.
+    /// Used for samples that arrive on the metrics stream as well as for
+    /// polled ones.
+    pub fn complete(&self, gateway: SystemMetrics) -> SystemMetrics {
+        if !self.merge_local {
+            return gateway;
+        }
+        match self.collect_local() {
+            Ok(local) => merge(gateway, local),
+            Err(_) => gateway,
+        }
+    }
+
+    fn collect_local(&self) -> Result<SystemMetrics, InterfaceError> {
+        let mut collector = self.collector.lock().unwrap();
+        collector
+            .get_or_insert_with(MetricsCollector::new)
+            .collect()
+            .map_err(|err| InterfaceError::from(err.to_string()))
+    }
+}
+
+impl TelemetryService for CompositeTelemetryService {
+    fn latest_metrics(&self) -> Result<SystemMetrics, InterfaceError> {
+        match self.gateway.latest_metrics() {
+            Ok(metrics) => Ok(self.complete(metrics)),
+            // Local data is better than nothing, even for a remote gateway;
+            // the source label tells the user which machine they are seeing.
+            Err(gateway_err) => self.collect_local().map_err(|_| gateway_err),
+        }
+    }
+}
+
+/// Keep the gateway's CPU, memory and load figures and take everything the
+/// gateway does not report from the local sample.
+fn merge(gateway: SystemMetrics, local: SystemMetrics) -> SystemMetrics {
+    SystemMetrics {
+        cpu_usage: gateway.cpu_usage,
+        memory_usage: gateway.memory_usage,
+        memory: tui_interface::MemoryMetrics {
+            swap_total_bytes: local.memory.swap_total_bytes,
+            swap_used_bytes: local.memory.swap_used_bytes,
+            swap_usage_percent: local.memory.swap_usage_percent,
+            ..gateway.memory
+        },
+        load_average: gateway.load_average.or(local.load_average),
+        last_update: gateway.last_update.or(local.last_update),
+        source: MetricsSource::Merged,
+        ..local
+    }
+}
+
+/// Whether `url` points at the loopback interface.
+fn is_local_url(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match authority.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback() || ip.is_unspecified())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui_interface::MockTelemetryService;
+
+    fn gateway_sample() -> SystemMetrics {
+        SystemMetrics {
+            cpu_usage: 42.0,
+            memory_usage: 50.0,
+            source: MetricsSource::Gateway,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_loopback_urls() {
+        assert!(is_local_url("http://127.0.0.1:7267"));
+        assert!(is_local_url("http://localhost:7267/v1"));
+        assert!(is_local_url("https://[::1]:7267"));
+        assert!(is_local_url("0.0.0.0:7267"));
+        assert!(!is_local_url("http://10.0.0.5:7267"));
+        assert!(!is_local_url("http://gateway.example.com"));
+    }
+
+    #[test]
+    fn remote_gateway_metrics_pass_through() {
+        let gateway = Arc::new(MockTelemetryService::new());
+        gateway.set_response(Ok(gateway_sample()));
+        let service = CompositeTelemetryService::new(gateway, false);
+
+        let metrics = service.latest_metrics().unwrap();
+        assert_eq!(metrics.source, MetricsSource::Gateway);
+        assert_eq!(metrics.cpu_usage, 42.0);
+    }
+
+    #[test]
+    fn merge_keeps_gateway_figures() {
+        let local = SystemMetrics {
+            cpu_usage: 1.0,
+            process_count: 7,
+            disk: tui_interface::DiskMetrics {
+                total_bytes: 100,
+                used_bytes: 40,
+                usage_percent: 40.0,
+            },
+            source: MetricsSource::Local,
+            ..Default::default()
+        };
+
+        let merged = merge(gateway_sample(), local);
+        assert_eq!(merged.source, MetricsSource::Merged);
+        assert_eq!(merged.cpu_usage, 42.0);
+        assert_eq!(merged.process_count, 7);
+        assert_eq!(merged.disk.used_bytes, 40);
+    }
+
+    #[test]
+    fn falls_back_to_local_collector() {
+        let gateway = Arc::new(MockTelemetryService::new());
+        gateway.set_response(Err(InterfaceError::from("connection refused")));
+        let service = CompositeTelemetryService::new(gateway, false);
+
+        let metrics = service.latest_metrics().unwrap();
+        assert_eq!(metrics.source, MetricsSource::Local);
+    }
+}
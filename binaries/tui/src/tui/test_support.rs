@@ -0,0 +1,16 @@
+//! Helpers shared by unit tests across modules.
+
+use std::{path::PathBuf, time::SystemTime};
+
+/// A path under the system temp dir unique to this test run. The directory
+/// is not created.
+pub fn scratch_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "dora-tui-{name}-{}-{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ))
+}
@@ -1,7 +1,12 @@
 #[cfg(test)]
 use crate::tui::{
-    app::{AppState, DataflowInfo, DoraApp, MessageLevel, NetworkMetrics, SystemMetrics, ViewType},
+    app::{
+        AppState, DataflowInfo, DoraApp, MessageLevel, MessageTarget, NetworkMetrics,
+        StatusMessage, SystemMetrics, ViewType,
+    },
     command_executor::StateUpdate as CommandStateUpdate,
+    keymap::Keymap,
+    test_support::scratch_dir,
     theme::ThemeConfig,
     views::{StateUpdate, View, ViewAction},
 };
@@ -14,14 +19,57 @@ use std::sync::{Arc, Mutex};
 #[cfg(test)]
 use std::time::{Duration, Instant};
 
+/// A single-threaded runtime for driving the app from synchronous tests.
+#[cfg(test)]
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// An app on mock services that have nothing to report.
+#[cfg(test)]
+fn mock_app(view: ViewType) -> DoraApp {
+    mock_app_with_coordinator(view, Arc::new(tui_interface::MockCoordinatorClient::new()))
+}
+
+/// An app on mock services, listing dataflows from `coordinator`.
+#[cfg(test)]
+fn mock_app_with_coordinator(
+    view: ViewType,
+    coordinator: Arc<dyn tui_interface::CoordinatorClient>,
+) -> DoraApp {
+    DoraApp::with_dependencies(
+        view,
+        Arc::new(tui_interface::MockPreferencesStore::new()),
+        coordinator,
+        Arc::new(tui_interface::MockTelemetryService::new()),
+    )
+}
+
+/// A dashboard on mock services that keeps its preferences in `store`.
+#[cfg(test)]
+fn app_with_preferences(store: Arc<dyn tui_interface::PreferencesStore>) -> DoraApp {
+    DoraApp::with_dependencies(
+        ViewType::Dashboard,
+        store,
+        Arc::new(tui_interface::MockCoordinatorClient::new()),
+        Arc::new(tui_interface::MockTelemetryService::new()),
+    )
+}
+
 #[cfg(test)]
 static CONFIG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 #[cfg(test)]
 mod app_tests {
     use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use tui_interface::{
-        MockCoordinatorClient, MockPreferencesStore, MockTelemetryService, UserPreferencesSnapshot,
+        DataflowCommand, DataflowStatus, InterfaceError, ManualClock, MockCoordinatorClient,
+        MockDataflowController, MockPreferencesStore, MockTelemetryService, NodeStatus, Scenario,
+        ScenarioBackend, ScenarioEvent, StartDataflowRequest, UserPreferencesSnapshot,
     };
 
     #[test]
@@ -85,6 +133,722 @@ mod app_tests {
         assert!(app.has_status_messages());
     }
 
+    #[test]
+    fn test_ticks_fetch_data_in_the_background() {
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            nodes: Vec::new(),
+        }]));
+        let mut app = mock_app_with_coordinator(ViewType::Dashboard, coordinator);
+
+        let rt = runtime();
+        rt.block_on(async {
+            // The tick only starts the fetches; it does not wait for them.
+            app.test_tick().await.unwrap();
+            assert!(app.last_dataflow_refresh().is_none());
+            assert!(app.state().dataflows.is_empty());
+
+            app.mark_drawn();
+            app.test_finish_fetches().await;
+            assert_eq!(app.state().dataflows.len(), 1);
+            assert!(app.last_dataflow_refresh().is_some());
+            assert!(app.needs_redraw());
+        });
+    }
+
+    #[test]
+    fn test_idle_ticks_do_not_redraw() {
+        // Help has no refresh interval, so it updates on every tick.
+        let mut app = mock_app(ViewType::Help);
+
+        let rt = runtime();
+        rt.block_on(async {
+            app.test_tick().await.unwrap();
+            app.test_finish_fetches().await;
+            app.mark_drawn();
+
+            // Nothing is due and nothing arrives, so nothing changes.
+            for _ in 0..4 {
+                app.test_tick().await.unwrap();
+            }
+            app.test_finish_fetches().await;
+            assert!(!app.needs_redraw());
+        });
+    }
+
+    #[test]
+    fn test_snapshot_collects_initial_data() {
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            nodes: Vec::new(),
+        }]));
+        let telemetry = Arc::new(MockTelemetryService::new());
+        telemetry.set_response(Err(InterfaceError::from("sensor offline")));
+        let mut app = DoraApp::with_dependencies(
+            ViewType::Dashboard,
+            Arc::new(MockPreferencesStore::new()),
+            coordinator,
+            telemetry,
+        );
+
+        let rt = runtime();
+        let snapshot = rt.block_on(app.snapshot()).unwrap();
+
+        assert_eq!(snapshot.dataflows.len(), 1);
+        assert_eq!(snapshot.dataflows[0].id, "df-1");
+        assert_eq!(
+            snapshot.errors,
+            ["failed to collect system metrics: sensor offline"]
+        );
+    }
+
+    #[test]
+    fn test_control_dataflow_dispatches_to_controller() {
+        let controller = Arc::new(MockDataflowController::new());
+        let mut app = mock_app(ViewType::Dashboard).with_dataflow_controller(controller.clone());
+
+        let rt = runtime();
+        rt.block_on(async {
+            app.control_dataflow(DataflowCommand::Stop("df-1".to_string()));
+            app.test_finish_fetches().await;
+        });
+
+        assert_eq!(
+            *controller.calls.lock().unwrap(),
+            vec![DataflowCommand::Stop("df-1".to_string())]
+        );
+        assert!(matches!(
+            app.last_status_message().map(|msg| &msg.level),
+            Some(MessageLevel::Success)
+        ));
+        assert!(app.last_dataflow_refresh().is_some());
+    }
+
+    #[test]
+    fn test_control_dataflow_reports_backend_errors() {
+        let controller = Arc::new(MockDataflowController::new());
+        controller.set_result(Err(InterfaceError::Unimplemented));
+        let mut app = mock_app(ViewType::Dashboard).with_dataflow_controller(controller);
+
+        let rt = runtime();
+        rt.block_on(async {
+            app.control_dataflow(DataflowCommand::Destroy("df-1".to_string()));
+            app.test_finish_fetches().await;
+        });
+
+        let message = app.last_status_message().expect("error message");
+        assert!(matches!(message.level, MessageLevel::Error));
+        assert!(message.message.contains("destroy df-1"));
+        assert_eq!(message.source, "control");
+        assert_eq!(
+            message.target,
+            Some(MessageTarget::Dataflow("df-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_notification_center_jumps_to_dataflow() {
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(["df-1", "df-2"]
+            .into_iter()
+            .map(|id| DataflowInfo {
+                id: id.to_string(),
+                name: id.to_string(),
+                status: DataflowStatus::Running,
+                nodes: Vec::new(),
+            })
+            .collect()));
+        let controller = Arc::new(MockDataflowController::new());
+        controller.set_result(Err(InterfaceError::Unimplemented));
+        let mut app = mock_app_with_coordinator(ViewType::Dashboard, coordinator)
+            .with_dataflow_controller(controller);
+
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        };
+        rt.block_on(async {
+            app.test_process_state_update(CommandStateUpdate::RefreshRequired)
+                .await
+                .unwrap();
+            app.test_finish_fetches().await;
+            app.control_dataflow(DataflowCommand::Stop("df-2".to_string()));
+            app.test_finish_fetches().await;
+        });
+        app.show_status_message("unrelated".to_string(), MessageLevel::Info);
+
+        // `6` opens the notification center with the newest message first.
+        press(&mut app, KeyCode::Char('6'));
+        assert_eq!(app.current_view(), &ViewType::Notifications);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_view(), &ViewType::DataflowExplorer);
+        assert_eq!(app.view_stack_len(), 1);
+        assert_eq!(app.selected_dataflow().as_deref(), Some("df-2"));
+    }
+
+    #[test]
+    fn test_refresh_follows_scenario_timeline() {
+        let scenario = Scenario::new()
+            .with_dataflow(DataflowInfo {
+                id: "df-1".to_string(),
+                name: "demo".to_string(),
+                status: DataflowStatus::Running,
+                ..Default::default()
+            })
+            .at(
+                Duration::from_secs(5),
+                ScenarioEvent::NodeStatus {
+                    dataflow: "df-1".to_string(),
+                    node: "camera".to_string(),
+                    status: NodeStatus::Failed,
+                },
+            )
+            .at(
+                Duration::from_secs(5),
+                ScenarioEvent::DataflowStatus {
+                    dataflow: "df-1".to_string(),
+                    status: DataflowStatus::Failed,
+                },
+            )
+            .at(
+                Duration::from_secs(8),
+                ScenarioEvent::CoordinatorError(InterfaceError::from("coordinator unreachable")),
+            )
+            .ramp_cpu(Duration::ZERO, Duration::from_secs(10), 20.0, 95.0);
+        let clock = ManualClock::new();
+        let backend = Arc::new(ScenarioBackend::new(scenario, clock.clone()));
+        let mut app = DoraApp::with_dependencies(
+            ViewType::Dashboard,
+            Arc::new(MockPreferencesStore::new()),
+            backend.clone(),
+            backend,
+        );
+
+        let rt = runtime();
+        let refresh = |app: &mut DoraApp| {
+            rt.block_on(async {
+                app.test_process_state_update(CommandStateUpdate::SystemMetricsUpdated)
+                    .await
+                    .unwrap();
+                app.test_process_state_update(CommandStateUpdate::RefreshRequired)
+                    .await
+                    .unwrap();
+                app.test_finish_fetches().await;
+            })
+        };
+
+        refresh(&mut app);
+        assert!(app.state().dataflows[0].status.is_running());
+        assert_eq!(app.state().system_metrics.cpu_usage, 20.0);
+
+        clock.advance(Duration::from_secs(5));
+        refresh(&mut app);
+        let dataflow = &app.state().dataflows[0];
+        assert!(dataflow.status.is_failed());
+        assert_eq!(dataflow.nodes[0].status, NodeStatus::Failed);
+        assert!(app.state().last_error.is_none());
+
+        clock.advance(Duration::from_secs(5));
+        refresh(&mut app);
+        assert_eq!(app.state().system_metrics.cpu_usage, 95.0);
+        assert!(
+            app.state()
+                .last_error
+                .as_deref()
+                .is_some_and(|err| err.contains("coordinator unreachable"))
+        );
+        // The last good list is kept while the coordinator is down.
+        assert_eq!(app.state().dataflows.len(), 1);
+    }
+
+    #[test]
+    fn test_slow_backend_does_not_hold_up_keys() {
+        let latency = Duration::from_millis(300);
+        let scenario = Scenario::new().with_dataflow(DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            ..Default::default()
+        });
+        let backend =
+            Arc::new(ScenarioBackend::new(scenario, ManualClock::new()).with_latency(latency));
+        let controller = Arc::new(MockDataflowController::new());
+        controller.set_latency(latency);
+        let mut app = DoraApp::with_dependencies(
+            ViewType::Dashboard,
+            Arc::new(MockPreferencesStore::new()),
+            backend.clone(),
+            backend,
+        )
+        .with_dataflow_controller(controller.clone());
+        app.push_view(ViewType::NodeInspector {
+            dataflow_id: "df-1".to_string(),
+            node_id: "camera".to_string(),
+        });
+
+        let rt = runtime();
+        rt.block_on(async {
+            let started = Instant::now();
+            let keys = [KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)]
+                .into_iter()
+                .chain(":stop demo\n".chars().map(|c| match c {
+                    '\n' => KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+                    c => KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+                }))
+                .chain([
+                    KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+                    KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE),
+                ]);
+            for key in keys {
+                app.test_handle_key_event(key).await.unwrap();
+            }
+
+            // The refresh and the stop are still running, but every key
+            // after them has been handled.
+            assert!(started.elapsed() < latency);
+            assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+            assert!(app.state().dataflows.is_empty());
+
+            app.test_finish_fetches().await;
+            assert_eq!(app.state().dataflows.len(), 1);
+            assert_eq!(
+                *controller.calls.lock().unwrap(),
+                vec![DataflowCommand::Stop("demo".to_string())]
+            );
+        });
+    }
+
+    #[test]
+    fn test_session_is_restored_without_stale_views() {
+        use crate::tui::session::SessionStore;
+
+        let dir = scratch_dir("session");
+        let store = SessionStore::new(dir.join("tui-session.toml"));
+        let new_app = |coordinator: Arc<MockCoordinatorClient>, restore: bool| {
+            mock_app_with_coordinator(ViewType::Dashboard, coordinator)
+                .with_session(store.clone(), restore)
+        };
+        let rt = runtime();
+
+        let mut app = new_app(Arc::new(MockCoordinatorClient::new()), true);
+        let inspector = ViewType::NodeInspector {
+            dataflow_id: "df-1".to_string(),
+            node_id: "camera".to_string(),
+        };
+        app.push_view(ViewType::DataflowExplorer);
+        app.push_view(ViewType::SystemMonitor);
+        app.push_view(inspector.clone());
+        app.test_save_session();
+
+        let fresh = new_app(Arc::new(MockCoordinatorClient::new()), false);
+        assert_eq!(fresh.current_view(), &ViewType::Dashboard);
+        assert_eq!(fresh.view_stack_len(), 0);
+
+        // df-1 is gone, so its node inspector is closed once the dataflows
+        // arrive.
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-2".to_string(),
+            name: "other".to_string(),
+            status: DataflowStatus::Running,
+            nodes: Vec::new(),
+        }]));
+        let mut app = new_app(coordinator, true);
+        assert_eq!(app.current_view(), &inspector);
+        assert_eq!(app.view_stack_len(), 3);
+
+        rt.block_on(async {
+            app.test_tick().await.unwrap();
+            app.test_finish_fetches().await;
+        });
+        assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+        assert_eq!(app.view_stack_len(), 2);
+        let message = app.last_status_message().unwrap();
+        assert_eq!(message.source, "session");
+        assert!(message.message.contains("node camera of df-1"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_deep_link_opens_the_resolved_view() {
+        use crate::tui::deep_link::DeepLink;
+
+        let dataflows = vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "camera".to_string(),
+            status: DataflowStatus::Running,
+            nodes: vec![tui_interface::NodeSummary {
+                id: "detector".to_string(),
+                ..Default::default()
+            }],
+        }];
+        let link = DeepLink {
+            view: "inspector".to_string(),
+            dataflow: Some("camera".to_string()),
+            node: Some("detector".to_string()),
+            ..Default::default()
+        };
+        let app = mock_app(ViewType::Dashboard).with_deep_link(link.resolve(&dataflows).unwrap());
+
+        assert_eq!(
+            app.current_view(),
+            &ViewType::NodeInspector {
+                dataflow_id: "df-1".to_string(),
+                node_id: "detector".to_string(),
+            }
+        );
+        assert_eq!(app.view_stack_len(), 0);
+    }
+
+    #[test]
+    fn test_mouse_capture_toggle_is_saved() {
+        use crate::tui::preferences::FilePreferencesStore;
+        use tui_interface::PreferencesStore;
+
+        let dir = scratch_dir("mouse");
+        let store = Arc::new(FilePreferencesStore::new(dir.join("tui.toml")));
+        let mut app = app_with_preferences(store.clone());
+        assert!(app.user_config().mouse_capture);
+
+        let rt = runtime();
+        rt.block_on(app.test_handle_key_event(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE)))
+            .unwrap();
+        rt.block_on(app.test_finish_fetches());
+        assert!(!app.user_config().mouse_capture);
+        assert!(!store.load().unwrap().mouse_capture);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_keymap_overrides_reach_views() {
+        let mut keymap = Keymap::default();
+        let problems = keymap
+            .apply_toml("[global]\nquit = \"ctrl+q\"\n\n[help]\nback = \"backspace\"\n")
+            .unwrap();
+        assert!(problems.is_empty(), "{problems:?}");
+
+        let mut app = mock_app(ViewType::Dashboard).with_keymap(Arc::new(keymap));
+
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode, modifiers: KeyModifiers| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, modifiers)))
+                .unwrap();
+        };
+
+        press(&mut app, KeyCode::F(1), KeyModifiers::NONE);
+        assert_eq!(app.current_view(), &ViewType::Help);
+
+        // `q` is no longer bound anywhere.
+        press(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(app.current_view(), &ViewType::Help);
+        assert!(!app.should_quit());
+
+        // The help view's back action follows its new key.
+        press(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+
+        press(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_keys_reach_the_active_view() {
+        let scenario = Scenario::new().with_dataflow(DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            ..Default::default()
+        });
+        let backend = Arc::new(ScenarioBackend::new(
+            scenario.at(
+                Duration::ZERO,
+                ScenarioEvent::NodeStatus {
+                    dataflow: "df-1".to_string(),
+                    node: "camera".to_string(),
+                    status: NodeStatus::Running,
+                },
+            ),
+            ManualClock::new(),
+        ));
+        let mut app = DoraApp::with_dependencies(
+            ViewType::Dashboard,
+            Arc::new(MockPreferencesStore::new()),
+            backend.clone(),
+            backend,
+        );
+
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        };
+        rt.block_on(app.test_process_state_update(CommandStateUpdate::RefreshRequired))
+            .unwrap();
+        rt.block_on(app.test_finish_fetches());
+
+        // Enter on the dashboard pushes the inspector for the running node.
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            app.current_view(),
+            &ViewType::NodeInspector {
+                dataflow_id: "df-1".to_string(),
+                node_id: "camera".to_string(),
+            }
+        );
+        assert_eq!(app.view_stack_len(), 1);
+
+        // The inspector binds `2` to a tab instead of a view switch, and Esc
+        // pops back.
+        press(&mut app, KeyCode::Char('2'));
+        assert!(matches!(app.current_view(), ViewType::NodeInspector { .. }));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+        assert!(!app.should_quit());
+
+        // Keys the view does not bind fall through to the global bindings.
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_view_actions_update_the_app() {
+        let mut app = mock_app(ViewType::Dashboard);
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        };
+
+        // `s` toggles the dashboard's system panel and reports it.
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(
+            app.last_status_message().map(|msg| msg.message.as_str()),
+            Some("System info disabled")
+        );
+
+        // `?` asks for help, which is pushed on top of the dashboard; Esc
+        // in the help view returns to it.
+        press(&mut app, KeyCode::Char('?'));
+        assert_eq!(app.current_view(), &ViewType::Help);
+        assert_eq!(app.view_stack_len(), 1);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+
+        // The dashboard kept its state while covered.
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(
+            app.last_status_message().map(|msg| msg.message.as_str()),
+            Some("System info enabled")
+        );
+    }
+
+    #[test]
+    fn test_dialogs_capture_keys_and_report_back() {
+        let node = |id: &str| tui_interface::NodeSummary {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: NodeStatus::Running,
+            ..Default::default()
+        };
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            nodes: vec![node("camera"), node("detector")],
+        }]));
+        let controller = Arc::new(MockDataflowController::new());
+        let mut app = mock_app_with_coordinator(ViewType::Dashboard, coordinator)
+            .with_dataflow_controller(controller.clone());
+
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        };
+        rt.block_on(app.test_process_state_update(CommandStateUpdate::RefreshRequired))
+            .unwrap();
+        rt.block_on(app.test_finish_fetches());
+
+        // With several nodes the dashboard asks which one to inspect.
+        press(&mut app, KeyCode::Enter);
+        assert!(app.has_modal());
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.has_modal());
+        assert_eq!(
+            app.current_view(),
+            &ViewType::NodeInspector {
+                dataflow_id: "df-1".to_string(),
+                node_id: "detector".to_string(),
+            }
+        );
+        press(&mut app, KeyCode::Esc);
+
+        // Stopping asks first; the dialog swallows navigation keys.
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.has_modal());
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.has_modal());
+        assert!(controller.calls.lock().unwrap().is_empty());
+
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char('y'));
+        rt.block_on(app.test_finish_fetches());
+        assert_eq!(
+            *controller.calls.lock().unwrap(),
+            vec![DataflowCommand::Stop("df-1".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_explorer_lifecycle_goes_through_dialogs() {
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Running,
+            nodes: Vec::new(),
+        }]));
+        let controller = Arc::new(MockDataflowController::new());
+        let mut app = mock_app_with_coordinator(ViewType::DataflowExplorer, coordinator.clone())
+            .with_dataflow_controller(controller.clone());
+
+        let rt = runtime();
+        let press = |app: &mut DoraApp, code: KeyCode| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        };
+        rt.block_on(app.test_process_state_update(CommandStateUpdate::RefreshRequired))
+            .unwrap();
+        rt.block_on(app.test_finish_fetches());
+
+        // Stopping a running dataflow asks first, like the dashboard.
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.has_modal());
+        press(&mut app, KeyCode::Esc);
+        assert!(controller.calls.lock().unwrap().is_empty());
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Char('y'));
+        rt.block_on(app.test_finish_fetches());
+        assert_eq!(
+            *controller.calls.lock().unwrap(),
+            vec![DataflowCommand::Stop("df-1".to_string())]
+        );
+
+        // A finished dataflow is restarted from a descriptor the user names,
+        // not from its id.
+        coordinator.set_response(Ok(vec![DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            status: DataflowStatus::Finished,
+            nodes: Vec::new(),
+        }]));
+        rt.block_on(app.test_process_state_update(CommandStateUpdate::RefreshRequired))
+            .unwrap();
+        rt.block_on(app.test_finish_fetches());
+        controller.calls.lock().unwrap().clear();
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.has_modal());
+        for c in "flows/demo.yml".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        rt.block_on(app.test_finish_fetches());
+        assert!(!app.has_modal());
+        assert_eq!(
+            *controller.calls.lock().unwrap(),
+            vec![DataflowCommand::Start(StartDataflowRequest {
+                descriptor_path: "flows/demo.yml".into(),
+                name: Some("demo".to_string()),
+                uv: false,
+            })]
+        );
+    }
+
+    #[test]
+    fn test_command_line_runs_commands() {
+        let controller = Arc::new(MockDataflowController::new());
+        let mut app = mock_app(ViewType::Dashboard).with_dataflow_controller(controller.clone());
+
+        let rt = runtime();
+        let run = |app: &mut DoraApp, line: &str| {
+            let keys = std::iter::once(KeyCode::Char(':'))
+                .chain(line.chars().map(KeyCode::Char))
+                .chain(std::iter::once(KeyCode::Enter));
+            for code in keys {
+                rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                    .unwrap();
+            }
+        };
+
+        // Typed keys go to the command line, not to view navigation.
+        run(&mut app, "view monitor");
+        assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+
+        run(&mut app, "stop 'my flow'");
+        rt.block_on(app.test_finish_fetches());
+        assert_eq!(
+            *controller.calls.lock().unwrap(),
+            vec![DataflowCommand::Stop("my flow".to_string())]
+        );
+
+        run(&mut app, "logs demo camera");
+        assert_eq!(
+            app.current_view(),
+            &ViewType::LogViewer {
+                target: "demo/camera".to_string(),
+            }
+        );
+
+        run(&mut app, "frobnicate");
+        assert!(matches!(
+            app.last_status_message().map(|msg| &msg.level),
+            Some(MessageLevel::Error)
+        ));
+
+        run(&mut app, "quit");
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_command_line_preferences_survive_initialization() {
+        use crate::tui::preferences::{LayeredPreferencesStore, PreferencesOverrides};
+
+        let store = LayeredPreferencesStore::new(None)
+            .with_file(None)
+            .with_env_overrides(PreferencesOverrides::default())
+            .with_cli_overrides(PreferencesOverrides {
+                theme: Some("light".to_string()),
+                auto_refresh_interval_secs: Some(2),
+                ..Default::default()
+            });
+        let mut app = app_with_preferences(Arc::new(store));
+
+        let rt = runtime();
+        rt.block_on(app.snapshot()).unwrap();
+        assert_eq!(
+            app.user_config().auto_refresh_interval,
+            Duration::from_secs(2)
+        );
+        assert_eq!(app.user_config().theme_name, "light");
+    }
+
     #[test]
     fn test_user_preferences_reload() {
         let _lock = CONFIG_LOCK.lock().unwrap();
@@ -97,6 +861,10 @@ mod app_tests {
             auto_refresh_interval_secs: 7,
             show_system_info: false,
             default_view: None,
+            ui_mode: None,
+            mouse_capture: true,
+            accessible: false,
+            notify_on: Vec::new(),
         }));
 
         let mut app = DoraApp::with_dependencies(
@@ -117,16 +885,18 @@ mod app_tests {
             auto_refresh_interval_secs: 3,
             show_system_info: true,
             default_view: None,
+            ui_mode: None,
+            mouse_capture: true,
+            accessible: false,
+            notify_on: Vec::new(),
         }));
 
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap();
+        let rt = runtime();
         rt.block_on(async {
             app.test_process_state_update(CommandStateUpdate::ConfigurationChanged)
                 .await
                 .unwrap();
+            app.test_finish_fetches().await;
         });
 
         assert_eq!(app.user_config().theme_name, "dark");
@@ -145,14 +915,11 @@ mod app_tests {
         let info = DataflowInfo {
             id: "df-test".to_string(),
             name: "demo".to_string(),
-            status: "running".to_string(),
+            status: tui_interface::DataflowStatus::Running,
             ..Default::default()
         };
 
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap();
+        let rt = runtime();
 
         rt.block_on(async {
             app.test_process_state_update(CommandStateUpdate::DataflowAdded(info))
@@ -202,8 +969,67 @@ mod theme_tests {
 
     #[test]
     fn test_theme_loading() {
-        let theme = ThemeConfig::load_user_theme();
-        assert!(!theme.name.is_empty());
+        let theme = ThemeConfig::load_user_theme("solarized-dark").unwrap();
+        assert_eq!(theme.name, "solarized-dark");
+        assert!(ThemeConfig::load_user_theme("no-such-theme").is_err());
+    }
+
+    #[test]
+    fn test_theme_files() {
+        use crate::tui::palette::ColorDepth;
+        use ratatui::{style::Color, widgets::BorderType};
+
+        let dir = scratch_dir("themes");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("ocean.toml"),
+            r##"
+                extends = "light"
+
+                [colors]
+                primary = "#88c0d0"
+                border = "240"
+
+                [styles]
+                border = "double"
+                selection = { bg = "#3b4252", bold = true }
+            "##,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.toml"), "[colors]\nprimry = \"red\"").unwrap();
+
+        let names = ThemeConfig::available(Some(&dir));
+        for name in [
+            "broken",
+            "dark",
+            "high-contrast",
+            "ocean",
+            "solarized-light",
+        ] {
+            assert!(names.iter().any(|known| known == name), "{name} missing");
+        }
+
+        let ocean = ThemeConfig::load("ocean", Some(&dir)).unwrap();
+        assert_eq!(ocean.name, "ocean");
+        assert_eq!(ocean.colors.primary, Color::Rgb(0x88, 0xc0, 0xd0));
+        assert_eq!(ocean.colors.border, Color::Indexed(240));
+        assert_eq!(ocean.colors.text, ThemeConfig::default_light().colors.text);
+        assert_eq!(ocean.styles.border_style, BorderType::Double);
+
+        let err = ThemeConfig::load("broken", Some(&dir)).unwrap_err();
+        assert!(
+            err.contains("broken.toml") && err.contains("primry"),
+            "{err}"
+        );
+        let err = ThemeConfig::load("nope", Some(&dir)).unwrap_err();
+        assert!(err.contains("available: broken, dark"), "{err}");
+
+        let ansi = ocean.for_depth(ColorDepth::Ansi16);
+        assert_eq!(ansi.colors.primary, Color::Gray);
+        assert_eq!(ansi.colors.border, Color::DarkGray);
+        assert_eq!(ansi.styles.selection_style.bg, Some(Color::DarkGray));
+
+        std::fs::remove_dir_all(dir).unwrap();
     }
 
     #[test]
@@ -220,6 +1046,26 @@ mod theme_tests {
         assert_ne!(error_style, warning_style);
     }
 
+    #[test]
+    fn test_typed_status_styling_matches_labels() {
+        use tui_interface::{DataflowStatus, NodeStatus};
+
+        let theme = ThemeConfig::default_dark();
+
+        assert_eq!(
+            theme.status_style(DataflowStatus::Running),
+            theme.status_style("running")
+        );
+        assert_eq!(
+            theme.status_style(NodeStatus::Failed),
+            theme.status_style("failed")
+        );
+        assert_eq!(
+            theme.status_style(DataflowStatus::Finished),
+            theme.status_style(NodeStatus::Completed)
+        );
+    }
+
     #[test]
     fn test_percentage_styling() {
         let theme = ThemeConfig::default_dark();
@@ -252,7 +1098,7 @@ mod view_tests {
     #[test]
     fn test_help_view() {
         let theme = ThemeConfig::default_dark();
-        let view = HelpView::new(&theme);
+        let view = HelpView::new(&theme, Arc::new(Keymap::default()));
 
         assert_eq!(view.title(), "Help");
 
@@ -274,6 +1120,508 @@ mod view_tests {
         assert!(matches!(action, ViewAction::ShowStatus(_)));
     }
 
+    #[test]
+    fn test_notifications_filter_by_level_and_text() {
+        use crate::tui::views::NotificationsView;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut state = AppState::default();
+        state.push_status_message(StatusMessage::new(
+            "coordinator",
+            "❌ failed to fetch dataflows".to_string(),
+            MessageLevel::Error,
+        ));
+        state.push_status_message(
+            StatusMessage::new(
+                "Node Inspector",
+                "camera restarted".to_string(),
+                MessageLevel::Warning,
+            )
+            .with_target(Some(MessageTarget::Node {
+                dataflow_id: "df-1".to_string(),
+                node_id: "camera".to_string(),
+            })),
+        );
+        state.push_status_message(StatusMessage::new(
+            "preferences",
+            "theme set to dark".to_string(),
+            MessageLevel::Success,
+        ));
+
+        let theme = ThemeConfig::default_dark();
+        let mut view = NotificationsView::new(&theme);
+        let rt = runtime();
+        let press = |view: &mut NotificationsView, state: &mut AppState, code: KeyCode| {
+            rt.block_on(view.handle_key(KeyEvent::new(code, KeyModifiers::NONE), state))
+                .unwrap()
+        };
+        let sources = |view: &NotificationsView, state: &AppState| {
+            view.visible(state)
+                .iter()
+                .map(|msg| msg.source.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sources(&view, &state),
+            ["preferences", "Node Inspector", "coordinator"]
+        );
+        press(&mut view, &mut state, KeyCode::Char('f'));
+        assert_eq!(sources(&view, &state), ["Node Inspector", "coordinator"]);
+
+        // Text filters match the target too; the filter captures keys
+        // while it is typed.
+        press(&mut view, &mut state, KeyCode::Char('/'));
+        for c in "df-1".chars() {
+            press(&mut view, &mut state, KeyCode::Char(c));
+        }
+        assert!(view.captures_text());
+        press(&mut view, &mut state, KeyCode::Enter);
+        assert!(!view.captures_text());
+        assert_eq!(sources(&view, &state), ["Node Inspector"]);
+
+        let action = press(&mut view, &mut state, KeyCode::Enter);
+        assert!(matches!(
+            action,
+            ViewAction::PushView(ViewType::NodeInspector { node_id, .. }) if node_id == "camera"
+        ));
+
+        // Esc clears the filter before it leaves the view.
+        press(&mut view, &mut state, KeyCode::Esc);
+        assert_eq!(sources(&view, &state).len(), 2);
+        assert!(matches!(
+            press(&mut view, &mut state, KeyCode::Esc),
+            ViewAction::PopView
+        ));
+    }
+
+    #[test]
+    fn test_mouse_hits_rendered_tabs_and_rows() {
+        use crate::tui::{
+            app::NodeInfo,
+            views::{DataflowExplorerView, ExplorerTab},
+        };
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut state = AppState::default();
+        state.dataflows.push(DataflowInfo {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            nodes: ["camera", "detector"]
+                .into_iter()
+                .map(|id| NodeInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        });
+
+        let theme = ThemeConfig::default_dark();
+        let mut view = DataflowExplorerView::new(&theme);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        let mut click_on = |view: &mut DataflowExplorerView, state: &mut AppState, text: &str| {
+            let frame = terminal.draw(|f| view.render(f, f.size(), state)).unwrap();
+            let (column, row) = find_text(frame.buffer, text);
+            let event = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            };
+            view.handle_mouse(event, state)
+        };
+
+        click_on(&mut view, &mut state, "Nodes");
+        assert_eq!(view.state.active_tab, ExplorerTab::Nodes);
+
+        click_on(&mut view, &mut state, "detector");
+        assert_eq!(view.selected_index, 1);
+    }
+
+    #[test]
+    fn test_explorer_hides_only_stopped_dataflows() {
+        use crate::tui::views::DataflowExplorerView;
+        use ratatui::{Terminal, backend::TestBackend};
+        use tui_interface::DataflowStatus;
+
+        let mut state = AppState::default();
+        for status in [
+            DataflowStatus::Running,
+            DataflowStatus::Stopped,
+            DataflowStatus::Finished,
+            DataflowStatus::Unknown,
+        ] {
+            state.dataflows.push(DataflowInfo {
+                id: status.to_string(),
+                name: status.to_string(),
+                status,
+                ..Default::default()
+            });
+        }
+
+        let theme = ThemeConfig::default_dark();
+        let mut view = DataflowExplorerView::new(&theme);
+        assert_eq!(view.get_item_count(&state), 4);
+
+        view.state.show_stopped = false;
+        assert_eq!(view.get_item_count(&state), 3);
+        view.selected_index = 1;
+        assert_eq!(
+            view.get_selected_dataflow(&state).unwrap().status,
+            DataflowStatus::Finished
+        );
+
+        // Only running dataflows are grouped under RUNNING.
+        state.dataflows.push(DataflowInfo {
+            id: "pending".to_string(),
+            name: "pending".to_string(),
+            status: DataflowStatus::Pending,
+            ..Default::default()
+        });
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let frame = terminal.draw(|f| view.render(f, f.size(), &state)).unwrap();
+        find_text(frame.buffer, "RUNNING (1)");
+    }
+
+    #[test]
+    fn test_chrome_shrinks_on_small_terminals() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = DoraApp::new(ViewType::Dashboard);
+        let chrome_rows = |app: &mut DoraApp, width, height| {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            let frame = terminal.draw(|f| app.test_draw(f)).unwrap();
+            let (_, row) = find_text(frame.buffer, "Dora TUI - Dashboard");
+            let (_, footer) = find_text(frame.buffer, "to quit");
+            (row, height - footer)
+        };
+
+        assert_eq!(chrome_rows(&mut app, 120, 40), (1, 2));
+        assert_eq!(chrome_rows(&mut app, 120, 28), (0, 1));
+        assert_eq!(chrome_rows(&mut app, 80, 40), (0, 1));
+    }
+
+    #[test]
+    fn test_gateway_outage_is_reported_once() {
+        use crate::tui::connection::{Link, LinkStatus};
+        use ratatui::{Terminal, backend::TestBackend};
+        use tui_interface::{InterfaceError, MockCoordinatorClient};
+
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        let mut app = mock_app_with_coordinator(ViewType::Dashboard, coordinator.clone());
+        let rt = runtime();
+        let refresh = |app: &mut DoraApp| {
+            rt.block_on(async {
+                app.test_process_state_update(CommandStateUpdate::RefreshRequired)
+                    .await
+                    .unwrap();
+                app.test_finish_fetches().await;
+            })
+        };
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        let mut screen = |app: &mut DoraApp| {
+            let frame = terminal.draw(|f| app.test_draw(f)).unwrap();
+            let area = frame.buffer.area;
+            (area.top()..area.bottom())
+                .map(|y| {
+                    (area.left()..area.right())
+                        .map(|x| frame.buffer.get(x, y).symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        refresh(&mut app);
+        let gateway = app.state().connection.health(Link::Gateway).unwrap();
+        assert_eq!(gateway.status(), LinkStatus::Connected);
+        assert!(gateway.latency.is_some());
+        assert!(screen(&mut app).contains("● gateway"));
+        let messages = app.state().status_messages.len();
+
+        coordinator.set_response(Err(InterfaceError::from("connection refused")));
+        for _ in 0..3 {
+            refresh(&mut app);
+        }
+        assert_eq!(app.state().status_messages.len(), messages + 1);
+        let text = screen(&mut app);
+        assert!(text.contains(
+            " Reconnecting to the gateway: failed to fetch dataflows: connection refused \
+             (3 failed attempts, last connected"
+        ));
+        assert!(text.contains("✗ gateway"));
+
+        coordinator.set_response(Ok(Vec::new()));
+        refresh(&mut app);
+        let message = app.last_status_message().unwrap();
+        assert_eq!(message.level, MessageLevel::Success);
+        assert!(message.message.contains("gateway reachable again"));
+        assert!(!screen(&mut app).contains("Reconnecting"));
+    }
+
+    #[test]
+    fn test_failed_node_raises_one_alert_until_it_recovers() {
+        use ratatui::{Terminal, backend::TestBackend};
+        use tui_interface::{DataflowStatus, MockCoordinatorClient, NodeStatus, NodeSummary};
+
+        let dataflows = |status| {
+            Ok(vec![DataflowInfo {
+                id: "df-1".to_string(),
+                name: "camera".to_string(),
+                status: DataflowStatus::Running,
+                nodes: vec![NodeSummary {
+                    id: "detector".to_string(),
+                    status,
+                    ..Default::default()
+                }],
+            }])
+        };
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(dataflows(NodeStatus::Failed));
+        let mut app = mock_app_with_coordinator(ViewType::Dashboard, coordinator.clone());
+        let rt = runtime();
+        let refresh = |app: &mut DoraApp| {
+            rt.block_on(async {
+                app.test_process_state_update(CommandStateUpdate::RefreshRequired)
+                    .await
+                    .unwrap();
+                app.test_finish_fetches().await;
+            })
+        };
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+        refresh(&mut app);
+        let message = app.last_status_message().unwrap();
+        assert_eq!(message.source, "alerts");
+        assert_eq!(message.level, MessageLevel::Error);
+        assert!(
+            message
+                .message
+                .ends_with("node failed: node detector of camera is failed")
+        );
+        let frame = terminal.draw(|f| app.test_draw(f)).unwrap();
+        find_text(frame.buffer, "Alerts (1)");
+
+        let messages = app.state().status_messages.len();
+        refresh(&mut app);
+        assert_eq!(app.state().alerts.active().len(), 1);
+        assert_eq!(app.state().status_messages.len(), messages);
+
+        coordinator.set_response(dataflows(NodeStatus::Running));
+        refresh(&mut app);
+        let message = app.last_status_message().unwrap();
+        assert_eq!(message.level, MessageLevel::Success);
+        assert!(message.message.starts_with("✅ resolved node failed"));
+        assert!(app.state().alerts.active().is_empty());
+    }
+
+    #[test]
+    fn test_opted_in_events_queue_terminal_notifications() {
+        use tui_interface::{
+            DataflowStatus, MockCoordinatorClient, MockPreferencesStore, MockTelemetryService,
+            NodeStatus, NodeSummary, UserPreferencesSnapshot,
+        };
+
+        let dataflows = |status, node_status| {
+            Ok(vec![DataflowInfo {
+                id: "df-1".to_string(),
+                name: "camera".to_string(),
+                status,
+                nodes: vec![NodeSummary {
+                    id: "detector".to_string(),
+                    status: node_status,
+                    ..Default::default()
+                }],
+            }])
+        };
+        let prefs_store = Arc::new(MockPreferencesStore::new());
+        prefs_store.set_load_result(Ok(UserPreferencesSnapshot {
+            notify_on: vec!["dataflow_failed".to_string()],
+            ..Default::default()
+        }));
+        let coordinator = Arc::new(MockCoordinatorClient::new());
+        coordinator.set_response(dataflows(DataflowStatus::Running, NodeStatus::Failed));
+        let mut app = DoraApp::with_dependencies(
+            ViewType::Dashboard,
+            prefs_store,
+            coordinator.clone(),
+            Arc::new(MockTelemetryService::new()),
+        );
+        let rt = runtime();
+        let refresh = |app: &mut DoraApp| {
+            rt.block_on(async {
+                app.test_process_state_update(CommandStateUpdate::RefreshRequired)
+                    .await
+                    .unwrap();
+                app.test_finish_fetches().await;
+            })
+        };
+
+        // The first list is not compared, and the node alert is not opted in.
+        refresh(&mut app);
+        assert!(app.take_notifications().is_empty());
+
+        // The node coming back is not opted in either.
+        coordinator.set_response(dataflows(DataflowStatus::Running, NodeStatus::Running));
+        refresh(&mut app);
+        assert!(app.take_notifications().is_empty());
+
+        coordinator.set_response(dataflows(DataflowStatus::Failed, NodeStatus::Running));
+        refresh(&mut app);
+        let notifications = app.take_notifications();
+        assert_eq!(notifications.len(), 1);
+        assert!(notifications[0].starts_with('\x07'));
+
+        refresh(&mut app);
+        assert!(app.take_notifications().is_empty());
+    }
+
+    #[test]
+    fn test_accessible_mode_from_the_command_line() {
+        use crate::tui::preferences::FilePreferencesStore;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use ratatui::{Terminal, backend::TestBackend};
+        use tui_interface::PreferencesStore;
+
+        let dir = scratch_dir("accessible");
+        let store = Arc::new(FilePreferencesStore::new(dir.join("tui.toml")));
+        let mut app = app_with_preferences(store.clone());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let header = |terminal: &mut Terminal<TestBackend>, app: &mut DoraApp| {
+            let frame = terminal.draw(|f| app.test_draw(f)).unwrap();
+            let (x, y) = find_text(frame.buffer, "Dora TUI - Dashboard");
+            frame.buffer.get(x - 3, y).symbol().to_string()
+        };
+        assert_eq!(header(&mut terminal, &mut app), "🚀");
+
+        let rt = runtime();
+        let keys = std::iter::once(KeyCode::Char(':'))
+            .chain("set accessible true".chars().map(KeyCode::Char))
+            .chain(std::iter::once(KeyCode::Enter));
+        for code in keys {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        }
+        rt.block_on(app.test_finish_fetches());
+        assert!(app.user_config().accessible);
+        assert!(store.load().unwrap().accessible);
+
+        // The header keeps its layout, the rocket is gone and the default
+        // theme gave way to the high-contrast one.
+        assert_eq!(header(&mut terminal, &mut app), " ");
+        let border = terminal.backend().buffer().get(0, 0);
+        assert_eq!(border.symbol(), "┏");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_default_view_only_applies_at_startup() {
+        use crate::tui::preferences::FilePreferencesStore;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let dir = scratch_dir("default-view");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("tui.toml"), "default_view = \"monitor\"\n").unwrap();
+        let mut app =
+            app_with_preferences(Arc::new(FilePreferencesStore::new(dir.join("tui.toml"))));
+        assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+
+        let rt = runtime();
+        let run = |app: &mut DoraApp, command: &str| {
+            let keys = std::iter::once(KeyCode::Char(':'))
+                .chain(command.chars().map(KeyCode::Char))
+                .chain(std::iter::once(KeyCode::Enter));
+            for code in keys {
+                rt.block_on(app.test_handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)))
+                    .unwrap();
+            }
+            rt.block_on(app.test_finish_fetches());
+        };
+        run(&mut app, "view explorer");
+        run(&mut app, "set mouse_capture false");
+        assert!(!app.user_config().mouse_capture);
+        assert_eq!(app.current_view(), &ViewType::DataflowExplorer);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_panes_and_tabs_from_the_keyboard() {
+        use crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        };
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let rt = runtime();
+        let mut app = mock_app(ViewType::Dashboard);
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut draw =
+            |app: &mut DoraApp| terminal.draw(|f| app.test_draw(f)).unwrap().buffer.clone();
+        let press = |app: &mut DoraApp, code, modifiers| {
+            rt.block_on(app.test_handle_key_event(KeyEvent::new(code, modifiers)))
+                .unwrap()
+        };
+        let alt = KeyModifiers::ALT;
+
+        // Dashboard on the left, system monitor on the right.
+        press(&mut app, KeyCode::Char('v'), alt);
+        press(&mut app, KeyCode::Char('3'), KeyModifiers::NONE);
+        assert_eq!(app.workspaces().active().pane_count(), 2);
+        let screen = draw(&mut app);
+        let titles: String = (0..120).map(|x| screen.get(x, 3).symbol()).collect();
+        assert!(titles.starts_with(" Dashboard "));
+        assert_eq!(titles.find(" System Monitor "), Some(60));
+
+        press(&mut app, KeyCode::Left, alt);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+        rt.block_on(app.test_handle_mouse_event(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 100,
+            row: 20,
+            modifiers: KeyModifiers::NONE,
+        }))
+        .unwrap();
+        assert_eq!(app.current_view(), &ViewType::SystemMonitor);
+
+        press(&mut app, KeyCode::Char('x'), alt);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+        press(&mut app, KeyCode::Char('x'), alt);
+        assert_eq!(app.workspaces().active().pane_count(), 1);
+        assert_eq!(app.last_status_message().unwrap().source, "workspace");
+
+        // A second tab starts from the focused view and has its own panes.
+        press(&mut app, KeyCode::Char('t'), alt);
+        press(&mut app, KeyCode::Char('6'), KeyModifiers::NONE);
+        let screen = draw(&mut app);
+        assert_eq!(find_text(&screen, " 1:main ").1, 1);
+        assert_eq!(find_text(&screen, " 2:tab 2 ").1, 1);
+
+        press(&mut app, KeyCode::Char('p'), alt);
+        assert_eq!(app.current_view(), &ViewType::Dashboard);
+        press(&mut app, KeyCode::Char('w'), alt);
+        assert_eq!(app.workspaces().tabs().len(), 1);
+        assert_eq!(app.current_view(), &ViewType::Notifications);
+    }
+
+    /// Position of the first cell where `text` is drawn.
+    fn find_text(buffer: &ratatui::buffer::Buffer, text: &str) -> (u16, u16) {
+        let area = buffer.area;
+        for y in area.top()..area.bottom() {
+            let line: Vec<&str> = (area.left()..area.right())
+                .map(|x| buffer.get(x, y).symbol())
+                .collect();
+            let line = line.concat();
+            if let Some(index) = line.find(text) {
+                return (line[..index].chars().count() as u16, y);
+            }
+        }
+        panic!("`{text}` is not on screen");
+    }
+
     #[test]
     fn test_state_updates() {
         let update = StateUpdate::RefreshDataflows;
@@ -306,11 +1654,29 @@ mod app_state_tests {
         let warning = MessageLevel::Warning;
         let error = MessageLevel::Error;
 
-        // Test that message levels can be cloned
-        let _info_clone = info.clone();
-        let _success_clone = success.clone();
-        let _warning_clone = warning.clone();
-        let _error_clone = error.clone();
+        // Levels are ordered by severity for filtering
+        assert!(info < success && success < warning && warning < error);
+        assert_eq!(error.label(), "error");
+    }
+
+    #[test]
+    fn test_message_history_and_toasts() {
+        let mut state = AppState::default();
+        for index in 0..600 {
+            state.push_status_message(StatusMessage::new(
+                "test",
+                format!("message {index}"),
+                MessageLevel::Info,
+            ));
+        }
+        assert_eq!(state.status_messages.len(), 500);
+        assert_eq!(state.status_messages[0].message, "message 100");
+
+        // Only the newest few recent messages are shown as toasts.
+        let now = Instant::now();
+        let toasts: Vec<_> = state.toasts(now).map(|msg| msg.message.as_str()).collect();
+        assert_eq!(toasts, ["message 599", "message 598", "message 597"]);
+        assert_eq!(state.toasts(now + Duration::from_secs(6)).count(), 0);
     }
 }
 
@@ -1,16 +1,68 @@
+//! Color themes.
+//!
+//! `dark` and `light` are defined here; the other built-in themes and user
+//! themes are TOML files. User themes live in `<config dir>/dora/themes/`,
+//! one `<name>.toml` each, and take precedence over built-in themes of the
+//! same name:
+//!
+//! ```toml
+//! extends = "dark"
+//!
+//! [colors]
+//! primary = "#88c0d0"
+//! muted = "gray"
+//! border = "240"
+//!
+//! [styles]
+//! border = "double"
+//! selection = { bg = "#3b4252", bold = true }
+//! ```
+//!
+//! Colors are names, `#rrggbb` or 256-color indices. Whatever a file leaves
+//! out comes from the built-in theme it `extends`, or from `dark`. Loaded
+//! themes are converted to the color depth of the terminal.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
 use ratatui::{
     style::{Color, Modifier, Style},
     widgets::{Block, BorderType, Borders},
 };
-
-#[derive(Debug, Clone)]
+use serde::Deserialize;
+use tui_interface::StatusTone;
+
+use crate::tui::{app::MessageLevel, palette::ColorDepth};
+
+const THEMES_DIR: &str = "themes";
+
+/// Built-in themes defined by theme files.
+const BUILTIN_FILES: &[(&str, &str)] = &[
+    (
+        "solarized-dark",
+        include_str!("../../themes/solarized-dark.toml"),
+    ),
+    (
+        "solarized-light",
+        include_str!("../../themes/solarized-light.toml"),
+    ),
+    (
+        "high-contrast",
+        include_str!("../../themes/high-contrast.toml"),
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ThemeConfig {
     pub name: String,
     pub colors: ColorScheme,
     pub styles: StyleConfig,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColorScheme {
     pub primary: Color,
     pub secondary: Color,
@@ -29,7 +81,7 @@ pub struct ColorScheme {
     pub accent: Color,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StyleConfig {
     pub border_style: BorderType,
     pub highlight_style: Style,
@@ -37,25 +89,244 @@ pub struct StyleConfig {
     pub status_style: Style,
 }
 
+/// A theme file. Every field is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    extends: Option<String>,
+    colors: BTreeMap<String, String>,
+    styles: StylesFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StylesFile {
+    border: Option<String>,
+    highlight: Option<StyleFile>,
+    selection: Option<StyleFile>,
+    status: Option<StyleFile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StyleFile {
+    fg: Option<String>,
+    bg: Option<String>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    reversed: bool,
+}
+
+impl StyleFile {
+    fn to_style(&self) -> Result<Style, String> {
+        let mut style = Style::default();
+        if let Some(fg) = &self.fg {
+            style = style.fg(parse_color(fg)?);
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(parse_color(bg)?);
+        }
+        for (set, modifier) in [
+            (self.bold, Modifier::BOLD),
+            (self.italic, Modifier::ITALIC),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.reversed, Modifier::REVERSED),
+        ] {
+            if set {
+                style = style.add_modifier(modifier);
+            }
+        }
+        Ok(style)
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid color `{value}`"))
+}
+
+fn parse_border(value: &str) -> Result<BorderType, String> {
+    match value {
+        "plain" => Ok(BorderType::Plain),
+        "rounded" => Ok(BorderType::Rounded),
+        "double" => Ok(BorderType::Double),
+        "thick" => Ok(BorderType::Thick),
+        _ => Err(format!(
+            "invalid border `{value}` (expected plain, rounded, double or thick)"
+        )),
+    }
+}
+
+impl ColorScheme {
+    fn set(&mut self, name: &str, color: Color) -> Result<(), String> {
+        let slot = match name {
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "info" => &mut self.info,
+            "background" => &mut self.background,
+            "foreground" => &mut self.foreground,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "border" => &mut self.border,
+            "border_focused" => &mut self.border_focused,
+            "selection" => &mut self.selection,
+            "highlight" => &mut self.highlight,
+            "accent" => &mut self.accent,
+            _ => return Err(format!("unknown color `{name}`")),
+        };
+        *slot = color;
+        Ok(())
+    }
+
+    fn map(&mut self, f: impl Fn(Color) -> Color) {
+        for color in [
+            &mut self.primary,
+            &mut self.secondary,
+            &mut self.success,
+            &mut self.warning,
+            &mut self.error,
+            &mut self.info,
+            &mut self.background,
+            &mut self.foreground,
+            &mut self.text,
+            &mut self.muted,
+            &mut self.border,
+            &mut self.border_focused,
+            &mut self.selection,
+            &mut self.highlight,
+            &mut self.accent,
+        ] {
+            *color = f(*color);
+        }
+    }
+}
+
 impl ThemeConfig {
-    pub fn load_user_theme() -> Self {
-        // For now, load default theme
-        // In the future, this would check user config files
-        Self::default_dark()
+    /// `<config dir>/dora/themes`, if the platform has one.
+    pub fn themes_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("dora").join(THEMES_DIR))
+    }
+
+    /// Load the theme called `name` from the user's theme directory or the
+    /// built-in themes, converted to the terminal's color depth.
+    pub fn load_user_theme(name: &str) -> Result<Self, String> {
+        let theme = Self::load(name, Self::themes_dir().as_deref())?;
+        Ok(theme.for_depth(ColorDepth::detect()))
     }
 
+    /// Like `load_user_theme`, but falls back to `dark` if the theme can't be
+    /// loaded.
     pub fn from_name(name: &str) -> Self {
-        match name.to_lowercase().as_str() {
-            "light" => Self::default_light(),
-            other => {
-                if other != "dark" {
-                    tracing::debug!("Unknown theme '{}', falling back to dark", other);
+        Self::load_user_theme(name).unwrap_or_else(|err| {
+            tracing::warn!("{err}, falling back to dark");
+            Self::default_dark().for_depth(ColorDepth::detect())
+        })
+    }
+
+    /// The theme file `<dir>/<name>.toml` if there is one, otherwise the
+    /// built-in theme called `name`.
+    pub fn load(name: &str, dir: Option<&Path>) -> Result<Self, String> {
+        if let Some(path) = dir.map(|dir| dir.join(format!("{name}.toml"))) {
+            match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    return Self::from_toml(name, &contents)
+                        .map_err(|err| format!("{}: {err}", path.display()));
                 }
-                Self::default_dark()
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(format!("{}: {err}", path.display())),
+            }
+        }
+        Self::builtin(name).ok_or_else(|| {
+            format!(
+                "unknown theme `{name}` (available: {})",
+                Self::available(dir).join(", ")
+            )
+        })
+    }
+
+    /// Names of the built-in themes and of the theme files in `dir`.
+    pub fn available(dir: Option<&Path>) -> Vec<String> {
+        let mut names: Vec<String> = ["dark", "light"]
+            .into_iter()
+            .chain(BUILTIN_FILES.iter().map(|(name, _)| *name))
+            .map(str::to_string)
+            .collect();
+        let entries = dir.and_then(|dir| fs::read_dir(dir).ok());
+        for entry in entries.into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "toml") {
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::default_dark()),
+            "light" => Some(Self::default_light()),
+            _ => {
+                let (_, contents) = BUILTIN_FILES.iter().find(|(file, _)| *file == name)?;
+                Some(Self::from_toml(name, contents).expect("built-in themes parse"))
             }
         }
     }
 
+    /// Parse a theme file. Colors and styles it leaves out come from the
+    /// built-in theme it extends, or from `dark`.
+    pub fn from_toml(name: &str, contents: &str) -> Result<Self, String> {
+        let file: ThemeFile = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let mut theme = match file.extends.as_deref() {
+            Some(base) if base == name => return Err(format!("theme `{name}` extends itself")),
+            Some(base) => {
+                Self::builtin(base).ok_or_else(|| format!("unknown base theme `{base}`"))?
+            }
+            None => Self::default_dark(),
+        };
+        theme.name = name.to_string();
+
+        for (key, value) in &file.colors {
+            theme.colors.set(key, parse_color(value)?)?;
+        }
+        let styles = &file.styles;
+        if let Some(border) = &styles.border {
+            theme.styles.border_style = parse_border(border)?;
+        }
+        for (style, slot) in [
+            (&styles.highlight, &mut theme.styles.highlight_style),
+            (&styles.selection, &mut theme.styles.selection_style),
+            (&styles.status, &mut theme.styles.status_style),
+        ] {
+            if let Some(style) = style {
+                *slot = style.to_style()?;
+            }
+        }
+        Ok(theme)
+    }
+
+    /// This theme with every color replaced by the closest one `depth` can
+    /// show.
+    pub fn for_depth(mut self, depth: ColorDepth) -> Self {
+        self.colors.map(|color| depth.convert(color));
+        for style in [
+            &mut self.styles.highlight_style,
+            &mut self.styles.selection_style,
+            &mut self.styles.status_style,
+        ] {
+            *style = depth.convert_style(*style);
+        }
+        self
+    }
+
     pub fn default_dark() -> Self {
         Self {
             name: "dark".to_string(),
@@ -135,13 +406,25 @@ impl ThemeConfig {
             )
     }
 
-    pub fn status_style(&self, status: &str) -> Style {
-        let color = match status.to_lowercase().as_str() {
-            "running" | "active" | "healthy" | "ok" | "success" => self.colors.success,
-            "warning" | "degraded" | "slow" | "pending" => self.colors.warning,
-            "error" | "failed" | "stopped" | "critical" | "down" => self.colors.error,
-            "info" | "idle" | "waiting" => self.colors.primary,
-            _ => self.colors.text,
+    /// Style for a status. Accepts the typed dataflow/node statuses as well
+    /// as free-form health labels such as `"degraded"`.
+    pub fn status_style(&self, status: impl Into<StatusTone>) -> Style {
+        let color = match status.into() {
+            StatusTone::Healthy => self.colors.success,
+            StatusTone::Pending | StatusTone::Warning => self.colors.warning,
+            StatusTone::Failed | StatusTone::Stopped => self.colors.error,
+            StatusTone::Completed | StatusTone::Idle => self.colors.primary,
+            StatusTone::Unknown => self.colors.text,
+        };
+        Style::default().fg(color)
+    }
+
+    pub fn message_style(&self, level: MessageLevel) -> Style {
+        let color = match level {
+            MessageLevel::Info => self.colors.info,
+            MessageLevel::Success => self.colors.success,
+            MessageLevel::Warning => self.colors.warning,
+            MessageLevel::Error => self.colors.error,
         };
         Style::default().fg(color)
     }
@@ -296,9 +579,12 @@ pub struct ThemeManager {
 }
 
 impl ThemeManager {
-    /// Load the theme manager with default themes
+    /// Load the theme manager with the built-in themes
     pub fn load_default() -> Self {
-        let themes = vec![ThemeConfig::default_dark(), ThemeConfig::default_light()];
+        let themes: Vec<ThemeConfig> = ThemeConfig::available(None)
+            .iter()
+            .filter_map(|name| ThemeConfig::builtin(name))
+            .collect();
 
         Self {
             current_theme: themes[0].clone(),
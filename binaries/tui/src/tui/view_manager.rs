@@ -0,0 +1,769 @@
+//! Keeps view instances alive between frames.
+//!
+//! The active view and every view under it on the navigation stack own
+//! their state (selection, tabs, search input, scroll position) for as long
+//! as they are on screen or on the stack. Instances are built lazily and
+//! mounted before the first update, and are unmounted when they leave.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyEvent, MouseEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Borders, Paragraph, Wrap},
+};
+use tui_interface::LogService;
+
+use crate::tui::{
+    Result,
+    app::{AppState, ViewType},
+    keymap::Keymap,
+    modal::ModalResult,
+    session::SavedView,
+    theme::ThemeConfig,
+    views::{
+        DashboardView, DataflowExplorerView, DataflowManagerView, HelpView, LogViewerView,
+        NodeInspectorView, NotificationsView, PerformanceAnalyzerView, SystemMonitorView, View,
+        ViewAction,
+    },
+};
+
+/// A live view. `View` returns `impl Future`, so views are held in an enum
+/// rather than as trait objects. Views are boxed to keep the enum small.
+pub enum ViewInstance {
+    Dashboard(Box<DashboardView>),
+    DataflowManager(Box<DataflowManagerView>),
+    DataflowExplorer(Box<DataflowExplorerView>),
+    NodeInspector(Box<NodeInspectorView>),
+    SystemMonitor(Box<SystemMonitorView>),
+    PerformanceAnalyzer(Box<PerformanceAnalyzerView>),
+    LogViewer(Box<LogViewerView>),
+    Help(Box<HelpView>),
+    Notifications(Box<NotificationsView>),
+    Placeholder(PlaceholderView),
+}
+
+macro_rules! dispatch {
+    ($instance:expr, $view:ident => $body:expr) => {
+        match $instance {
+            ViewInstance::Dashboard($view) => $body,
+            ViewInstance::DataflowManager($view) => $body,
+            ViewInstance::DataflowExplorer($view) => $body,
+            ViewInstance::NodeInspector($view) => $body,
+            ViewInstance::SystemMonitor($view) => $body,
+            ViewInstance::PerformanceAnalyzer($view) => $body,
+            ViewInstance::LogViewer($view) => $body,
+            ViewInstance::Help($view) => $body,
+            ViewInstance::Notifications($view) => $body,
+            ViewInstance::Placeholder($view) => $body,
+        }
+    };
+}
+
+impl ViewInstance {
+    pub fn build(
+        view_type: &ViewType,
+        theme: &ThemeConfig,
+        keymap: &Arc<Keymap>,
+        log_service: Option<Arc<dyn LogService>>,
+    ) -> Self {
+        match view_type {
+            ViewType::Dashboard => Self::Dashboard(Box::new(DashboardView::new(theme))),
+            ViewType::DataflowManager => {
+                Self::DataflowManager(Box::new(DataflowManagerView::new(theme)))
+            }
+            ViewType::DataflowExplorer => {
+                Self::DataflowExplorer(Box::new(DataflowExplorerView::new(theme)))
+            }
+            ViewType::NodeInspector {
+                dataflow_id,
+                node_id,
+            } => Self::NodeInspector(Box::new(NodeInspectorView::new(
+                theme,
+                dataflow_id.clone(),
+                node_id.clone(),
+            ))),
+            ViewType::SystemMonitor => Self::SystemMonitor(Box::new(SystemMonitorView::new(theme))),
+            ViewType::PerformanceAnalyzer => {
+                Self::PerformanceAnalyzer(Box::new(PerformanceAnalyzerView::new(theme)))
+            }
+            ViewType::LogViewer { target } => {
+                Self::LogViewer(Box::new(LogViewerView::new(target, theme, log_service)))
+            }
+            ViewType::Help => Self::Help(Box::new(HelpView::new(theme, keymap.clone()))),
+            ViewType::Notifications => Self::Notifications(Box::new(NotificationsView::new(theme))),
+            other => Self::Placeholder(PlaceholderView::new(other, theme)),
+        }
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        dispatch!(self, view => view.render(f, area, app_state))
+    }
+
+    pub async fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        app_state: &mut AppState,
+    ) -> Result<ViewAction> {
+        dispatch!(self, view => view.handle_key(key, app_state).await)
+    }
+
+    pub fn handle_mouse(&mut self, event: MouseEvent, app_state: &mut AppState) -> ViewAction {
+        dispatch!(self, view => view.handle_mouse(event, app_state))
+    }
+
+    pub async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
+        dispatch!(self, view => view.update(app_state).await)
+    }
+
+    pub async fn on_mount(&mut self, app_state: &mut AppState) -> Result<()> {
+        dispatch!(self, view => view.on_mount(app_state).await)
+    }
+
+    pub fn on_unmount(&mut self) {
+        dispatch!(self, view => view.on_unmount())
+    }
+
+    pub fn on_focus(&mut self) {
+        dispatch!(self, view => view.on_focus())
+    }
+
+    pub fn on_blur(&mut self) {
+        dispatch!(self, view => view.on_blur())
+    }
+
+    pub fn auto_refresh(&self) -> Option<Duration> {
+        dispatch!(self, view => view.auto_refresh())
+    }
+
+    pub fn captures_text(&self) -> bool {
+        dispatch!(self, view => view.captures_text())
+    }
+
+    pub fn help_text(&self) -> Vec<(&str, &str)> {
+        dispatch!(self, view => view.help_text())
+    }
+
+    pub fn set_theme(&mut self, theme: &ThemeConfig) {
+        dispatch!(self, view => view.set_theme(theme))
+    }
+
+    pub fn on_modal_result(
+        &mut self,
+        id: &str,
+        result: ModalResult,
+        app_state: &mut AppState,
+    ) -> ViewAction {
+        dispatch!(self, view => view.on_modal_result(id, result, app_state))
+    }
+
+    /// Record the state of this view that should survive a restart.
+    fn save(&self, saved: &mut SavedView, app_state: &AppState) {
+        match self {
+            Self::DataflowExplorer(view) => saved.explorer = Some(view.session(app_state)),
+            Self::NodeInspector(view) => saved.inspector_tab = Some(view.state.active_tab),
+            Self::LogViewer(view) => saved.log_filter = Some(view.state.filter.clone()),
+            _ => {}
+        }
+    }
+
+    fn restore(&mut self, saved: SavedView) {
+        match self {
+            Self::DataflowExplorer(view) => {
+                if let Some(session) = saved.explorer {
+                    view.restore_session(session);
+                }
+            }
+            Self::NodeInspector(view) => {
+                if let Some(tab) = saved.inspector_tab {
+                    view.state.active_tab = tab;
+                }
+            }
+            Self::LogViewer(view) => {
+                if let Some(filter) = saved.log_filter {
+                    view.state.filter = filter;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct ViewEntry {
+    view_type: ViewType,
+    instance: Option<ViewInstance>,
+    mounted: bool,
+    last_update: Option<Instant>,
+}
+
+impl ViewEntry {
+    fn new(view_type: ViewType) -> Self {
+        Self {
+            view_type,
+            instance: None,
+            mounted: false,
+            last_update: None,
+        }
+    }
+
+    fn unmount(&mut self) {
+        if let Some(instance) = self.instance.as_mut() {
+            if self.mounted {
+                instance.on_blur();
+                instance.on_unmount();
+            }
+        }
+        self.mounted = false;
+    }
+}
+
+/// Owns the active view and the navigation stack under it.
+pub struct ViewManager {
+    active: ViewEntry,
+    stack: Vec<ViewEntry>,
+    theme: ThemeConfig,
+    keymap: Arc<Keymap>,
+    log_service: Option<Arc<dyn LogService>>,
+}
+
+impl ViewManager {
+    pub fn new(initial_view: ViewType, theme: ThemeConfig) -> Self {
+        Self {
+            active: ViewEntry::new(initial_view),
+            stack: Vec::new(),
+            theme,
+            keymap: Arc::default(),
+            log_service: None,
+        }
+    }
+
+    pub fn current(&self) -> &ViewType {
+        &self.active.view_type
+    }
+
+    pub fn stack_len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Views built from now on use `log_service`.
+    pub fn set_log_service(&mut self, log_service: Option<Arc<dyn LogService>>) {
+        self.log_service = log_service;
+    }
+
+    /// Views built from now on describe `keymap` in their help.
+    pub fn set_keymap(&mut self, keymap: Arc<Keymap>) {
+        self.keymap = keymap;
+    }
+
+    /// Views hold a copy of the theme, so a theme change is handed to every
+    /// live view; they keep their state.
+    pub fn set_theme(&mut self, theme: &ThemeConfig) {
+        if self.theme == *theme {
+            return;
+        }
+        self.theme = theme.clone();
+        for instance in self
+            .stack
+            .iter_mut()
+            .chain(std::iter::once(&mut self.active))
+            .filter_map(|entry| entry.instance.as_mut())
+        {
+            instance.set_theme(theme);
+        }
+    }
+
+    /// Replace the active view. Switching to the view already shown keeps
+    /// its state.
+    pub fn switch(&mut self, view_type: ViewType) {
+        if self.active.view_type == view_type {
+            return;
+        }
+        self.active.unmount();
+        self.active = ViewEntry::new(view_type);
+    }
+
+    /// Show `view_type` on top of the active view, which keeps its state
+    /// until it is popped back.
+    pub fn push(&mut self, view_type: ViewType) {
+        if let Some(instance) = self.active.instance.as_mut() {
+            instance.on_blur();
+        }
+        let covered = std::mem::replace(&mut self.active, ViewEntry::new(view_type));
+        self.stack.push(covered);
+    }
+
+    /// Return to the previous view. Returns `false` if the stack is empty.
+    pub fn pop(&mut self) -> bool {
+        let Some(previous) = self.stack.pop() else {
+            return false;
+        };
+        self.active.unmount();
+        self.active = previous;
+        if let Some(instance) = self.active.instance.as_mut() {
+            if self.active.mounted {
+                instance.on_focus();
+            }
+        }
+        true
+    }
+
+    /// The navigation stack and the state of every view on it, bottom
+    /// first.
+    pub fn saved_views(&self, app_state: &AppState) -> Vec<SavedView> {
+        self.stack
+            .iter()
+            .chain(std::iter::once(&self.active))
+            .map(|entry| {
+                let mut saved = SavedView::new(entry.view_type.clone());
+                if let Some(instance) = &entry.instance {
+                    instance.save(&mut saved, app_state);
+                }
+                saved
+            })
+            .collect()
+    }
+
+    /// Replace the stack with a saved one. Views are built right away so
+    /// they can take their saved state, and mounted when they are shown.
+    /// An empty stack leaves the current view alone.
+    pub fn restore_views(&mut self, views: Vec<SavedView>) {
+        let mut entries: Vec<ViewEntry> = views
+            .into_iter()
+            .map(|saved| {
+                let mut entry = ViewEntry::new(saved.view.clone());
+                let mut instance = ViewInstance::build(
+                    &entry.view_type,
+                    &self.theme,
+                    &self.keymap,
+                    self.log_service.clone(),
+                );
+                instance.restore(saved);
+                entry.instance = Some(instance);
+                entry
+            })
+            .collect();
+        let Some(active) = entries.pop() else {
+            return;
+        };
+        self.active.unmount();
+        for entry in &mut self.stack {
+            entry.unmount();
+        }
+        self.active = active;
+        self.stack = entries;
+    }
+
+    /// Unmount every view before the manager is dropped.
+    pub fn close(&mut self) {
+        self.active.unmount();
+        for entry in &mut self.stack {
+            entry.unmount();
+        }
+    }
+
+    /// Drop restored views about dataflows or nodes that no longer exist
+    /// and restore selections that still do. Returns what was dropped.
+    pub fn drop_stale_views(&mut self, app_state: &AppState) -> Vec<String> {
+        let mut dropped = Vec::new();
+        let exists = |view_type: &ViewType| match view_type {
+            ViewType::NodeInspector {
+                dataflow_id,
+                node_id,
+            } => app_state.dataflows.iter().any(|dataflow| {
+                (dataflow.id == *dataflow_id || dataflow.name == *dataflow_id)
+                    && dataflow
+                        .nodes
+                        .iter()
+                        .any(|node| node.id == *node_id || node.name == *node_id)
+            }),
+            ViewType::DebugSession { dataflow_id } => app_state
+                .dataflows
+                .iter()
+                .any(|dataflow| dataflow.id == *dataflow_id || dataflow.name == *dataflow_id),
+            _ => true,
+        };
+
+        self.stack.retain_mut(|entry| {
+            let keep = exists(&entry.view_type);
+            if !keep {
+                entry.unmount();
+                dropped.push(describe(&entry.view_type));
+            }
+            keep
+        });
+        if !exists(&self.active.view_type) {
+            dropped.push(describe(&self.active.view_type));
+            if !self.pop() {
+                self.active.unmount();
+                self.active = ViewEntry::new(ViewType::Dashboard);
+            }
+        }
+
+        for entry in self
+            .stack
+            .iter_mut()
+            .chain(std::iter::once(&mut self.active))
+        {
+            if let Some(ViewInstance::DataflowExplorer(view)) = entry.instance.as_mut() {
+                if let Some(id) = view.restore_selection(app_state) {
+                    dropped.push(format!("selected dataflow {id}"));
+                }
+            }
+        }
+        dropped
+    }
+
+    pub fn active_instance(&mut self) -> &mut ViewInstance {
+        let theme = &self.theme;
+        let keymap = &self.keymap;
+        let log_service = &self.log_service;
+        let entry = &mut self.active;
+        entry.instance.get_or_insert_with(|| {
+            ViewInstance::build(&entry.view_type, theme, keymap, log_service.clone())
+        })
+    }
+
+    /// Mount the active view if it has not been mounted yet.
+    pub async fn mount_pending(&mut self, app_state: &mut AppState) -> Result<()> {
+        if self.active.mounted {
+            return Ok(());
+        }
+        let instance = self.active_instance();
+        instance.on_mount(app_state).await?;
+        instance.on_focus();
+        self.active.mounted = true;
+        self.active.last_update = None;
+        Ok(())
+    }
+
+    /// Run the active view's periodic update when it is due. Views without
+    /// an auto-refresh interval update on every tick. Returns whether the
+    /// view's state changed and needs redrawing.
+    pub async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
+        self.mount_pending(app_state).await?;
+
+        let now = Instant::now();
+        let interval = self.active_instance().auto_refresh();
+        let due = match (interval, self.active.last_update) {
+            (Some(interval), Some(last)) => now.duration_since(last) >= interval,
+            _ => true,
+        };
+        if !due {
+            return Ok(false);
+        }
+        let changed = self.active_instance().update(app_state).await?;
+        self.active.last_update = Some(now);
+        Ok(changed)
+    }
+
+    pub async fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        app_state: &mut AppState,
+    ) -> Result<ViewAction> {
+        self.mount_pending(app_state).await?;
+        self.active_instance().handle_key(key, app_state).await
+    }
+
+    pub async fn handle_mouse(
+        &mut self,
+        event: MouseEvent,
+        app_state: &mut AppState,
+    ) -> Result<ViewAction> {
+        self.mount_pending(app_state).await?;
+        Ok(self.active_instance().handle_mouse(event, app_state))
+    }
+
+    /// Whether the active view wants raw keys for text entry.
+    pub fn captures_text(&mut self) -> bool {
+        self.active_instance().captures_text()
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        self.active_instance().render(f, area, app_state);
+    }
+
+    #[cfg(test)]
+    pub fn selected_dataflow(&mut self, app_state: &AppState) -> Option<String> {
+        match self.active_instance() {
+            ViewInstance::DataflowExplorer(view) => view
+                .get_selected_dataflow(app_state)
+                .map(|dataflow| dataflow.id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Select `dataflow` in the active view if it lists dataflows. Returns
+    /// whether the dataflow was found.
+    pub fn select_dataflow(&mut self, dataflow: &str, app_state: &AppState) -> bool {
+        match self.active_instance() {
+            ViewInstance::DataflowExplorer(view) => view.select_dataflow(dataflow, app_state),
+            _ => false,
+        }
+    }
+
+    /// Hand a dialog result to the view that opened it. Results for a view
+    /// that is no longer active are dropped.
+    pub fn deliver_modal_result(
+        &mut self,
+        origin: &ViewType,
+        id: &str,
+        result: ModalResult,
+        app_state: &mut AppState,
+    ) -> ViewAction {
+        if &self.active.view_type != origin {
+            return ViewAction::None;
+        }
+        self.active_instance()
+            .on_modal_result(id, result, app_state)
+    }
+}
+
+fn describe(view_type: &ViewType) -> String {
+    match view_type {
+        ViewType::NodeInspector {
+            dataflow_id,
+            node_id,
+        } => format!("inspector for node {node_id} of {dataflow_id}"),
+        ViewType::DebugSession { dataflow_id } => format!("debug session of {dataflow_id}"),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Stand-in for views that have no implementation yet.
+pub struct PlaceholderView {
+    title: String,
+    content: String,
+    theme: ThemeConfig,
+}
+
+impl PlaceholderView {
+    fn new(view_type: &ViewType, theme: &ThemeConfig) -> Self {
+        let (title, content) = match view_type {
+            ViewType::RecordingAnalyzer { recording_id } => (
+                format!("Recording: {recording_id}"),
+                format!(
+                    "🎬 Recording Analyzer\n\nAnalyzing recording: {recording_id}\n\nPlayback controls and data analysis tools."
+                ),
+            ),
+            ViewType::DebugSession { dataflow_id } => (
+                format!("Debug: {dataflow_id}"),
+                format!(
+                    "🐛 Debug Session\n\nDebugging dataflow: {dataflow_id}\n\nBreakpoints, step-through debugging, and variable inspection."
+                ),
+            ),
+            ViewType::SettingsManager => (
+                "Settings".to_string(),
+                "⚙️ Settings Manager\n\nConfigure Dora CLI/TUI:\n- Theme settings\n- Key bindings\n- Default behaviors\n- Performance tuning".to_string(),
+            ),
+            _ => (
+                "Not implemented".to_string(),
+                "View not implemented yet".to_string(),
+            ),
+        };
+
+        Self {
+            title,
+            content,
+            theme: theme.clone(),
+        }
+    }
+}
+
+impl View for PlaceholderView {
+    fn render(&mut self, f: &mut Frame, area: Rect, _app_state: &AppState) {
+        let widget = Paragraph::new(self.content.as_str())
+            .style(Style::default().fg(self.theme.colors.text))
+            .block(self.theme.styled_block(&self.title).borders(Borders::ALL))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(widget, area);
+    }
+
+    async fn handle_key(
+        &mut self,
+        _key: KeyEvent,
+        _app_state: &mut AppState,
+    ) -> Result<ViewAction> {
+        Ok(ViewAction::Unhandled)
+    }
+
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn help_text(&self) -> Vec<(&str, &str)> {
+        Vec::new()
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn view_state_survives_frames_and_pushes() {
+        let rt = runtime();
+        let mut state = AppState::default();
+        let mut views = ViewManager::new(ViewType::DataflowExplorer, ThemeConfig::default());
+
+        rt.block_on(views.handle_key(key(KeyCode::Char('2')), &mut state))
+            .unwrap();
+        let ViewInstance::DataflowExplorer(explorer) = views.active_instance() else {
+            panic!("explorer expected");
+        };
+        let tab = explorer.state.active_tab;
+
+        views.push(ViewType::Help);
+        assert_eq!(views.stack_len(), 1);
+        assert!(views.pop());
+
+        let ViewInstance::DataflowExplorer(explorer) = views.active_instance() else {
+            panic!("explorer expected");
+        };
+        assert_eq!(explorer.state.active_tab, tab);
+        assert!(!views.pop());
+    }
+
+    #[test]
+    fn switching_to_the_same_view_keeps_it() {
+        let rt = runtime();
+        let mut state = AppState::default();
+        let mut views = ViewManager::new(
+            ViewType::LogViewer {
+                target: String::new(),
+            },
+            ThemeConfig::default(),
+        );
+
+        rt.block_on(views.update(&mut state)).unwrap();
+        let ViewInstance::LogViewer(viewer) = views.active_instance() else {
+            panic!("log viewer expected");
+        };
+        let buffered = viewer.state.buffer_count();
+        assert!(buffered > 0);
+
+        views.switch(ViewType::LogViewer {
+            target: String::new(),
+        });
+        let ViewInstance::LogViewer(viewer) = views.active_instance() else {
+            panic!("log viewer expected");
+        };
+        assert_eq!(viewer.state.buffer_count(), buffered);
+
+        views.switch(ViewType::Dashboard);
+        assert!(matches!(
+            views.active_instance(),
+            ViewInstance::Dashboard(_)
+        ));
+    }
+
+    #[test]
+    fn theme_changes_keep_view_state() {
+        let rt = runtime();
+        let mut state = AppState::default();
+        let mut views = ViewManager::new(ViewType::DataflowExplorer, ThemeConfig::default());
+
+        rt.block_on(views.handle_key(key(KeyCode::Char('2')), &mut state))
+            .unwrap();
+        views.push(ViewType::LogViewer {
+            target: String::new(),
+        });
+        rt.block_on(views.update(&mut state)).unwrap();
+        let ViewInstance::LogViewer(viewer) = views.active_instance() else {
+            panic!("log viewer expected");
+        };
+        let buffered = viewer.state.buffer_count();
+        assert!(buffered > 0);
+
+        views.set_theme(&ThemeConfig::from_name("light"));
+        let ViewInstance::LogViewer(viewer) = views.active_instance() else {
+            panic!("log viewer expected");
+        };
+        assert_eq!(viewer.state.buffer_count(), buffered);
+
+        assert!(views.pop());
+        let ViewInstance::DataflowExplorer(explorer) = views.active_instance() else {
+            panic!("explorer expected");
+        };
+        assert_eq!(
+            explorer.state.active_tab,
+            crate::tui::views::ExplorerTab::Nodes
+        );
+    }
+
+    #[test]
+    fn sessions_restore_view_state() {
+        use crate::tui::{
+            app::DataflowInfo,
+            views::{ExplorerTab, LogLevel, ViewMode},
+        };
+
+        let rt = runtime();
+        let mut state = AppState {
+            dataflows: ["df-1", "df-2"]
+                .into_iter()
+                .map(|id| DataflowInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut views = ViewManager::new(ViewType::DataflowExplorer, ThemeConfig::default());
+        for code in [KeyCode::Char('v'), KeyCode::Down] {
+            rt.block_on(views.handle_key(key(code), &mut state))
+                .unwrap();
+        }
+        views.push(ViewType::LogViewer {
+            target: "df-2".to_string(),
+        });
+        rt.block_on(views.handle_key(key(KeyCode::Char('4')), &mut state))
+            .unwrap();
+        let saved = views.saved_views(&state);
+
+        let mut restored = ViewManager::new(ViewType::Dashboard, ThemeConfig::default());
+        restored.restore_views(saved);
+        assert_eq!(restored.stack_len(), 1);
+        let ViewInstance::LogViewer(viewer) = restored.active_instance() else {
+            panic!("log viewer expected");
+        };
+        assert!(!viewer.state.filter.is_level_enabled(LogLevel::Debug));
+
+        assert!(restored.drop_stale_views(&state).is_empty());
+        assert!(restored.pop());
+        let ViewInstance::DataflowExplorer(explorer) = restored.active_instance() else {
+            panic!("explorer expected");
+        };
+        assert_eq!(explorer.state.active_tab, ExplorerTab::Overview);
+        assert_eq!(explorer.state.view_mode, ViewMode::ListFlat);
+        assert_eq!(restored.selected_dataflow(&state).as_deref(), Some("df-2"));
+    }
+}
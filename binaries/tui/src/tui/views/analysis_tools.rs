@@ -430,8 +430,8 @@ impl View for AnalysisToolsView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -453,6 +453,10 @@ impl View for AnalysisToolsView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 #[cfg(test)]
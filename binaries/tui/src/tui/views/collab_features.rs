@@ -401,8 +401,8 @@ impl View for CollabFeaturesView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -424,6 +424,10 @@ impl View for CollabFeaturesView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 #[cfg(test)]
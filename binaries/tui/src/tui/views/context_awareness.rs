@@ -511,8 +511,8 @@ impl View for ContextAwarenessView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -527,6 +527,10 @@ impl View for ContextAwarenessView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -7,15 +7,20 @@ use ratatui::{
     widgets::{Cell, List, ListItem, Paragraph, Row, Table},
 };
 use std::time::Duration;
+use tui_interface::DataflowCommand;
 
 use super::{
-    BaseView, DashboardState, DataflowSummary, DiskUsage, MemoryUsage, NetworkActivity,
+    Alert, BaseView, DashboardState, DataflowSummary, DiskUsage, MemoryUsage, NetworkActivity,
     RefreshManager, StateUpdate, SystemOverview, SystemStatus, View, ViewAction, utils,
 };
 use crate::tui::{
     Result,
     app::{AppState, NodeInfo, ViewType},
     components::{Component, DataflowSummaryComponent, SystemOverviewComponent},
+    connection::{Link, LinkStatus},
+    modal::ModalResult,
+    mouse::{self, MouseGesture},
+    responsive::{self, Breakpoint},
     theme::ThemeConfig,
 };
 
@@ -24,6 +29,8 @@ pub struct DashboardView {
     theme: ThemeConfig,
     selected_dataflow: usize,
     show_system_info: bool,
+    /// Where the dataflow table was drawn, for mouse hit testing
+    dataflows_area: Rect,
     // Issue #24 additions
     dashboard_state: DashboardState,
     system_overview_component: SystemOverviewComponent,
@@ -38,6 +45,7 @@ impl DashboardView {
             theme: theme.clone(),
             selected_dataflow: 0,
             show_system_info: true,
+            dataflows_area: Rect::default(),
             // Issue #24 additions
             dashboard_state: DashboardState::default(),
             system_overview_component: SystemOverviewComponent::new(),
@@ -50,11 +58,10 @@ impl DashboardView {
     fn collect_system_overview(&self, app_state: &AppState) -> SystemOverview {
         let metrics = &app_state.system_metrics;
 
-        // Determine system status based on daemon connectivity
-        let status = if app_state.dataflows.is_empty() {
-            SystemStatus::Disconnected
-        } else {
-            SystemStatus::Connected
+        let status = match app_state.connection.status(Link::Gateway) {
+            LinkStatus::Connecting => SystemStatus::Connecting,
+            LinkStatus::Connected => SystemStatus::Connected,
+            LinkStatus::Reconnecting => SystemStatus::Disconnected,
         };
 
         let memory_total_mb = bytes_to_megabytes(metrics.memory.total_bytes);
@@ -95,12 +102,12 @@ impl DashboardView {
         let running = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "running")
+            .filter(|df| df.status.is_running())
             .count() as u32;
         let failed = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "failed")
+            .filter(|df| df.status.is_failed())
             .count() as u32;
         let stopped = total - running - failed;
 
@@ -114,7 +121,7 @@ impl DashboardView {
             .dataflows
             .iter()
             .flat_map(|df| &df.nodes)
-            .filter(|node| node.status == "running" || node.status == "healthy")
+            .filter(|node| node.status.is_running())
             .count() as u32;
 
         let unhealthy_nodes = total_nodes - healthy_nodes;
@@ -132,22 +139,28 @@ impl DashboardView {
     }
 
     /// Update dashboard data if refresh is needed (Issue #24)
-    fn refresh_dashboard_data(&mut self, app_state: &AppState) {
-        if self.refresh_manager.should_refresh() {
-            // Collect fresh data
-            let system_overview = self.collect_system_overview(app_state);
-            let dataflow_summary = self.collect_dataflow_summary(app_state);
+    /// Returns whether the collected data differs from what was shown.
+    fn refresh_dashboard_data(&mut self, app_state: &AppState) -> bool {
+        if !self.refresh_manager.should_refresh() {
+            return false;
+        }
 
-            // Update dashboard state
-            self.dashboard_state.system_overview = system_overview.clone();
-            self.dashboard_state.dataflow_summary = dataflow_summary.clone();
+        // Collect fresh data
+        let system_overview = self.collect_system_overview(app_state);
+        let dataflow_summary = self.collect_dataflow_summary(app_state);
+        let changed = system_overview != self.dashboard_state.system_overview
+            || dataflow_summary != self.dashboard_state.dataflow_summary;
 
-            // Update components
-            self.system_overview_component.set_data(system_overview);
-            self.dataflow_summary_component.set_data(dataflow_summary);
+        // Update dashboard state
+        self.dashboard_state.system_overview = system_overview.clone();
+        self.dashboard_state.dataflow_summary = dataflow_summary.clone();
 
-            self.refresh_manager.mark_refreshed();
-        }
+        // Update components
+        self.system_overview_component.set_data(system_overview);
+        self.dataflow_summary_component.set_data(dataflow_summary);
+
+        self.refresh_manager.mark_refreshed();
+        changed
     }
 
     fn render_overview(&self, f: &mut Frame, area: Rect, app_state: &AppState) {
@@ -155,7 +168,7 @@ impl DashboardView {
         let running_count = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "running")
+            .filter(|df| df.status.is_running())
             .count();
 
         let overview_text = vec![
@@ -198,7 +211,8 @@ impl DashboardView {
         f.render_widget(overview, area);
     }
 
-    fn render_dataflows(&self, f: &mut Frame, area: Rect, app_state: &AppState) {
+    /// The dataflow table; `wide` adds running-node counts and ids.
+    fn render_dataflows(&self, f: &mut Frame, area: Rect, app_state: &AppState, wide: bool) {
         if app_state.dataflows.is_empty() {
             let empty_msg = Paragraph::new(
                 "No dataflows found. Use 'dora start <dataflow.yaml>' to start one.",
@@ -210,7 +224,11 @@ impl DashboardView {
             return;
         }
 
-        let header_cells = ["Name", "Status", "Nodes", "Actions"]
+        let mut headers = vec!["Name", "Status", "Nodes", "Actions"];
+        if wide {
+            headers.extend(["Running", "ID"]);
+        }
+        let header_cells = headers
             .iter()
             .map(|h| Cell::from(*h).style(self.theme.table_header_style()))
             .collect::<Vec<_>>();
@@ -231,29 +249,49 @@ impl DashboardView {
                     Style::default().fg(self.theme.colors.text)
                 };
 
-                let status_style = self.theme.status_style(&dataflow.status);
-                let status_indicator = utils::status_indicator(&dataflow.status);
+                let status_style = self.theme.status_style(dataflow.status);
+                let status_indicator = utils::status_indicator(dataflow.status);
 
-                Row::new(vec![
+                let mut cells = vec![
                     Cell::from(dataflow.name.clone()),
                     Cell::from(Line::from(vec![
                         status_indicator,
                         Span::raw(" "),
-                        Span::styled(dataflow.status.clone(), status_style),
+                        Span::styled(dataflow.status.as_str(), status_style),
                     ])),
                     Cell::from(dataflow.nodes.len().to_string()),
                     Cell::from("inspect | logs | stop"),
-                ])
-                .style(style)
+                ];
+                if wide {
+                    let running = dataflow
+                        .nodes
+                        .iter()
+                        .filter(|node| node.status.is_running())
+                        .count();
+                    cells.push(Cell::from(running.to_string()));
+                    cells.push(Cell::from(dataflow.id.clone()));
+                }
+                Row::new(cells).style(style)
             })
             .collect::<Vec<_>>();
 
-        let widths = [
-            Constraint::Percentage(30),
-            Constraint::Percentage(20),
-            Constraint::Percentage(15),
-            Constraint::Percentage(35),
-        ];
+        let widths = if wide {
+            vec![
+                Constraint::Percentage(20),
+                Constraint::Percentage(12),
+                Constraint::Percentage(8),
+                Constraint::Percentage(20),
+                Constraint::Percentage(8),
+                Constraint::Min(36),
+            ]
+        } else {
+            vec![
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+                Constraint::Percentage(15),
+                Constraint::Percentage(35),
+            ]
+        };
 
         let table = Table::new(rows, widths)
             .header(header)
@@ -273,6 +311,41 @@ impl DashboardView {
             .render(f, area, &self.theme, app_state);
     }
 
+    /// The most severe active alerts; the rest are counted in the title.
+    fn render_alerts(&self, f: &mut Frame, area: Rect, alerts: &[&Alert]) {
+        let items: Vec<ListItem> = alerts
+            .iter()
+            .take(MAX_ALERT_ROWS)
+            .map(|alert| {
+                let style = if alert.acknowledged {
+                    Style::default().fg(self.theme.colors.muted)
+                } else {
+                    Style::default().fg(alert.level.color())
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} {}", alert.level.icon(), alert.title), style),
+                    Span::styled(
+                        format!("  {}", alert.message),
+                        Style::default().fg(self.theme.colors.text),
+                    ),
+                ]))
+            })
+            .collect();
+        let title = if alerts.len() > MAX_ALERT_ROWS {
+            format!(
+                "Alerts ({}, {} more in the performance analyzer)",
+                alerts.len(),
+                alerts.len() - MAX_ALERT_ROWS
+            )
+        } else {
+            format!("Alerts ({})", alerts.len())
+        };
+        f.render_widget(
+            List::new(items).block(self.theme.styled_block(&title)),
+            area,
+        );
+    }
+
     fn render_quick_actions(&self, f: &mut Frame, area: Rect) {
         let actions = vec![
             "1: Dashboard",
@@ -311,13 +384,20 @@ impl DashboardView {
     }
 }
 
+// Dialog ids; the stop and inspect ids are followed by the dataflow id,
+// the restart id by its name.
+const STOP_PREFIX: &str = "stop:";
+const INSPECT_PREFIX: &str = "inspect:";
+const RESTART_PREFIX: &str = "restart:";
+const NEW_DATAFLOW: &str = "new-dataflow";
+
+/// Alerts listed on the dashboard.
+const MAX_ALERT_ROWS: usize = 3;
+
 fn select_preferred_node(nodes: &[NodeInfo]) -> Option<&NodeInfo> {
     nodes
         .iter()
-        .find(|node| {
-            let status = node.status.to_ascii_lowercase();
-            status == "running" || status == "active"
-        })
+        .find(|node| node.status.is_running())
         .or_else(|| nodes.first())
 }
 
@@ -331,11 +411,21 @@ fn bytes_to_gigabytes(bytes: u64) -> u64 {
 
 impl View for DashboardView {
     fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        let breakpoint = Breakpoint::of(area);
+        // Active alerts get a few rows between the overview and the
+        // dataflows, and no room at all when there are none.
+        let alerts = app_state.alerts.active();
+        let alert_rows = if alerts.is_empty() {
+            0
+        } else {
+            alerts.len().min(MAX_ALERT_ROWS) as u16 + 2
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(5), // Overview
-                Constraint::Min(8),    // Dataflows table
+                Constraint::Length(5),          // Overview
+                Constraint::Length(alert_rows), // Alerts
+                Constraint::Min(8),             // Dataflows table
             ])
             .split(area);
 
@@ -347,18 +437,50 @@ impl View for DashboardView {
             ])
             .split(chunks[0]);
 
-        let bottom_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(75), // Dataflows
-                Constraint::Percentage(25), // Quick actions
-            ])
-            .split(chunks[1]);
+        // The quick actions repeat the help screen, so compact areas give
+        // their room to the dataflows.
+        let (dataflows_area, actions_area) = if breakpoint.is_compact() {
+            (chunks[2], None)
+        } else {
+            responsive::with_side_panel(chunks[2], 25)
+        };
 
         self.render_overview(f, top_chunks[0], app_state);
         self.render_system_metrics(f, top_chunks[1], app_state);
-        self.render_dataflows(f, bottom_chunks[0], app_state);
-        self.render_quick_actions(f, bottom_chunks[1]);
+        if !alerts.is_empty() {
+            self.render_alerts(f, chunks[1], &alerts);
+        }
+        self.dataflows_area = dataflows_area;
+        self.render_dataflows(f, dataflows_area, app_state, breakpoint.is_wide());
+        if let Some(actions_area) = actions_area {
+            self.render_quick_actions(f, actions_area);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, app_state: &mut AppState) -> ViewAction {
+        if !mouse::hits(self.dataflows_area, &event) {
+            return ViewAction::Unhandled;
+        }
+
+        let count = app_state.dataflows.len();
+        match MouseGesture::from_event(&event) {
+            Some(MouseGesture::Click) => {
+                // Rows start below the header and its margin.
+                if let Some(row) = mouse::row_at(self.dataflows_area, 2, &event) {
+                    if row < count {
+                        self.selected_dataflow = row;
+                    }
+                }
+            }
+            Some(MouseGesture::ScrollUp) => {
+                self.selected_dataflow = self.selected_dataflow.saturating_sub(1);
+            }
+            Some(MouseGesture::ScrollDown) => {
+                self.selected_dataflow = (self.selected_dataflow + 1).min(count.saturating_sub(1));
+            }
+            None => return ViewAction::Unhandled,
+        }
+        ViewAction::None
     }
 
     async fn handle_key(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<ViewAction> {
@@ -379,7 +501,13 @@ impl View for DashboardView {
 
             KeyCode::Enter => {
                 if let Some(dataflow) = app_state.dataflows.get(self.selected_dataflow) {
-                    if let Some(node) = select_preferred_node(&dataflow.nodes) {
+                    if dataflow.nodes.len() > 1 {
+                        Ok(ViewAction::ShowSelection {
+                            id: format!("{INSPECT_PREFIX}{}", dataflow.id),
+                            title: format!("Inspect node in {}", dataflow.name),
+                            options: dataflow.nodes.iter().map(|node| node.id.clone()).collect(),
+                        })
+                    } else if let Some(node) = select_preferred_node(&dataflow.nodes) {
                         Ok(ViewAction::PushView(ViewType::NodeInspector {
                             dataflow_id: dataflow.id.clone(),
                             node_id: node.id.clone(),
@@ -396,12 +524,20 @@ impl View for DashboardView {
 
             KeyCode::Char(' ') => {
                 if let Some(dataflow) = app_state.dataflows.get(self.selected_dataflow) {
-                    let command = if dataflow.status == "running" {
-                        format!("stop {}", dataflow.id)
+                    if dataflow.status.is_running() {
+                        Ok(ViewAction::ShowConfirmation {
+                            message: format!("Stop dataflow {}?", dataflow.name),
+                            confirm_action: format!("{STOP_PREFIX}{}", dataflow.id),
+                        })
                     } else {
-                        format!("start {}", dataflow.id)
-                    };
-                    Ok(ViewAction::ExecuteCommand(command))
+                        // The coordinator does not report where a dataflow
+                        // was started from, so ask for the descriptor again.
+                        Ok(ViewAction::ShowInput {
+                            id: format!("{RESTART_PREFIX}{}", dataflow.name),
+                            prompt: format!("Descriptor to restart {}", dataflow.name),
+                            default: None,
+                        })
+                    }
                 } else {
                     Ok(ViewAction::None)
                 }
@@ -431,7 +567,11 @@ impl View for DashboardView {
                 )))
             }
 
-            KeyCode::Char('n') => Ok(ViewAction::ExecuteCommand("new dataflow".to_string())),
+            KeyCode::Char('n') => Ok(ViewAction::ShowInput {
+                id: NEW_DATAFLOW.to_string(),
+                prompt: "Dataflow descriptor to start".to_string(),
+                default: None,
+            }),
 
             KeyCode::Char('?') | KeyCode::F(1) => Ok(ViewAction::ShowHelp),
 
@@ -445,26 +585,26 @@ impl View for DashboardView {
                 Ok(ViewAction::UpdateState(StateUpdate::RefreshDataflows))
             }
 
-            _ => Ok(ViewAction::None),
+            _ => Ok(ViewAction::Unhandled),
         }
     }
 
-    async fn update(&mut self, app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
         // Issue #24: Refresh dashboard data at 2-second intervals
-        self.refresh_dashboard_data(app_state);
+        let changed = self.refresh_dashboard_data(app_state);
 
         if self.base.needs_refresh() {
             // Request data refresh
             self.base.mark_updated();
         }
-        Ok(())
+        Ok(changed)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
         vec![
             ("↑/k", "Move up"),
             ("↓/j", "Move down"),
-            ("Enter", "Inspect selected dataflow"),
+            ("Enter", "Inspect a node of the selected dataflow"),
             ("Space", "Start/Stop selected dataflow"),
             ("d", "Navigate to dataflows"),           // Issue #24
             ("p", "Navigate to performance monitor"), // Issue #24
@@ -483,6 +623,10 @@ impl View for DashboardView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn auto_refresh(&self) -> Option<Duration> {
         self.base.auto_refresh_interval
     }
@@ -502,6 +646,39 @@ impl View for DashboardView {
         self.base.mark_updated();
         Ok(())
     }
+
+    fn on_modal_result(
+        &mut self,
+        id: &str,
+        result: ModalResult,
+        _app_state: &mut AppState,
+    ) -> ViewAction {
+        match result {
+            ModalResult::Confirmed => match id.strip_prefix(STOP_PREFIX) {
+                Some(dataflow_id) => {
+                    ViewAction::ControlDataflow(DataflowCommand::Stop(dataflow_id.to_string()))
+                }
+                None => ViewAction::None,
+            },
+            ModalResult::Input(path) if !path.trim().is_empty() => {
+                let mut command = vec!["start", path.trim()];
+                if let Some(name) = id.strip_prefix(RESTART_PREFIX) {
+                    command.extend(["--name", name]);
+                } else if id != NEW_DATAFLOW {
+                    return ViewAction::None;
+                }
+                ViewAction::ExecuteCommand(shell_words::join(command))
+            }
+            ModalResult::Selected { value, .. } => match id.strip_prefix(INSPECT_PREFIX) {
+                Some(dataflow_id) => ViewAction::PushView(ViewType::NodeInspector {
+                    dataflow_id: dataflow_id.to_string(),
+                    node_id: value,
+                }),
+                None => ViewAction::None,
+            },
+            _ => ViewAction::None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -15,7 +15,7 @@ pub struct DashboardState {
 
 
 /// System overview with comprehensive metrics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SystemOverview {
     pub status: SystemStatus,
     pub uptime: Duration,
@@ -61,7 +61,7 @@ impl std::fmt::Display for SystemStatus {
 }
 
 /// Detailed memory usage information
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MemoryUsage {
     pub total_mb: u64,
     pub used_mb: u64,
@@ -69,7 +69,7 @@ pub struct MemoryUsage {
 }
 
 /// Disk usage information
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct DiskUsage {
     pub total_gb: u64,
     pub used_gb: u64,
@@ -77,14 +77,14 @@ pub struct DiskUsage {
 }
 
 /// Network activity metrics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct NetworkActivity {
     pub bytes_received: u64,
     pub bytes_transmitted: u64,
 }
 
 /// Dataflow summary with health tracking
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[derive(Default)]
 pub struct DataflowSummary {
     pub total_dataflows: u32,
@@ -119,7 +119,7 @@ impl DataflowSummary {
 }
 
 /// Recent deployment information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RecentDeployment {
     pub dataflow_name: String,
     pub timestamp: Instant,
@@ -468,8 +468,8 @@ impl View for DataVizView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -492,6 +492,10 @@ impl View for DataVizView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 #[cfg(test)]
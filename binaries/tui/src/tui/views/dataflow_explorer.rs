@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 /// Dataflow Explorer View for Issue #25
 use ratatui::{
     Frame,
@@ -8,15 +8,26 @@ use ratatui::{
     widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, Tabs},
 };
 use std::time::Duration;
+use tui_interface::{DataflowCommand, DataflowStatus};
 
 use crate::tui::{
     Result,
     app::{AppState, ViewType},
+    modal::ModalResult,
+    mouse::{self, MouseGesture},
+    responsive::{self, Breakpoint},
+    session::ExplorerSession,
     theme::ThemeConfig,
 };
 
 use super::{BaseView, ExplorerState, ExplorerTab, StateUpdate, View, ViewAction, ViewMode, utils};
 
+const TAB_DIVIDER: &str = " │ ";
+
+/// Dialog ids for lifecycle prompts; the dataflow follows the prefix.
+const STOP_PREFIX: &str = "stop:";
+const RESTART_PREFIX: &str = "restart:";
+
 pub struct DataflowExplorerView {
     base: BaseView,
     theme: ThemeConfig,
@@ -28,6 +39,13 @@ pub struct DataflowExplorerView {
     /// Whether inspection panel is shown (for testing/internal use)
     #[doc(hidden)]
     pub show_inspection_panel: bool,
+    /// Where the tab bar and tab content were drawn, for mouse hit testing
+    tabs_area: Rect,
+    content_area: Rect,
+    /// Size class of the last frame; wide frames get extra table columns
+    breakpoint: Breakpoint,
+    /// Dataflow selected in the last session, until the dataflows arrive
+    pending_selection: Option<String>,
 }
 
 impl DataflowExplorerView {
@@ -39,6 +57,52 @@ impl DataflowExplorerView {
             state: ExplorerState::new(),
             selected_index: 0,
             show_inspection_panel: false,
+            tabs_area: Rect::default(),
+            content_area: Rect::default(),
+            breakpoint: Breakpoint::Regular,
+            pending_selection: None,
+        }
+    }
+
+    /// The selection is only kept on the overview tab, where it indexes
+    /// dataflows rather than nodes.
+    pub fn session(&self, app_state: &AppState) -> ExplorerSession {
+        let selected = (self.state.active_tab == ExplorerTab::Overview)
+            .then(|| self.get_selected_dataflow(app_state))
+            .flatten()
+            .map(|dataflow| dataflow.id.clone());
+        ExplorerSession {
+            tab: self.state.active_tab,
+            view_mode: self.state.view_mode,
+            show_stopped: self.state.show_stopped,
+            selected_dataflow: self.pending_selection.clone().or(selected),
+        }
+    }
+
+    /// Restore tabs and modes now; the selection is restored by
+    /// [`Self::restore_selection`] once the dataflows are known.
+    pub fn restore_session(&mut self, session: ExplorerSession) {
+        self.state.active_tab = session.tab;
+        self.state.view_mode = session.view_mode;
+        self.state.show_stopped = session.show_stopped;
+        self.pending_selection = session.selected_dataflow;
+    }
+
+    /// Select the dataflow saved with the session. Returns its id if it no
+    /// longer exists.
+    pub fn restore_selection(&mut self, app_state: &AppState) -> Option<String> {
+        let id = self.pending_selection.take()?;
+        match app_state
+            .dataflows
+            .iter()
+            .filter(|df| self.state.show_stopped || df.status != DataflowStatus::Stopped)
+            .position(|df| df.id == id)
+        {
+            Some(index) => {
+                self.selected_index = index;
+                None
+            }
+            None => Some(id),
         }
     }
 
@@ -51,7 +115,7 @@ impl DataflowExplorerView {
         let filtered: Vec<_> = app_state
             .dataflows
             .iter()
-            .filter(|df| self.state.show_stopped || df.status != "stopped")
+            .filter(|df| self.state.show_stopped || df.status != DataflowStatus::Stopped)
             .collect();
         filtered.get(self.selected_index).copied()
     }
@@ -91,13 +155,36 @@ impl DataflowExplorerView {
             ExplorerTab::Overview => app_state
                 .dataflows
                 .iter()
-                .filter(|df| self.state.show_stopped || df.status != "stopped")
+                .filter(|df| self.state.show_stopped || df.status != DataflowStatus::Stopped)
                 .count(),
             ExplorerTab::Nodes => app_state.dataflows.iter().flat_map(|df| &df.nodes).count(),
             _ => 0,
         }
     }
 
+    /// Show `dataflow`, given by id or name, on the overview tab. Stopped
+    /// dataflows are shown if needed. Returns whether it was found.
+    pub fn select_dataflow(&mut self, dataflow: &str, app_state: &AppState) -> bool {
+        let Some(found) = app_state
+            .dataflows
+            .iter()
+            .find(|df| df.id == dataflow || df.name == dataflow)
+        else {
+            return false;
+        };
+        if found.status == DataflowStatus::Stopped {
+            self.state.show_stopped = true;
+        }
+        self.state.active_tab = ExplorerTab::Overview;
+        self.selected_index = app_state
+            .dataflows
+            .iter()
+            .filter(|df| self.state.show_stopped || df.status != DataflowStatus::Stopped)
+            .position(|df| df.id == found.id)
+            .unwrap_or_default();
+        true
+    }
+
     /// Navigate selection up (internal/testing use)
     #[doc(hidden)]
     pub fn select_previous(&mut self, app_state: &AppState) {
@@ -144,7 +231,7 @@ impl DataflowExplorerView {
                     .fg(self.theme.colors.primary)
                     .add_modifier(Modifier::BOLD),
             )
-            .divider(Span::raw(" │ "));
+            .divider(Span::raw(TAB_DIVIDER));
 
         f.render_widget(tabs, area);
     }
@@ -164,17 +251,17 @@ impl DataflowExplorerView {
         let running: Vec<_> = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "running")
+            .filter(|df| df.status.is_running())
             .collect();
         let stopped: Vec<_> = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "stopped")
+            .filter(|df| df.status.is_inactive())
             .collect();
         let failed: Vec<_> = app_state
             .dataflows
             .iter()
-            .filter(|df| df.status == "failed")
+            .filter(|df| df.status.is_failed())
             .collect();
 
         let mut items = Vec::new();
@@ -195,7 +282,7 @@ impl DataflowExplorerView {
             ])));
 
             for df in running {
-                let status_indicator = utils::status_indicator(&df.status);
+                let status_indicator = utils::status_indicator(df.status);
                 items.push(ListItem::new(Line::from(vec![
                     Span::raw("  "),
                     status_indicator,
@@ -227,7 +314,7 @@ impl DataflowExplorerView {
             ])));
 
             for df in failed {
-                let status_indicator = utils::status_indicator(&df.status);
+                let status_indicator = utils::status_indicator(df.status);
                 items.push(ListItem::new(Line::from(vec![
                     Span::raw("  "),
                     status_indicator,
@@ -259,7 +346,7 @@ impl DataflowExplorerView {
             ])));
 
             for df in stopped {
-                let status_indicator = utils::status_indicator(&df.status);
+                let status_indicator = utils::status_indicator(df.status);
                 items.push(ListItem::new(Line::from(vec![
                     Span::raw("  "),
                     status_indicator,
@@ -308,7 +395,12 @@ impl DataflowExplorerView {
             return;
         }
 
-        let header_cells = ["Status", "Name", "Nodes", "Uptime"]
+        let wide = self.breakpoint.is_wide();
+        let mut headers = vec!["Status", "Name", "Nodes", "Uptime"];
+        if wide {
+            headers.extend(["Running", "ID"]);
+        }
+        let header_cells = headers
             .iter()
             .map(|h| Cell::from(*h).style(self.theme.table_header_style()))
             .collect::<Vec<_>>();
@@ -321,31 +413,51 @@ impl DataflowExplorerView {
         let rows = app_state
             .dataflows
             .iter()
-            .filter(|df| self.state.show_stopped || df.status != "stopped")
+            .filter(|df| self.state.show_stopped || df.status != DataflowStatus::Stopped)
             .map(|df| {
-                let status_style = self.theme.status_style(&df.status);
-                let status_indicator = utils::status_indicator(&df.status);
+                let status_style = self.theme.status_style(df.status);
+                let status_indicator = utils::status_indicator(df.status);
 
-                Row::new(vec![
+                let mut cells = vec![
                     Cell::from(Line::from(vec![
                         status_indicator,
                         Span::raw(" "),
-                        Span::styled(&df.status, status_style),
+                        Span::styled(df.status.as_str(), status_style),
                     ])),
                     Cell::from(df.name.clone()),
                     Cell::from(df.nodes.len().to_string()),
                     Cell::from("--"), // TODO: Track uptime
-                ])
-                .style(Style::default().fg(self.theme.colors.text))
+                ];
+                if wide {
+                    let running = df
+                        .nodes
+                        .iter()
+                        .filter(|node| node.status.is_running())
+                        .count();
+                    cells.push(Cell::from(running.to_string()));
+                    cells.push(Cell::from(df.id.clone()));
+                }
+                Row::new(cells).style(Style::default().fg(self.theme.colors.text))
             })
             .collect::<Vec<_>>();
 
-        let widths = [
-            Constraint::Percentage(20),
-            Constraint::Percentage(40),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-        ];
+        let widths = if wide {
+            vec![
+                Constraint::Percentage(12),
+                Constraint::Percentage(25),
+                Constraint::Percentage(8),
+                Constraint::Percentage(10),
+                Constraint::Percentage(8),
+                Constraint::Min(36),
+            ]
+        } else {
+            vec![
+                Constraint::Percentage(20),
+                Constraint::Percentage(40),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ]
+        };
 
         let title = format!("Dataflows [{}]", self.state.view_mode.name());
         let table = Table::new(rows, widths)
@@ -377,7 +489,7 @@ impl DataflowExplorerView {
             let node_prefix_base = if is_last_df { "  " } else { "│ " };
 
             // Dataflow line
-            let status_indicator = utils::status_indicator(&dataflow.status);
+            let status_indicator = utils::status_indicator(dataflow.status);
             items.push(ListItem::new(Line::from(vec![
                 Span::styled(df_prefix, Style::default().fg(self.theme.colors.muted)),
                 Span::raw(" "),
@@ -400,7 +512,7 @@ impl DataflowExplorerView {
             for (node_idx, node) in dataflow.nodes.iter().enumerate() {
                 let is_last_node = node_idx == dataflow.nodes.len() - 1;
                 let node_prefix = if is_last_node { "└─" } else { "├─" };
-                let node_status = utils::status_indicator(&node.status);
+                let node_status = utils::status_indicator(node.status);
 
                 items.push(ListItem::new(Line::from(vec![
                     Span::styled(
@@ -449,7 +561,12 @@ impl DataflowExplorerView {
             Some(self.selected_index.min(all_nodes.len() - 1))
         };
 
-        let header_cells = ["Dataflow", "Node", "Status"]
+        let wide = self.breakpoint.is_wide();
+        let mut headers = vec!["Dataflow", "Node", "Status"];
+        if wide {
+            headers.extend(["Kind", "Inputs", "Outputs"]);
+        }
+        let header_cells = headers
             .iter()
             .map(|h| Cell::from(*h).style(self.theme.table_header_style()))
             .collect::<Vec<_>>();
@@ -463,28 +580,46 @@ impl DataflowExplorerView {
             .iter()
             .enumerate()
             .map(|(idx, (df_name, node))| {
-                let status_style = self.theme.status_style(&node.status);
-                let status_indicator = utils::status_indicator(&node.status);
+                let status_style = self.theme.status_style(node.status);
+                let status_indicator = utils::status_indicator(node.status);
                 let is_selected = selected_idx == Some(idx);
 
-                Row::new(vec![
+                let mut cells = vec![
                     Cell::from(df_name.clone()),
                     Cell::from(node.name.clone()),
                     Cell::from(Line::from(vec![
                         status_indicator,
                         Span::raw(" "),
-                        Span::styled(&node.status, status_style),
+                        Span::styled(node.status.as_str(), status_style),
                     ])),
-                ])
-                .style(self.theme.table_row_style(is_selected))
+                ];
+                if wide {
+                    cells.extend([
+                        Cell::from(node.kind.as_str()),
+                        Cell::from(node.inputs.join(", ")),
+                        Cell::from(node.outputs.join(", ")),
+                    ]);
+                }
+                Row::new(cells).style(self.theme.table_row_style(is_selected))
             })
             .collect::<Vec<_>>();
 
-        let widths = [
-            Constraint::Percentage(35),
-            Constraint::Percentage(40),
-            Constraint::Percentage(25),
-        ];
+        let widths = if wide {
+            vec![
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(17),
+                Constraint::Percentage(18),
+            ]
+        } else {
+            vec![
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+                Constraint::Percentage(25),
+            ]
+        };
 
         let table = Table::new(rows, widths)
             .header(header)
@@ -594,9 +729,12 @@ impl DataflowExplorerView {
                 ]),
                 Line::from(vec![
                     Span::styled("Status: ", Style::default().fg(self.theme.colors.muted)),
-                    utils::status_indicator(&dataflow.status),
+                    utils::status_indicator(dataflow.status),
                     Span::raw(" "),
-                    Span::styled(&dataflow.status, self.theme.status_style(&dataflow.status)),
+                    Span::styled(
+                        dataflow.status.as_str(),
+                        self.theme.status_style(dataflow.status),
+                    ),
                 ]),
                 Line::from(vec![
                     Span::styled("Nodes: ", Style::default().fg(self.theme.colors.muted)),
@@ -617,7 +755,7 @@ impl DataflowExplorerView {
             for node in &dataflow.nodes {
                 lines.push(Line::from(vec![
                     Span::raw("  "),
-                    utils::status_indicator(&node.status),
+                    utils::status_indicator(node.status),
                     Span::raw(" "),
                     Span::styled(&node.name, Style::default().fg(self.theme.colors.text)),
                 ]));
@@ -790,7 +928,7 @@ impl DataflowExplorerView {
                 app_state
                     .dataflows
                     .iter()
-                    .filter(|df| df.status == "running")
+                    .filter(|df| df.status.is_running())
                     .count()
             )),
             Line::from(format!(
@@ -798,7 +936,7 @@ impl DataflowExplorerView {
                 app_state
                     .dataflows
                     .iter()
-                    .filter(|df| df.status == "stopped")
+                    .filter(|df| df.status.is_inactive())
                     .count()
             )),
             Line::from(format!(
@@ -806,7 +944,7 @@ impl DataflowExplorerView {
                 app_state
                     .dataflows
                     .iter()
-                    .filter(|df| df.status == "failed")
+                    .filter(|df| df.status.is_failed())
                     .count()
             )),
             Line::from(""),
@@ -853,7 +991,7 @@ impl DataflowExplorerView {
 
     /// Render status bar with controls info
     fn render_status_bar(&self, f: &mut Frame, area: Rect) {
-        let controls = vec![
+        let controls = [
             ("↑↓", "Navigate"),
             ("i", "Inspector"),
             ("s", "Start/Stop"),
@@ -890,38 +1028,36 @@ impl DataflowExplorerView {
 
 impl View for DataflowExplorerView {
     fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        self.breakpoint = Breakpoint::of(area);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3), // Tab bar
-                Constraint::Min(10),   // Content area
+                Constraint::Min(0),    // Content area
                 Constraint::Length(2), // Status bar
             ])
             .split(area);
 
         // Render tab bar
+        self.tabs_area = chunks[0];
         self.render_tabs(f, chunks[0]);
 
-        // Split content area if inspection panel is shown
+        // Split content area if inspection panel is shown; wide frames
+        // leave more room for the extra table columns.
         let content_area =
             if self.show_inspection_panel && self.state.active_tab == ExplorerTab::Overview {
-                let split = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Percentage(60), // Main content
-                        Constraint::Percentage(40), // Inspection panel
-                    ])
-                    .split(chunks[1]);
-
-                // Render inspection panel
-                self.render_inspection_panel(f, split[1], app_state);
-
-                split[0]
+                let panel_percent = if self.breakpoint.is_wide() { 30 } else { 40 };
+                let (main, panel) = responsive::with_side_panel(chunks[1], panel_percent);
+                if let Some(panel) = panel {
+                    self.render_inspection_panel(f, panel, app_state);
+                }
+                main
             } else {
                 chunks[1]
             };
 
         // Render active tab content
+        self.content_area = content_area;
         match self.state.active_tab {
             ExplorerTab::Overview => self.render_overview_tab(f, content_area, app_state),
             ExplorerTab::Nodes => self.render_nodes_tab(f, content_area, app_state),
@@ -935,6 +1071,47 @@ impl View for DataflowExplorerView {
         self.render_status_bar(f, chunks[2]);
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent, app_state: &mut AppState) -> ViewAction {
+        let Some(gesture) = MouseGesture::from_event(&event) else {
+            return ViewAction::Unhandled;
+        };
+
+        if gesture == MouseGesture::Click && mouse::hits(self.tabs_area, &event) {
+            let tabs = ExplorerTab::all();
+            let widths = tabs
+                .iter()
+                .map(|tab| tab.shortcut().len() + 1 + tab.name().len());
+            let divider = TAB_DIVIDER.chars().count();
+            if let Some(index) = mouse::tab_at(self.tabs_area, widths, divider, &event) {
+                self.state.switch_tab(tabs[index]);
+            }
+            return ViewAction::None;
+        }
+
+        if !mouse::hits(self.content_area, &event) {
+            return ViewAction::Unhandled;
+        }
+        match gesture {
+            MouseGesture::ScrollUp => self.select_previous(app_state),
+            MouseGesture::ScrollDown => self.select_next(app_state),
+            MouseGesture::Click => {
+                // Only the tables map one row to one item; both have a header
+                // and a margin above the first row.
+                let tabular = match self.state.active_tab {
+                    ExplorerTab::Overview => self.state.view_mode == ViewMode::ListFlat,
+                    ExplorerTab::Nodes => true,
+                    _ => false,
+                };
+                if let Some(row) = mouse::row_at(self.content_area, 2, &event) {
+                    if tabular && row < self.get_item_count(app_state) {
+                        self.selected_index = row;
+                    }
+                }
+            }
+        }
+        ViewAction::None
+    }
+
     async fn handle_key(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<ViewAction> {
         match key.code {
             // Tab switching with number keys
@@ -1019,12 +1196,20 @@ impl View for DataflowExplorerView {
             // Lifecycle controls
             KeyCode::Char('s') => {
                 if let Some(dataflow) = self.get_selected_dataflow(app_state) {
-                    let action = if dataflow.status == "running" {
-                        format!("stop {}", dataflow.id)
+                    if dataflow.status.is_running() {
+                        Ok(ViewAction::ShowConfirmation {
+                            message: format!("Stop dataflow {}?", dataflow.name),
+                            confirm_action: format!("{STOP_PREFIX}{}", dataflow.id),
+                        })
                     } else {
-                        format!("start {}", dataflow.id)
-                    };
-                    Ok(ViewAction::ExecuteCommand(action))
+                        // Same as the dashboard: the descriptor path is not
+                        // known for a finished dataflow, so ask for it.
+                        Ok(ViewAction::ShowInput {
+                            id: format!("{RESTART_PREFIX}{}", dataflow.name),
+                            prompt: format!("Descriptor to restart {}", dataflow.name),
+                            default: None,
+                        })
+                    }
                 } else {
                     Ok(ViewAction::ShowStatus("No dataflow selected".to_string()))
                 }
@@ -1048,7 +1233,7 @@ impl View for DataflowExplorerView {
             KeyCode::Char('?') | KeyCode::F(1) => Ok(ViewAction::ShowHelp),
 
             // Back/Quit
-            KeyCode::Char('q') | KeyCode::Esc => Ok(ViewAction::PopView),
+            KeyCode::Esc => Ok(ViewAction::PopView),
 
             // Enter to inspect selected dataflow
             KeyCode::Enter => {
@@ -1066,16 +1251,16 @@ impl View for DataflowExplorerView {
                 }
             }
 
-            _ => Ok(ViewAction::None),
+            _ => Ok(ViewAction::Unhandled),
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
         if self.base.needs_refresh() {
             self.state.mark_refreshed();
             self.base.mark_updated();
         }
-        Ok(())
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -1091,7 +1276,7 @@ impl View for DataflowExplorerView {
             ("F5", "Refresh"),
             ("Enter", "Inspect selected dataflow"),
             ("?/F1", "Show help"),
-            ("q/Esc", "Go back"),
+            ("Esc", "Go back"),
         ]
     }
 
@@ -1099,6 +1284,10 @@ impl View for DataflowExplorerView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn auto_refresh(&self) -> Option<Duration> {
         self.base.auto_refresh_interval
     }
@@ -1116,6 +1305,34 @@ impl View for DataflowExplorerView {
         self.base.mark_updated();
         Ok(())
     }
+
+    fn on_modal_result(
+        &mut self,
+        id: &str,
+        result: ModalResult,
+        _app_state: &mut AppState,
+    ) -> ViewAction {
+        match result {
+            ModalResult::Confirmed => match id.strip_prefix(STOP_PREFIX) {
+                Some(dataflow_id) => {
+                    ViewAction::ControlDataflow(DataflowCommand::Stop(dataflow_id.to_string()))
+                }
+                None => ViewAction::None,
+            },
+            ModalResult::Input(path) if !path.trim().is_empty() => {
+                match id.strip_prefix(RESTART_PREFIX) {
+                    Some(name) => ViewAction::ExecuteCommand(shell_words::join([
+                        "start",
+                        path.trim(),
+                        "--name",
+                        name,
+                    ])),
+                    None => ViewAction::None,
+                }
+            }
+            _ => ViewAction::None,
+        }
+    }
 }
 
 fn select_preferred_node(
@@ -1123,9 +1340,6 @@ fn select_preferred_node(
 ) -> Option<&crate::tui::app::NodeInfo> {
     nodes
         .iter()
-        .find(|node| {
-            let status = node.status.to_ascii_lowercase();
-            status == "running" || status == "active"
-        })
+        .find(|node| node.status.is_running())
         .or_else(|| nodes.first())
 }
@@ -1,8 +1,10 @@
 /// Dataflow Explorer types for Issue #25
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
 /// Tab selection in the dataflow explorer
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ExplorerTab {
     Overview,
     Nodes,
@@ -70,7 +72,8 @@ impl ExplorerTab {
 }
 
 /// View mode for dataflow list display
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ViewMode {
     /// List view with grouping by status
     ListGrouped,
@@ -7,6 +7,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
+use tui_interface::DataflowStatus;
 
 pub struct DataflowManagerView {
     base: BaseView,
@@ -67,7 +68,7 @@ impl View for DataflowManagerView {
                 app_state
                     .dataflows
                     .iter()
-                    .filter(|df| df.status.eq_ignore_ascii_case("running"))
+                    .filter(|df| df.status.is_running())
                     .count()
                     .to_string(),
             ),
@@ -80,7 +81,7 @@ impl View for DataflowManagerView {
                 app_state
                     .dataflows
                     .iter()
-                    .filter(|df| df.status.eq_ignore_ascii_case("failed"))
+                    .filter(|df| df.status.is_failed())
                     .count()
                     .to_string(),
             ),
@@ -92,10 +93,10 @@ impl View for DataflowManagerView {
             .dataflows
             .iter()
             .map(|df| {
-                let status_style = match df.status.to_lowercase().as_str() {
-                    "running" => Style::default().fg(Color::Green),
-                    "finished" => Style::default().fg(Color::Blue),
-                    "failed" => Style::default().fg(Color::Red),
+                let status_style = match df.status {
+                    DataflowStatus::Running => Style::default().fg(Color::Green),
+                    DataflowStatus::Finished => Style::default().fg(Color::Blue),
+                    DataflowStatus::Failed => Style::default().fg(Color::Red),
                     _ => Style::default().fg(self.theme.colors.muted),
                 };
 
@@ -115,7 +116,7 @@ impl View for DataflowManagerView {
                     ]),
                     Line::from(vec![
                         Span::styled("Status: ", Style::default().fg(self.theme.colors.muted)),
-                        Span::styled(df.status.clone(), status_style),
+                        Span::styled(df.status.as_str(), status_style),
                     ]),
                 ])
             })
@@ -137,11 +138,11 @@ impl View for DataflowManagerView {
         _key: KeyEvent,
         _app_state: &mut AppState,
     ) -> Result<ViewAction> {
-        Ok(ViewAction::None)
+        Ok(ViewAction::Unhandled)
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -151,4 +152,8 @@ impl View for DataflowManagerView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
@@ -524,11 +524,11 @@ impl View for DebugSessionView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
         // Auto-refresh logic could go here
         // For mock implementation, we just mark as refreshed
         self.state.mark_refreshed();
-        Ok(())
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -553,6 +553,10 @@ impl View for DebugSessionView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn on_focus(&mut self) {
         self.base.set_focused(true);
     }
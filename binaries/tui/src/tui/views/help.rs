@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -6,97 +6,117 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
+use std::sync::Arc;
+
 use super::{BaseView, View, ViewAction};
-use crate::tui::{Frame, Result, app::AppState, theme::ThemeConfig};
+use crate::tui::{
+    Frame, Result,
+    app::AppState,
+    keymap::{KeyContext, Keymap},
+    mouse::{MouseGesture, SCROLL_STEP},
+    theme::ThemeConfig,
+};
+
+type HelpSection = (String, Vec<(String, String)>);
 
 pub struct HelpView {
     base: BaseView,
     theme: ThemeConfig,
+    keymap: Arc<Keymap>,
     scroll_offset: usize,
 }
 
 impl HelpView {
-    pub fn new(theme: &ThemeConfig) -> Self {
+    pub fn new(theme: &ThemeConfig, keymap: Arc<Keymap>) -> Self {
         Self {
             base: BaseView::new("Help".to_string()),
             theme: theme.clone(),
+            keymap,
             scroll_offset: 0,
         }
     }
 
-    fn get_help_sections(&self) -> Vec<(&str, Vec<(&str, &str)>)> {
-        vec![
-            (
-                "Global Shortcuts",
-                vec![
-                    ("q", "Quit application"),
-                    (":", "Enter command mode"),
-                    ("Esc", "Back/Cancel"),
-                    ("F1", "Show this help"),
-                    ("Ctrl+C", "Force quit"),
-                    ("Ctrl+L", "Refresh current view"),
-                ],
-            ),
-            (
-                "Navigation",
-                vec![
-                    ("1", "Dashboard view"),
-                    ("2", "Dataflow manager"),
-                    ("3", "System monitor"),
-                    ("4", "Log viewer"),
-                    ("5", "Settings"),
-                    ("Tab", "Next widget"),
-                    ("Shift+Tab", "Previous widget"),
-                ],
-            ),
-            (
-                "Dashboard",
-                vec![
-                    ("↑/k", "Move up in list"),
-                    ("↓/j", "Move down in list"),
-                    ("Enter", "Inspect selected item"),
-                    ("Space", "Toggle dataflow state"),
-                    ("l", "View logs"),
-                    ("r", "Refresh data"),
-                    ("s", "Toggle system info"),
-                    ("n", "New dataflow"),
-                ],
-            ),
+    /// Key binding sections come from the active keymap, so remapped keys
+    /// show up here as the user configured them.
+    fn get_help_sections(&self) -> Vec<HelpSection> {
+        let owned = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(key, description)| (key.to_string(), description.to_string()))
+                .collect()
+        };
+
+        let mut global = self.bindings(KeyContext::Global);
+        global.push(("Ctrl+C".to_string(), "Force quit".to_string()));
+        let mut sections = vec![("Global Shortcuts".to_string(), global)];
+
+        for context in KeyContext::ALL {
+            if matches!(context, KeyContext::Global | KeyContext::Help) {
+                continue;
+            }
+            sections.push((context.title().to_string(), self.bindings(context)));
+        }
+
+        let quit = self.key_label(KeyContext::Global, "quit");
+        let back = self.key_label(KeyContext::Global, "back");
+        let command = self.key_label(KeyContext::Global, "command");
+        sections.extend([
             (
-                "Command Mode",
-                vec![
+                "Command Mode".to_string(),
+                owned(&[
                     ("Enter", "Execute command"),
                     ("Esc", "Exit command mode"),
                     ("↑/↓", "Command history"),
                     ("Tab", "Auto-complete"),
                     ("Ctrl+A", "Move to beginning"),
                     ("Ctrl+E", "Move to end"),
-                ],
+                ]),
             ),
             (
-                "Common Commands",
-                vec![
-                    ("ps", "List dataflows"),
-                    ("start <file>", "Start dataflow"),
-                    ("stop <id>", "Stop dataflow"),
-                    ("logs <target>", "View logs"),
-                    ("inspect <target>", "Inspect resource"),
-                    ("monitor", "System monitoring"),
-                    ("ui <view>", "Switch TUI view"),
-                ],
+                "Common Commands".to_string(),
+                owned(&[
+                    ("start <file> [--name <n>]", "Start dataflow"),
+                    ("stop <dataflow>", "Stop dataflow"),
+                    ("logs <dataflow> [node]", "View logs"),
+                    ("inspect <dataflow> <node>", "Inspect node"),
+                    ("view <name>", "Switch TUI view"),
+                    ("theme <name>", "Switch theme"),
+                    ("set <key> <value>", "Save preference"),
+                    ("split <right|down> [view]", "Split pane"),
+                    ("tab new [name]", "Open tab"),
+                    ("tab rename <name>", "Rename tab"),
+                    ("quit", "Quit"),
+                ]),
             ),
             (
-                "Tips",
-                vec![
-                    ("", "• Use : to enter CLI commands within TUI"),
-                    ("", "• Press q to quit from any view"),
-                    ("", "• Use Esc to go back in view stack"),
+                "Tips".to_string(),
+                owned(&[
+                    (
+                        "",
+                        &format!("• Use {command} to enter CLI commands within TUI"),
+                    ),
+                    ("", &format!("• Press {quit} to quit from any view")),
+                    ("", &format!("• Use {back} to go back in view stack")),
                     ("", "• Tab completion works in command mode"),
-                    ("", "• Arrow keys navigate in most views"),
+                    ("", "• Keys can be remapped in keymap.toml"),
                     ("", "• Many views auto-refresh data"),
-                ],
+                ]),
             ),
-        ]
+        ]);
+        sections
+    }
+
+    fn bindings(&self, context: KeyContext) -> Vec<(String, String)> {
+        self.keymap
+            .bindings(context)
+            .map(|binding| (binding.keys_label(), binding.description.to_string()))
+            .collect()
+    }
+
+    fn key_label(&self, context: KeyContext, action: &str) -> String {
+        self.keymap
+            .keys_label(context, action)
+            .unwrap_or_else(|| "(unbound)".to_string())
     }
 }
 
@@ -136,7 +156,11 @@ impl View for HelpView {
         self.render_help_column(f, content_chunks[1], right_sections);
 
         // Footer
-        let footer_text = "Press Esc to go back, ↑↓ to scroll, q to quit";
+        let footer_text = format!(
+            "Press {} to go back, ↑↓ to scroll, {} to quit",
+            self.key_label(KeyContext::Help, "back"),
+            self.key_label(KeyContext::Global, "quit"),
+        );
         let footer = Paragraph::new(footer_text)
             .style(Style::default().fg(self.theme.colors.muted))
             .alignment(Alignment::Center)
@@ -144,6 +168,20 @@ impl View for HelpView {
         f.render_widget(footer, chunks[2]);
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent, _app_state: &mut AppState) -> ViewAction {
+        match MouseGesture::from_event(&event) {
+            Some(MouseGesture::ScrollUp) => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(SCROLL_STEP);
+                ViewAction::None
+            }
+            Some(MouseGesture::ScrollDown) => {
+                self.scroll_offset += SCROLL_STEP;
+                ViewAction::None
+            }
+            _ => ViewAction::Unhandled,
+        }
+    }
+
     async fn handle_key(&mut self, key: KeyEvent, _app_state: &mut AppState) -> Result<ViewAction> {
         match key.code {
             KeyCode::Esc => Ok(ViewAction::PopView),
@@ -169,12 +207,12 @@ impl View for HelpView {
                 self.scroll_offset = 0;
                 Ok(ViewAction::None)
             }
-            _ => Ok(ViewAction::None),
+            _ => Ok(ViewAction::Unhandled),
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -191,15 +229,14 @@ impl View for HelpView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 impl HelpView {
-    fn render_help_column(
-        &self,
-        f: &mut Frame,
-        area: Rect,
-        sections: &[(&str, Vec<(&str, &str)>)],
-    ) {
+    fn render_help_column(&self, f: &mut Frame, area: Rect, sections: &[HelpSection]) {
         let mut items = Vec::new();
 
         for (section_title, entries) in sections {
@@ -241,6 +278,7 @@ impl HelpView {
             items.push(ListItem::new(Line::from("")));
         }
 
+        let items: Vec<_> = items.into_iter().skip(self.scroll_offset).collect();
         let help_list = List::new(items)
             .block(Block::default().borders(Borders::ALL))
             .style(Style::default().fg(self.theme.colors.text));
@@ -319,9 +319,9 @@ impl View for HelpBrowserView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
         self.state.mark_refreshed();
-        Ok(())
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -344,6 +344,10 @@ impl View for HelpBrowserView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn on_focus(&mut self) {
         self.base.set_focused(true);
     }
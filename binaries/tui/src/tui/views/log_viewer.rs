@@ -1,13 +1,13 @@
 /// Interactive Log Viewer implementation (Issue #28 - Phase 1)
 use super::{BaseView, View, ViewAction};
-#[cfg(feature = "protocol")]
-use crate::tui::bridge::spawn_protocol_log_stream;
-use crate::tui::{Result, app::AppState, theme::ThemeConfig};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-#[cfg(feature = "protocol")]
-use dora_protocol::{LogEvent as ProtocolLogEvent, LogLevel as ProtocolLogLevel};
-#[cfg(feature = "protocol")]
-use dora_protocol_client::ProtocolClients;
+use crate::tui::{
+    Result,
+    app::AppState,
+    connection::Link,
+    mouse::{self, MouseGesture},
+    theme::ThemeConfig,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,20 +15,20 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
-#[cfg(feature = "protocol")]
-use std::sync::mpsc::TryRecvError;
-#[cfg(feature = "protocol")]
-use std::sync::{Arc, mpsc::Receiver};
-use std::time::Duration;
-#[cfg(feature = "protocol")]
-use std::time::Instant;
-#[cfg(feature = "protocol")]
-use uuid::Uuid;
+use std::sync::{Arc, mpsc::TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::oneshot;
+use tui_interface::{LogQuery, LogRecord, LogService, LogSubscription};
 
 pub use super::log_viewer_types::*;
 
-#[cfg(feature = "protocol")]
 const STREAM_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const HISTORY_LIMIT: usize = 500;
+
+/// The history and live subscription fetched by a connection attempt. The
+/// stream is still followed when the history cannot be read.
+type Connection =
+    std::result::Result<(std::result::Result<Vec<LogRecord>, String>, LogSubscription), String>;
 
 /// Interactive Log Viewer View
 pub struct LogViewerView {
@@ -38,34 +38,41 @@ pub struct LogViewerView {
     mock_log_counter: usize,
     search_mode: bool,
     search_input: String,
-    #[cfg(feature = "protocol")]
     target: String,
-    #[cfg(feature = "protocol")]
-    protocol_clients: Option<Arc<ProtocolClients>>,
-    #[cfg(feature = "protocol")]
-    log_stream: Option<Receiver<ProtocolLogEvent>>,
-    #[cfg(feature = "protocol")]
+    /// Only show logs from this node
+    node: Option<String>,
+    log_service: Option<Arc<dyn LogService>>,
+    log_stream: Option<LogSubscription>,
+    /// Connection attempt running on the blocking pool
+    connecting: Option<oneshot::Receiver<Connection>>,
+    /// Newest history record; stream records up to it are already shown
+    history_until: Option<SystemTime>,
     stream_failed: bool,
-    #[cfg(feature = "protocol")]
     next_log_id: usize,
-    #[cfg(feature = "protocol")]
     stream_status: Option<String>,
-    #[cfg(feature = "protocol")]
     last_stream_attempt: Option<Instant>,
+    /// Where the log list was drawn, for mouse hit testing
+    list_area: Rect,
 }
 
 impl LogViewerView {
-    /// Create a new log viewer view
+    /// Create a new log viewer view. `target` is a dataflow, or
+    /// `dataflow/node` to follow a single node. Without a log service the
+    /// viewer shows generated demo logs.
     pub fn new(
         target: &str,
         theme: &ThemeConfig,
-        #[cfg(feature = "protocol")] protocol_clients: Option<Arc<ProtocolClients>>,
+        log_service: Option<Arc<dyn LogService>>,
     ) -> Self {
         let title = if target.is_empty() {
             "Log Viewer".to_string()
         } else {
             format!("Logs: {target}")
         };
+        let (dataflow, node) = match target.split_once('/') {
+            Some((dataflow, node)) => (dataflow, Some(node.to_string())),
+            None => (target, None),
+        };
 
         Self {
             base: BaseView::new(title).with_auto_refresh(Duration::from_secs(1)),
@@ -74,20 +81,17 @@ impl LogViewerView {
             mock_log_counter: 0,
             search_mode: false,
             search_input: String::new(),
-            #[cfg(feature = "protocol")]
-            target: target.to_string(),
-            #[cfg(feature = "protocol")]
-            protocol_clients,
-            #[cfg(feature = "protocol")]
+            target: dataflow.to_string(),
+            node,
+            log_service,
             log_stream: None,
-            #[cfg(feature = "protocol")]
+            connecting: None,
+            history_until: None,
             stream_failed: false,
-            #[cfg(feature = "protocol")]
             next_log_id: 0,
-            #[cfg(feature = "protocol")]
             stream_status: None,
-            #[cfg(feature = "protocol")]
             last_stream_attempt: None,
+            list_area: Rect::default(),
         }
     }
 
@@ -151,7 +155,6 @@ impl LogViewerView {
         }
     }
 
-    #[cfg(feature = "protocol")]
     fn ensure_target_selection(&mut self, app_state: &AppState) {
         if (self.target.is_empty() || self.target == "system") && !app_state.dataflows.is_empty() {
             if let Some(df) = app_state.dataflows.first() {
@@ -160,7 +163,6 @@ impl LogViewerView {
         }
     }
 
-    #[cfg(feature = "protocol")]
     fn refresh_title(&mut self, app_state: &AppState) {
         let display = if let Some(df) = app_state
             .dataflows
@@ -181,20 +183,33 @@ impl LogViewerView {
         };
     }
 
-    #[cfg(feature = "protocol")]
-    fn maybe_connect_stream(&mut self, app_state: &AppState) {
-        let Some(clients) = self.protocol_clients.as_ref() else {
-            return;
-        };
+    /// Start connecting to the log stream if it is time to; returns the
+    /// outcome of an attempt that finished since the last call.
+    fn maybe_connect_stream(
+        &mut self,
+        app_state: &AppState,
+    ) -> Option<std::result::Result<(), String>> {
+        let service = self.log_service.clone()?;
 
         if self.log_stream.is_some() {
-            return;
+            return None;
+        }
+        if let Some(connecting) = &mut self.connecting {
+            let connection = match connecting.try_recv() {
+                Err(oneshot::error::TryRecvError::Empty) => return None,
+                Ok(connection) => connection,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    Err("log connection task failed".to_string())
+                }
+            };
+            self.connecting = None;
+            return Some(self.finish_connecting(connection));
         }
 
         let now = Instant::now();
         if let Some(last_attempt) = self.last_stream_attempt {
             if now.duration_since(last_attempt) < STREAM_RETRY_INTERVAL {
-                return;
+                return None;
             }
         }
         self.last_stream_attempt = Some(now);
@@ -203,10 +218,10 @@ impl LogViewerView {
             self.stream_status =
                 Some("No running dataflows detected; start one to stream logs".to_string());
             self.stream_failed = false;
-            return;
+            return None;
         }
 
-        let Some(uuid) = self.resolve_dataflow_id(app_state) else {
+        let Some(dataflow) = self.resolve_dataflow_id(app_state) else {
             self.stream_status = Some(format!(
                 "Unable to resolve dataflow `{}` for log streaming",
                 if self.target.is_empty() {
@@ -216,32 +231,87 @@ impl LogViewerView {
                 }
             ));
             self.stream_failed = false;
-            return;
+            return None;
         };
 
-        match spawn_protocol_log_stream(Arc::clone(clients), uuid) {
-            Some(receiver) => {
+        let mut query = LogQuery::dataflow(dataflow);
+        if let Some(node) = &self.node {
+            query = query.with_node(node.clone());
+        }
+        let (tx, rx) = oneshot::channel();
+        tokio::task::spawn_blocking(move || {
+            // History first; the stream may repeat what it already holds,
+            // which is skipped by timestamp.
+            let history = service
+                .history(&query, HISTORY_LIMIT)
+                .map_err(|err| err.to_string());
+            let connection = service
+                .subscribe(&query)
+                .map(|receiver| (history, receiver))
+                .map_err(|err| err.to_string());
+            let _ = tx.send(connection);
+        });
+        self.connecting = Some(rx);
+        self.stream_status = Some("Connecting to live log stream".to_string());
+        None
+    }
+
+    fn finish_connecting(&mut self, connection: Connection) -> std::result::Result<(), String> {
+        match connection {
+            Ok((history, receiver)) => {
                 self.log_stream = Some(receiver);
                 self.stream_failed = false;
-                self.stream_status = Some("Connected to live log stream".to_string());
                 self.next_log_id = 0;
+                self.state.clear();
+                let history = match history {
+                    Ok(history) => {
+                        self.stream_status = Some("Connected to live log stream".to_string());
+                        history
+                    }
+                    Err(err) => {
+                        self.stream_status = Some(format!(
+                            "Connected to live log stream; history unavailable ({err})"
+                        ));
+                        Vec::new()
+                    }
+                };
+                self.history_until = history.iter().map(|record| record.timestamp).max();
+                for record in history {
+                    self.push_record(record);
+                }
+                Ok(())
             }
-            None => {
+            Err(err) => {
                 self.stream_failed = true;
-                self.stream_status =
-                    Some("Failed to open log stream; retrying shortly".to_string());
+                self.stream_status = Some(format!(
+                    "Failed to open log stream ({err}); retrying shortly"
+                ));
+                Err(err)
             }
         }
     }
 
-    #[cfg(feature = "protocol")]
-    fn drain_protocol_stream(&mut self) -> bool {
+    /// Let a connection attempt in flight finish, as the next updates would.
+    #[cfg(test)]
+    pub async fn wait_for_connection(&mut self, app_state: &mut AppState) -> Result<()> {
+        while self.connecting.is_some() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            self.update(app_state).await?;
+        }
+        Ok(())
+    }
+
+    fn drain_log_stream(&mut self) -> bool {
         let mut disconnected = false;
         let mut events = Vec::new();
 
         if let Some(stream) = self.log_stream.as_ref() {
             loop {
                 match stream.try_recv() {
+                    Ok(event)
+                        if self
+                            .history_until
+                            .is_some_and(|until| event.timestamp <= until) => {}
                     Ok(event) => events.push(event),
                     Err(TryRecvError::Empty) => break,
                     Err(TryRecvError::Disconnected) => {
@@ -254,8 +324,8 @@ impl LogViewerView {
 
         let has_events = !events.is_empty();
         if has_events {
-            for event in events {
-                self.push_protocol_log(event);
+            for record in events {
+                self.push_record(record);
             }
             self.stream_status = None;
         }
@@ -270,33 +340,29 @@ impl LogViewerView {
         has_events
     }
 
-    #[cfg(feature = "protocol")]
-    fn resolve_dataflow_id(&self, app_state: &AppState) -> Option<Uuid> {
-        if let Ok(uuid) = Uuid::parse_str(&self.target) {
-            return Some(uuid);
-        }
-
-        if !self.target.is_empty() {
+    fn resolve_dataflow_id(&self, app_state: &AppState) -> Option<String> {
+        if !self.target.is_empty() && self.target != "system" {
             if let Some(df) = app_state
                 .dataflows
                 .iter()
                 .find(|df| df.id == self.target || df.name == self.target)
             {
-                return Uuid::parse_str(&df.id).ok();
+                return Some(df.id.clone());
             }
+            return Some(self.target.clone());
         }
 
-        app_state
-            .dataflows
-            .first()
-            .and_then(|df| Uuid::parse_str(&df.id).ok())
+        app_state.dataflows.first().map(|df| df.id.clone())
     }
 
-    #[cfg(feature = "protocol")]
-    fn push_protocol_log(&mut self, event: ProtocolLogEvent) {
-        let level = map_protocol_level(event.level);
-        let source = event.node.unwrap_or_else(|| "system".to_string());
-        let entry = LogEntry::new(self.next_log_id, level, source, event.line);
+    fn push_record(&mut self, record: LogRecord) {
+        let source = record.node.unwrap_or_else(|| "system".to_string());
+        let entry = LogEntry::new(
+            self.next_log_id,
+            record.level.into(),
+            source,
+            record.message,
+        );
         self.next_log_id = self.next_log_id.wrapping_add(1);
         self.state.add_log(entry);
     }
@@ -338,7 +404,7 @@ impl LogViewerView {
     }
 
     /// Render a single log entry with syntax highlighting
-    fn render_log_entry(&self, entry: &LogEntry, is_selected: bool) -> ListItem {
+    fn render_log_entry(&self, entry: &LogEntry, is_selected: bool) -> ListItem<'_> {
         let timestamp = entry.timestamp_str();
         let level_str = entry.level.short_name();
         let source = &entry.source;
@@ -430,7 +496,6 @@ impl LogViewerView {
     fn render_stats(&self, f: &mut Frame, area: Rect) {
         let stats = self.state.stats();
 
-        #[allow(unused_mut)]
         let mut content = vec![Line::from(vec![
             Span::styled("Total: ", Style::default().fg(Color::Gray)),
             Span::styled(
@@ -470,7 +535,6 @@ impl LogViewerView {
             ),
         ])];
 
-        #[cfg(feature = "protocol")]
         if let Some(status) = &self.stream_status {
             content.push(Line::from(vec![Span::styled(
                 status,
@@ -578,6 +642,7 @@ impl View for LogViewerView {
             .split(area);
 
         self.render_filter_panel(f, chunks[0]);
+        self.list_area = chunks[1];
         self.render_log_list(f, chunks[1]);
         self.render_log_details(f, chunks[2]);
         self.render_stats(f, chunks[3]);
@@ -588,6 +653,37 @@ impl View for LogViewerView {
         }
     }
 
+    fn handle_mouse(&mut self, event: MouseEvent, _app_state: &mut AppState) -> ViewAction {
+        if self.search_mode || !mouse::hits(self.list_area, &event) {
+            return ViewAction::Unhandled;
+        }
+
+        match MouseGesture::from_event(&event) {
+            Some(MouseGesture::Click) => {
+                // Selecting a line pauses the stream, like moving up does.
+                let count = self.state.get_filtered_logs().len();
+                if let Some(row) = mouse::row_at(self.list_area, 0, &event) {
+                    if row < count {
+                        self.state.selected_index = row;
+                        self.state.paused = true;
+                    }
+                }
+            }
+            Some(MouseGesture::ScrollUp) => {
+                for _ in 0..mouse::SCROLL_STEP {
+                    self.state.move_up();
+                }
+            }
+            Some(MouseGesture::ScrollDown) => {
+                for _ in 0..mouse::SCROLL_STEP {
+                    self.state.move_down();
+                }
+            }
+            None => return ViewAction::Unhandled,
+        }
+        ViewAction::None
+    }
+
     async fn handle_key(&mut self, key: KeyEvent, _app_state: &mut AppState) -> Result<ViewAction> {
         // Handle search mode separately
         if self.search_mode {
@@ -617,7 +713,7 @@ impl View for LogViewerView {
 
         // Normal mode key handling
         match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => Ok(ViewAction::PopView),
+            KeyCode::Esc => Ok(ViewAction::PopView),
 
             // Navigation
             KeyCode::Up | KeyCode::Char('k') => {
@@ -708,50 +804,58 @@ impl View for LogViewerView {
                 Ok(ViewAction::None)
             }
 
-            _ => Ok(ViewAction::None),
+            _ => Ok(ViewAction::Unhandled),
         }
     }
 
-    async fn update(&mut self, app_state: &mut AppState) -> Result<()> {
-        #[cfg(feature = "protocol")]
-        {
-            if self.protocol_clients.is_some() {
-                self.ensure_target_selection(app_state);
-                self.refresh_title(app_state);
-                self.maybe_connect_stream(app_state);
-
-                let received = self.drain_protocol_stream();
-                if received || self.log_stream.is_some() {
-                    self.state.mark_refreshed();
-                    return Ok(());
+    async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
+        if self.log_service.is_some() {
+            let shown = (self.base.title.clone(), self.stream_status.clone());
+            self.ensure_target_selection(app_state);
+            self.refresh_title(app_state);
+            let was_streaming = self.log_stream.is_some();
+            let attempt = self.maybe_connect_stream(app_state);
+            let received = self.drain_log_stream();
+            let changed = received
+                || attempt.is_some()
+                || shown != (self.base.title.clone(), self.stream_status.clone());
+
+            let connection = &mut app_state.connection;
+            match attempt {
+                Some(Err(err)) => {
+                    connection.record_failure(Link::LogStream, err);
                 }
-
-                if self.stream_failed {
-                    self.state.mark_refreshed();
-                    return Ok(());
+                _ if was_streaming && self.log_stream.is_none() => {
+                    connection.record_failure(Link::LogStream, "log stream disconnected");
                 }
-
-                // Waiting for stream to connect; keep UI alive without generating mock logs.
-                self.state.mark_refreshed();
-                return Ok(());
+                Some(Ok(())) => {
+                    connection.record_success(Link::LogStream, None, Instant::now());
+                }
+                None if received => {
+                    connection.record_success(Link::LogStream, None, Instant::now());
+                }
+                None => {}
             }
-        }
 
-        #[cfg(not(feature = "protocol"))]
-        let _ = app_state;
+            // Never fall back to generated logs while a real backend is
+            // configured, even if the stream is still connecting.
+            self.state.mark_refreshed();
+            return Ok(changed);
+        }
 
-        if !self.state.paused {
+        let paused = self.state.paused;
+        if !paused {
             let new_logs = (self.mock_log_counter % 3) + 1;
             self.generate_mock_logs(new_logs);
         }
 
         self.state.mark_refreshed();
-        Ok(())
+        Ok(!paused)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
         vec![
-            ("q/Esc", "Back"),
+            ("Esc", "Back"),
             ("↑↓/k/j", "Navigate"),
             ("PgUp/PgDn", "Page up/down"),
             ("Home/End", "Start/End"),
@@ -760,24 +864,21 @@ impl View for LogViewerView {
             ("Ctrl+n", "Clear search"),
             ("1-5", "Toggle level filter"),
             ("a", "Toggle all filters"),
-            ("Ctrl+c", "Clear logs"),
+            ("Ctrl+l", "Clear logs"),
             ("Ctrl+r", "Manual refresh"),
         ]
     }
 
+    fn captures_text(&self) -> bool {
+        self.search_mode
+    }
+
     fn title(&self) -> &str {
         &self.base.title
     }
-}
 
-#[cfg(feature = "protocol")]
-fn map_protocol_level(level: ProtocolLogLevel) -> LogLevel {
-    match level {
-        ProtocolLogLevel::Error => LogLevel::Error,
-        ProtocolLogLevel::Warn => LogLevel::Warn,
-        ProtocolLogLevel::Info => LogLevel::Info,
-        ProtocolLogLevel::Debug => LogLevel::Debug,
-        ProtocolLogLevel::Trace => LogLevel::Trace,
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
     }
 }
 
@@ -788,10 +889,7 @@ mod tests {
     #[test]
     fn test_log_viewer_creation() {
         let theme = ThemeConfig::default();
-        #[cfg(feature = "protocol")]
         let viewer = LogViewerView::new("", &theme, None);
-        #[cfg(not(feature = "protocol"))]
-        let viewer = LogViewerView::new("", &theme);
 
         assert_eq!(viewer.state.buffer_count(), 0);
         assert!(!viewer.search_mode);
@@ -801,10 +899,7 @@ mod tests {
     #[test]
     fn test_mock_log_generation() {
         let theme = ThemeConfig::default();
-        #[cfg(feature = "protocol")]
         let mut viewer = LogViewerView::new("", &theme, None);
-        #[cfg(not(feature = "protocol"))]
-        let mut viewer = LogViewerView::new("", &theme);
 
         viewer.generate_mock_logs(10);
         assert_eq!(viewer.state.buffer_count(), 10);
@@ -814,11 +909,100 @@ mod tests {
     #[test]
     fn test_view_title() {
         let theme = ThemeConfig::default();
-        #[cfg(feature = "protocol")]
         let viewer = LogViewerView::new("", &theme, None);
-        #[cfg(not(feature = "protocol"))]
-        let viewer = LogViewerView::new("", &theme);
 
         assert_eq!(viewer.title(), "Log Viewer");
     }
+
+    #[test]
+    fn test_streams_records_from_log_service() {
+        use std::time::SystemTime;
+        use tui_interface::{DataflowSummary, MockLogService};
+
+        let service = Arc::new(MockLogService::new());
+        let ready = SystemTime::now();
+        service.set_history(Ok(vec![LogRecord {
+            timestamp: ready,
+            level: tui_interface::LogLevel::Info,
+            node: Some("camera".to_string()),
+            message: "camera ready".to_string(),
+        }]));
+
+        let theme = ThemeConfig::default();
+        let mut viewer = LogViewerView::new("df-1", &theme, Some(service.clone()));
+        let mut app_state = AppState::default();
+        app_state.dataflows.push(DataflowSummary {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            ..Default::default()
+        });
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            viewer.update(&mut app_state).await.unwrap();
+            viewer.wait_for_connection(&mut app_state).await.unwrap();
+        });
+        assert_eq!(
+            service.subscriptions.lock().unwrap()[0],
+            LogQuery::dataflow("df-1")
+        );
+        assert_eq!(viewer.state.buffer_count(), 1);
+
+        // Records the history already covered are not shown twice.
+        service.emit(LogRecord {
+            timestamp: ready,
+            level: tui_interface::LogLevel::Info,
+            node: Some("camera".to_string()),
+            message: "camera ready".to_string(),
+        });
+        service.emit(LogRecord {
+            timestamp: SystemTime::now(),
+            level: tui_interface::LogLevel::Error,
+            node: Some("planner".to_string()),
+            message: "no path".to_string(),
+        });
+        rt.block_on(viewer.update(&mut app_state)).unwrap();
+
+        let logs = viewer.state.get_filtered_logs();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1].level, LogLevel::Error);
+        assert_eq!(logs[1].source, "planner");
+        assert_eq!(viewer.mock_log_counter, 0);
+    }
+
+    #[test]
+    fn test_history_errors_are_shown() {
+        use tui_interface::{DataflowSummary, InterfaceError, MockLogService};
+
+        let service = Arc::new(MockLogService::new());
+        service.set_history(Err(InterfaceError::from("permission denied")));
+
+        let theme = ThemeConfig::default();
+        let mut viewer = LogViewerView::new("df-1", &theme, Some(service));
+        let mut app_state = AppState::default();
+        app_state.dataflows.push(DataflowSummary {
+            id: "df-1".to_string(),
+            name: "demo".to_string(),
+            ..Default::default()
+        });
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            viewer.update(&mut app_state).await.unwrap();
+            viewer.wait_for_connection(&mut app_state).await.unwrap();
+        });
+        assert!(viewer.log_stream.is_some());
+        assert!(
+            viewer
+                .stream_status
+                .as_deref()
+                .is_some_and(|status| status.contains("permission denied"))
+        );
+    }
 }
@@ -1,9 +1,11 @@
 /// Types and state management for Interactive Log Viewer (Issue #28 - Phase 1)
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::time::Instant;
 
 /// Log level enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LogLevel {
     Error,
     Warn,
@@ -58,6 +60,18 @@ impl LogLevel {
     }
 }
 
+impl From<tui_interface::LogLevel> for LogLevel {
+    fn from(level: tui_interface::LogLevel) -> Self {
+        match level {
+            tui_interface::LogLevel::Error => LogLevel::Error,
+            tui_interface::LogLevel::Warn => LogLevel::Warn,
+            tui_interface::LogLevel::Info => LogLevel::Info,
+            tui_interface::LogLevel::Debug => LogLevel::Debug,
+            tui_interface::LogLevel::Trace => LogLevel::Trace,
+        }
+    }
+}
+
 /// Individual log entry
 #[derive(Debug, Clone, PartialEq)]
 pub struct LogEntry {
@@ -111,7 +125,7 @@ impl LogEntry {
 }
 
 /// Log filtering configuration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LogFilter {
     pub enabled_levels: Vec<LogLevel>,
     pub search_query: String,
@@ -1,10 +1,12 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{Frame, layout::Rect};
 use std::time::Duration;
 
 use crate::tui::{
     Result,
     app::{AppState, ViewType},
+    modal::ModalResult,
+    theme::ThemeConfig,
 };
 
 pub mod analysis_tools;
@@ -30,6 +32,7 @@ pub mod log_viewer;
 pub mod log_viewer_types;
 pub mod node_inspector;
 pub mod node_inspector_types;
+pub mod notifications;
 pub mod perf_optimization;
 pub mod perf_optimization_types;
 pub mod performance_analyzer;
@@ -61,6 +64,7 @@ pub use log_viewer::LogViewerView;
 pub use log_viewer_types::*;
 pub use node_inspector::NodeInspectorView;
 pub use node_inspector_types::*;
+pub use notifications::NotificationsView;
 pub use perf_optimization::PerfOptimizationView;
 pub use perf_optimization_types::TrendDirection as PerfTrendDirection;
 pub use perf_optimization_types::{
@@ -87,11 +91,12 @@ pub trait View {
         _app_state: &mut AppState,
     ) -> impl std::future::Future<Output = Result<ViewAction>> + Send;
 
-    /// Update view state (called periodically)
+    /// Update view state (called periodically). Returns whether anything
+    /// the view draws changed.
     fn update(
         &mut self,
         _app_state: &mut AppState,
-    ) -> impl std::future::Future<Output = Result<()>> + Send;
+    ) -> impl std::future::Future<Output = Result<bool>> + Send;
 
     /// View-specific help text
     fn help_text(&self) -> Vec<(&str, &str)>;
@@ -104,11 +109,26 @@ pub trait View {
     /// View title for display
     fn title(&self) -> &str;
 
+    /// Draw with `theme` from now on
+    fn set_theme(&mut self, theme: &ThemeConfig);
+
     /// Whether this view can handle focus
     fn can_focus(&self) -> bool {
         true
     }
 
+    /// Handle a click or wheel event; positions are hit-tested against the
+    /// areas drawn on the last frame
+    fn handle_mouse(&mut self, _event: MouseEvent, _app_state: &mut AppState) -> ViewAction {
+        ViewAction::Unhandled
+    }
+
+    /// Whether the view is editing text and needs raw keys rather than
+    /// keymap actions
+    fn captures_text(&self) -> bool {
+        false
+    }
+
     /// Called when view gains focus
     fn on_focus(&mut self) {}
 
@@ -125,6 +145,17 @@ pub trait View {
 
     /// Called when view is hidden/destroyed
     fn on_unmount(&mut self) {}
+
+    /// Called when a dialog opened by this view closes; `id` is the one the
+    /// view gave when asking for the dialog
+    fn on_modal_result(
+        &mut self,
+        _id: &str,
+        _result: ModalResult,
+        _app_state: &mut AppState,
+    ) -> ViewAction {
+        ViewAction::None
+    }
 }
 
 /// Actions that views can return to the main application
@@ -133,6 +164,9 @@ pub enum ViewAction {
     /// No action needed
     None,
 
+    /// The view did not use the key; the application may handle it
+    Unhandled,
+
     /// Switch to a different view
     SwitchView(ViewType),
 
@@ -142,12 +176,18 @@ pub enum ViewAction {
     /// Pop the current view from the stack
     PopView,
 
+    /// Open the dataflow explorer with a dataflow selected
+    ShowDataflow(String),
+
     /// Quit the application
     Quit,
 
     /// Execute a CLI command
     ExecuteCommand(String),
 
+    /// Start, stop or destroy a dataflow through the active backend
+    ControlDataflow(tui_interface::DataflowCommand),
+
     /// Show help
     ShowHelp,
 
@@ -163,7 +203,7 @@ pub enum ViewAction {
     /// Request focus on a specific widget
     FocusWidget(String),
 
-    /// Show a confirmation dialog
+    /// Show a confirmation dialog; `confirm_action` identifies the result
     ShowConfirmation {
         message: String,
         confirm_action: String,
@@ -171,10 +211,18 @@ pub enum ViewAction {
 
     /// Show an input dialog
     ShowInput {
+        id: String,
         prompt: String,
         default: Option<String>,
     },
 
+    /// Show a list to pick one option from
+    ShowSelection {
+        id: String,
+        title: String,
+        options: Vec<String>,
+    },
+
     /// Update application state
     UpdateState(StateUpdate),
 }
@@ -308,6 +356,7 @@ pub mod utils {
         text::{Line, Span},
         widgets::{Block, Borders, Cell, Gauge, List, ListItem, Row, Table},
     };
+    use tui_interface::StatusTone;
 
     /// Create a centered popup area
     pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -331,21 +380,24 @@ pub mod utils {
     }
 
     /// Create a progress bar widget
-    pub fn progress_bar(percent: u16, label: &str) -> Gauge {
+    pub fn progress_bar(percent: u16, label: &str) -> Gauge<'_> {
         Gauge::default()
             .block(Block::default().title(label).borders(Borders::ALL))
             .gauge_style(Style::default().fg(Color::Cyan))
             .percent(percent)
     }
 
-    /// Create a status indicator
-    pub fn status_indicator(status: &str) -> Span {
-        let (symbol, color) = match status.to_lowercase().as_str() {
-            "running" | "active" | "healthy" => ("●", Color::Green),
-            "stopped" | "inactive" | "down" => ("●", Color::Red),
-            "warning" | "degraded" => ("●", Color::Yellow),
-            "pending" | "starting" => ("◐", Color::Yellow),
-            _ => ("○", Color::Gray),
+    /// Create a status indicator. Every tone has its own shape, so the
+    /// status reads without color too.
+    pub fn status_indicator(status: impl Into<StatusTone>) -> Span<'static> {
+        let (symbol, color) = match status.into() {
+            StatusTone::Healthy => ("●", Color::Green),
+            StatusTone::Failed => ("✗", Color::Red),
+            StatusTone::Stopped => ("■", Color::Red),
+            StatusTone::Warning => ("▲", Color::Yellow),
+            StatusTone::Pending => ("◐", Color::Yellow),
+            StatusTone::Completed => ("✓", Color::Blue),
+            StatusTone::Idle | StatusTone::Unknown => ("○", Color::Gray),
         };
 
         Span::styled(symbol, Style::default().fg(color))
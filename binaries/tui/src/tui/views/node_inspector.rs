@@ -1,11 +1,11 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 /// Node Inspector View - Detailed node inspection and monitoring (Issue #27)
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Tabs, Wrap},
 };
 use std::time::Duration;
 
@@ -13,9 +13,11 @@ use super::{BaseView, InspectorTab, NodeInspectorState, View, ViewAction};
 use crate::tui::{
     Result,
     app::{AppState, DataflowInfo, NodeMetrics, NodeTelemetrySample},
+    mouse::{self, MouseGesture},
+    responsive::{self, Breakpoint},
     theme::ThemeConfig,
 };
-use tui_interface::NodeResolvedKind;
+use tui_interface::{NodeResolvedKind, NodeStatus};
 
 /// Node Inspector View for detailed node inspection and monitoring
 pub struct NodeInspectorView {
@@ -23,6 +25,9 @@ pub struct NodeInspectorView {
     theme: ThemeConfig,
     /// Current inspector state
     pub state: NodeInspectorState,
+    /// Where the tab bar and tab content were drawn, for mouse hit testing
+    tabs_area: Rect,
+    content_area: Rect,
 }
 
 impl NodeInspectorView {
@@ -33,6 +38,8 @@ impl NodeInspectorView {
                 .with_auto_refresh(Duration::from_secs(1)),
             theme: theme.clone(),
             state: NodeInspectorState::new(dataflow_id, node_id),
+            tabs_area: Rect::default(),
+            content_area: Rect::default(),
         }
     }
 
@@ -50,10 +57,7 @@ impl NodeInspectorView {
             .dataflows
             .iter()
             .flat_map(|df| df.nodes.iter())
-            .filter(|node| {
-                let status = node.status.to_lowercase();
-                status == "running" || status == "active"
-            })
+            .filter(|node| node.status.is_running())
             .count();
         let node_divisor = max(running_nodes, 1) as f64;
         let node_divisor_count = max(running_nodes, 1) as u64;
@@ -65,13 +69,15 @@ impl NodeInspectorView {
             (
                 node.inputs.len() as f64,
                 node.outputs.len() as f64,
-                node.status.to_lowercase(),
+                Some(node.status),
             )
         } else {
-            (0.0, 0.0, String::new())
+            (0.0, 0.0, None)
         };
+        let running = status.is_some_and(|s| s.is_running());
+        let failed = status.is_some_and(|s| s.is_failed());
 
-        if status != "running" && status != "active" {
+        if !running {
             cpu_percent *= 0.35;
             memory_percent *= 0.5;
         } else {
@@ -88,19 +94,15 @@ impl NodeInspectorView {
         };
 
         let mut message_rate = (base_rate * structural_weight).clamp(0.5, 750.0);
-        if status == "failed" || status == "error" {
+        if failed {
             message_rate *= 0.2;
-        } else if status != "running" && status != "active" && !status.is_empty() {
+        } else if !running && status.is_some() {
             message_rate *= 0.5;
         }
 
         let processing_latency_ms = (1000.0 / message_rate).clamp(0.5, 250.0);
         let uptime_seconds = (sys.uptime.as_secs() / node_divisor_count.max(1)).max(1);
-        let error_count = if status == "failed" || status == "error" {
-            1
-        } else {
-            0
-        };
+        let error_count = if failed { 1 } else { 0 };
 
         let mut metrics = NodeMetrics {
             cpu_percent,
@@ -130,7 +132,7 @@ impl NodeInspectorView {
     }
 
     /// Render tab bar
-    fn render_tab_bar(&self, f: &mut Frame, area: Rect) {
+    fn render_tab_bar(&self, f: &mut Frame, area: Rect, title: &str) {
         let tabs = InspectorTab::all();
         let tab_titles: Vec<Line> = tabs
             .iter()
@@ -144,25 +146,25 @@ impl NodeInspectorView {
                 };
 
                 Line::from(vec![
-                    Span::raw(" ["),
+                    Span::raw("["),
                     Span::styled(tab.shortcut(), style),
                     Span::raw("] "),
                     Span::styled(tab.name(), style),
-                    Span::raw(" "),
                 ])
             })
             .collect();
 
-        let tabs_text = tab_titles
-            .into_iter()
-            .flat_map(|line| vec![line, Line::from("")])
-            .collect::<Vec<_>>();
-
-        let tabs_paragraph = Paragraph::new(tabs_text)
-            .block(self.theme.styled_block("Tabs"))
-            .wrap(Wrap { trim: true });
-
-        f.render_widget(tabs_paragraph, area);
+        let selected = tabs
+            .iter()
+            .position(|tab| *tab == self.state.active_tab)
+            .unwrap_or(0);
+        let tabs_widget = Tabs::new(tab_titles)
+            .block(self.theme.styled_block(title))
+            .select(selected)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .divider(" ");
+
+        f.render_widget(tabs_widget, area);
     }
 
     /// Render help bar
@@ -182,6 +184,16 @@ impl NodeInspectorView {
         f.render_widget(help, area);
     }
 
+    fn render_active_tab(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        match self.state.active_tab {
+            InspectorTab::Overview => self.render_overview_tab(f, area, app_state),
+            InspectorTab::Connections => self.render_connections_tab(f, area, app_state),
+            InspectorTab::Performance => self.render_performance_tab(f, area, app_state),
+            InspectorTab::Configuration => self.render_configuration_tab(f, area, app_state),
+            InspectorTab::Debug => self.render_debug_tab(f, area, app_state),
+        }
+    }
+
     /// Render Overview tab
     fn render_overview_tab(&self, f: &mut Frame, area: Rect, app_state: &AppState) {
         let node_info = self.find_node_info(app_state);
@@ -198,7 +210,7 @@ impl NodeInspectorView {
         let node_name = node_info
             .map(|n| n.name.as_str())
             .unwrap_or(&self.state.node_id);
-        let node_status = node_info.map(|n| n.status.as_str()).unwrap_or("unknown");
+        let node_status = node_info.map(|n| n.status).unwrap_or_default();
         let node_kind = node_info.map(|n| n.kind.as_str()).unwrap_or("unknown");
         let node_source = node_info.and_then(|n| n.source.as_deref());
         let node_description = node_info.and_then(|n| n.description.as_deref());
@@ -298,10 +310,16 @@ impl NodeInspectorView {
             .block(self.theme.styled_block(&title))
             .wrap(Wrap { trim: true });
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(12), Constraint::Min(5)])
-            .split(area);
+        // Wide areas put the gauges next to the details instead of below.
+        let chunks = if Breakpoint::of(area).is_wide() {
+            responsive::halves(area).to_vec()
+        } else {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(12), Constraint::Min(5)])
+                .split(area)
+                .to_vec()
+        };
 
         f.render_widget(paragraph, chunks[0]);
         self.render_metrics_gauges(f, chunks[1], app_state, &metrics);
@@ -382,10 +400,7 @@ impl NodeInspectorView {
         let node_info = self.find_node_info(app_state);
 
         // Split into inputs and outputs
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(area);
+        let chunks = responsive::halves(area);
 
         let input_items: Vec<ListItem> = if let Some(node) = node_info {
             if node.inputs.is_empty() {
@@ -460,7 +475,7 @@ impl NodeInspectorView {
                     "Node Status: ",
                     Style::default().fg(self.theme.colors.muted),
                 ),
-                Span::raw(node.status.clone()),
+                Span::raw(node.status.as_str()),
             ]));
             perf_text.push(Line::from(vec![
                 Span::styled("Inputs: ", Style::default().fg(self.theme.colors.muted)),
@@ -601,11 +616,11 @@ impl NodeInspectorView {
                 ]),
                 Line::from(vec![
                     Span::styled("Kind: ", Style::default().fg(self.theme.colors.muted)),
-                    Span::raw(node.kind.clone()),
+                    Span::raw(node.kind.as_str()),
                 ]),
                 Line::from(vec![
                     Span::styled("Status: ", Style::default().fg(self.theme.colors.muted)),
-                    Span::raw(node.status.clone()),
+                    Span::raw(node.status.as_str()),
                 ]),
             ];
             if let Some(source) = &node.source {
@@ -810,17 +825,17 @@ impl NodeInspectorView {
     }
 
     /// Get status indicator span
-    fn status_span(&self, status: &str) -> Span {
-        let (symbol, color) = match status.to_lowercase().as_str() {
-            "running" | "active" => ("● running", Color::Green),
-            "stopped" | "inactive" => ("● stopped", Color::Red),
-            "error" | "failed" => ("● error", Color::Red),
-            "completed" => ("● completed", Color::Blue),
-            "exited" => ("● exited", Color::Yellow),
-            _ => ("○ unknown", Color::Gray),
+    fn status_span(&self, status: NodeStatus) -> Span<'static> {
+        let (symbol, color) = match status {
+            NodeStatus::Running => ("●", Color::Green),
+            NodeStatus::Stopped | NodeStatus::Failed => ("●", Color::Red),
+            NodeStatus::Completed => ("●", Color::Blue),
+            NodeStatus::Exited => ("●", Color::Yellow),
+            NodeStatus::Initializing => ("◐", Color::Yellow),
+            NodeStatus::Unknown => ("○", Color::Gray),
         };
 
-        Span::styled(symbol, Style::default().fg(color))
+        Span::styled(format!("{symbol} {status}"), Style::default().fg(color))
     }
 
     /// Find node info in app state
@@ -866,14 +881,17 @@ impl NodeInspectorView {
 
 impl View for NodeInspectorView {
     fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
-        // Main layout: title, tabs, content, help
+        // Main layout: title, tabs, content, help. Compact areas drop the
+        // title and help rows; the tab bar names the node instead.
+        let compact = Breakpoint::of(area).is_compact();
+        let chrome = if compact { 0 } else { 3 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Title
-                Constraint::Length(7), // Tabs
-                Constraint::Min(10),   // Content
-                Constraint::Length(3), // Help
+                Constraint::Length(chrome), // Title
+                Constraint::Length(3),      // Tabs
+                Constraint::Min(0),         // Content
+                Constraint::Length(chrome), // Help
             ])
             .split(area);
 
@@ -883,35 +901,71 @@ impl View for NodeInspectorView {
             .map(|n| n.name.as_str())
             .unwrap_or(&self.state.node_id);
         let title = format!("Node Inspector: {node_name}");
-        let title_paragraph = Paragraph::new(title)
-            .block(self.theme.styled_block(""))
-            .style(
-                Style::default()
-                    .fg(self.theme.colors.primary)
-                    .add_modifier(Modifier::BOLD),
-            );
-        f.render_widget(title_paragraph, chunks[0]);
+        let tabs_title = if compact { title.as_str() } else { "Tabs" };
+        if !compact {
+            let title_paragraph = Paragraph::new(title.as_str())
+                .block(self.theme.styled_block(""))
+                .style(
+                    Style::default()
+                        .fg(self.theme.colors.primary)
+                        .add_modifier(Modifier::BOLD),
+                );
+            f.render_widget(title_paragraph, chunks[0]);
+        }
 
         // Render tabs
-        self.render_tab_bar(f, chunks[1]);
+        self.tabs_area = chunks[1];
+        self.render_tab_bar(f, chunks[1], tabs_title);
 
         // Render active tab content
-        match self.state.active_tab {
-            InspectorTab::Overview => self.render_overview_tab(f, chunks[2], app_state),
-            InspectorTab::Connections => self.render_connections_tab(f, chunks[2], app_state),
-            InspectorTab::Performance => self.render_performance_tab(f, chunks[2], app_state),
-            InspectorTab::Configuration => self.render_configuration_tab(f, chunks[2], app_state),
-            InspectorTab::Debug => self.render_debug_tab(f, chunks[2], app_state),
-        }
+        self.content_area = chunks[2];
+        self.render_active_tab(f, chunks[2], app_state);
 
         // Render help bar
-        self.render_help_bar(f, chunks[3]);
+        if !compact {
+            self.render_help_bar(f, chunks[3]);
+        }
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, _app_state: &mut AppState) -> ViewAction {
+        match MouseGesture::from_event(&event) {
+            Some(MouseGesture::Click) if mouse::hits(self.tabs_area, &event) => {
+                let tabs = InspectorTab::all();
+                let widths = tabs
+                    .iter()
+                    .map(|tab| tab.shortcut().len() + 3 + tab.name().len());
+                if let Some(index) = mouse::tab_at(self.tabs_area, widths, 1, &event) {
+                    self.state.switch_tab(tabs[index]);
+                }
+                ViewAction::None
+            }
+            // Only the debug tab scrolls, as with the arrow keys.
+            Some(MouseGesture::ScrollUp)
+                if self.state.active_tab == InspectorTab::Debug
+                    && mouse::hits(self.content_area, &event) =>
+            {
+                for _ in 0..mouse::SCROLL_STEP {
+                    self.state.scroll_up();
+                }
+                ViewAction::None
+            }
+            Some(MouseGesture::ScrollDown)
+                if self.state.active_tab == InspectorTab::Debug
+                    && mouse::hits(self.content_area, &event) =>
+            {
+                for _ in 0..mouse::SCROLL_STEP {
+                    self.state.scroll_down(20);
+                }
+                ViewAction::None
+            }
+            _ => ViewAction::Unhandled,
+        }
     }
 
     async fn handle_key(&mut self, key: KeyEvent, _app_state: &mut AppState) -> Result<ViewAction> {
         match key.code {
-            // Quit
-            KeyCode::Char('q') | KeyCode::Esc => {
+            // Back
+            KeyCode::Esc => {
                 return Ok(ViewAction::PopView);
             }
 
@@ -953,25 +1007,26 @@ impl View for NodeInspectorView {
                 self.state.toggle_edit_mode();
             }
 
-            _ => {}
+            _ => return Ok(ViewAction::Unhandled),
         }
 
         Ok(ViewAction::None)
     }
 
-    async fn update(&mut self, app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
         let previous = app_state
             .node_telemetry_sample(&self.state.dataflow_id, &self.state.node_id)
             .map(|sample| &sample.current);
         let metrics = self.compute_node_metrics(app_state, previous);
+        let changed = previous != Some(&metrics);
         app_state.store_node_metrics(&self.state.dataflow_id, &self.state.node_id, metrics);
         self.state.mark_refreshed();
-        Ok(())
+        Ok(changed)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
         vec![
-            ("q/Esc", "Back to previous view"),
+            ("Esc", "Back to previous view"),
             ("←/→", "Switch tabs"),
             ("1-5", "Jump to specific tab"),
             ("d", "Toggle detailed metrics"),
@@ -984,6 +1039,10 @@ impl View for NodeInspectorView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn auto_refresh(&self) -> Option<Duration> {
         self.base.auto_refresh_interval
     }
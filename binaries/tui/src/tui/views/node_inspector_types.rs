@@ -1,8 +1,10 @@
 /// Type definitions for Node Inspector View (Issue #27)
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
 /// Tab variants for Node Inspector
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InspectorTab {
     Overview,
     Connections,
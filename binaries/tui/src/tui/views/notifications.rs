@@ -0,0 +1,301 @@
+//! Notification center: every status message of the session, newest first.
+
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use super::{BaseView, View, ViewAction};
+use crate::tui::{
+    Frame, Result,
+    app::{AppState, MessageLevel, MessageTarget, StatusMessage, ViewType},
+    mouse::{self, MouseGesture, SCROLL_STEP},
+    theme::ThemeConfig,
+};
+
+/// Lines above the first row: the table header.
+const HEADER_ROWS: u16 = 1;
+
+pub struct NotificationsView {
+    base: BaseView,
+    theme: ThemeConfig,
+    /// Messages below this level are hidden
+    min_level: MessageLevel,
+    /// Text that the message, source or target must contain
+    filter: String,
+    editing_filter: bool,
+    /// Index into the filtered messages, newest first
+    selected: usize,
+    /// First filtered message on screen
+    offset: usize,
+    /// Where the table was drawn, for mouse hit testing
+    table_area: Rect,
+}
+
+impl NotificationsView {
+    pub fn new(theme: &ThemeConfig) -> Self {
+        Self {
+            base: BaseView::new("Notifications".to_string()),
+            theme: theme.clone(),
+            min_level: MessageLevel::Info,
+            filter: String::new(),
+            editing_filter: false,
+            selected: 0,
+            offset: 0,
+            table_area: Rect::default(),
+        }
+    }
+
+    fn matches(&self, message: &StatusMessage) -> bool {
+        if message.level < self.min_level {
+            return false;
+        }
+        if self.filter.is_empty() {
+            return true;
+        }
+        let filter = self.filter.to_lowercase();
+        message.message.to_lowercase().contains(&filter)
+            || message.source.to_lowercase().contains(&filter)
+            || message
+                .target
+                .as_ref()
+                .is_some_and(|target| target.to_string().to_lowercase().contains(&filter))
+    }
+
+    /// Messages passing the filters, newest first.
+    pub fn visible<'a>(&self, app_state: &'a AppState) -> Vec<&'a StatusMessage> {
+        app_state
+            .status_messages
+            .iter()
+            .rev()
+            .filter(|message| self.matches(message))
+            .collect()
+    }
+
+    fn cycle_level(&mut self) {
+        self.min_level = match self.min_level {
+            MessageLevel::Info | MessageLevel::Success => MessageLevel::Warning,
+            MessageLevel::Warning => MessageLevel::Error,
+            MessageLevel::Error => MessageLevel::Info,
+        };
+        self.selected = 0;
+    }
+
+    fn level_label(&self) -> &'static str {
+        match self.min_level {
+            MessageLevel::Info | MessageLevel::Success => "all",
+            MessageLevel::Warning => "warnings and errors",
+            MessageLevel::Error => "errors",
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize, count: usize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(count.saturating_sub(1));
+    }
+
+    fn open(&self, app_state: &AppState) -> ViewAction {
+        let Some(message) = self.visible(app_state).get(self.selected).copied() else {
+            return ViewAction::None;
+        };
+        match &message.target {
+            Some(MessageTarget::Dataflow(dataflow)) => ViewAction::ShowDataflow(dataflow.clone()),
+            Some(MessageTarget::Node {
+                dataflow_id,
+                node_id,
+            }) => ViewAction::PushView(ViewType::NodeInspector {
+                dataflow_id: dataflow_id.clone(),
+                node_id: node_id.clone(),
+            }),
+            None => ViewAction::ShowStatus("This message is not about a dataflow".to_string()),
+        }
+    }
+
+    fn render_filter_bar(&self, f: &mut Frame, area: Rect, shown: usize, total: usize) {
+        let label = Style::default().fg(self.theme.colors.muted);
+        let value = Style::default().fg(self.theme.colors.text);
+        let mut spans = vec![
+            Span::styled("Level: ", label),
+            Span::styled(self.level_label(), value),
+            Span::styled("   Filter: ", label),
+        ];
+        if self.editing_filter {
+            spans.push(Span::styled(
+                format!("{}_", self.filter),
+                Style::default().fg(self.theme.colors.primary),
+            ));
+        } else if self.filter.is_empty() {
+            spans.push(Span::styled("none", label));
+        } else {
+            spans.push(Span::styled(self.filter.as_str(), value));
+        }
+        spans.push(Span::styled(format!("   {shown} of {total}"), label));
+
+        let bar = Paragraph::new(Line::from(spans)).block(self.theme.styled_block("Notifications"));
+        f.render_widget(bar, area);
+    }
+}
+
+impl View for NotificationsView {
+    fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+        self.table_area = chunks[1];
+
+        let messages = self.visible(app_state);
+        self.render_filter_bar(
+            f,
+            chunks[0],
+            messages.len(),
+            app_state.status_messages.len(),
+        );
+
+        // Scroll just far enough to keep the selection on screen.
+        let rows = usize::from(chunks[1].height.saturating_sub(2 + HEADER_ROWS)).max(1);
+        self.selected = self.selected.min(messages.len().saturating_sub(1));
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + rows {
+            self.offset = self.selected + 1 - rows;
+        }
+
+        let header = Row::new(["Time", "Level", "Source", "Target", "Message"])
+            .style(self.theme.table_header_style());
+        let body = messages
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(rows)
+            .map(|(index, message)| {
+                let target = message
+                    .target
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let row = Row::new([
+                    Cell::from(message.time.format("%H:%M:%S").to_string()),
+                    Cell::from(message.level.label())
+                        .style(self.theme.message_style(message.level)),
+                    Cell::from(message.source.as_str()),
+                    Cell::from(target),
+                    Cell::from(message.message.as_str()),
+                ]);
+                if index == self.selected {
+                    row.style(
+                        Style::default()
+                            .bg(self.theme.colors.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
+            });
+
+        let table = Table::new(
+            body,
+            [
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(18),
+                Constraint::Length(24),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(table, chunks[1]);
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, app_state: &mut AppState) -> ViewAction {
+        if self.editing_filter || !mouse::hits(self.table_area, &event) {
+            return ViewAction::Unhandled;
+        }
+
+        let count = self.visible(app_state).len();
+        match MouseGesture::from_event(&event) {
+            Some(MouseGesture::Click) => {
+                if let Some(row) = mouse::row_at(self.table_area, HEADER_ROWS, &event) {
+                    if self.offset + row < count {
+                        self.selected = self.offset + row;
+                    }
+                }
+            }
+            Some(MouseGesture::ScrollUp) => self.move_selection(-(SCROLL_STEP as isize), count),
+            Some(MouseGesture::ScrollDown) => self.move_selection(SCROLL_STEP as isize, count),
+            None => return ViewAction::Unhandled,
+        }
+        ViewAction::None
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<ViewAction> {
+        if self.editing_filter {
+            match key.code {
+                KeyCode::Esc => {
+                    self.editing_filter = false;
+                    self.filter.clear();
+                }
+                KeyCode::Enter => self.editing_filter = false,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => return Ok(ViewAction::None),
+            }
+            self.selected = 0;
+            return Ok(ViewAction::None);
+        }
+
+        let count = self.visible(app_state).len();
+        match key.code {
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.selected = 0;
+            }
+            KeyCode::Esc => return Ok(ViewAction::PopView),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1, count),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1, count),
+            KeyCode::PageUp => self.move_selection(-10, count),
+            KeyCode::PageDown => self.move_selection(10, count),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = count.saturating_sub(1),
+            KeyCode::Char('/') => self.editing_filter = true,
+            KeyCode::Char('f') => self.cycle_level(),
+            KeyCode::Enter => return Ok(self.open(app_state)),
+            _ => return Ok(ViewAction::Unhandled),
+        }
+        Ok(ViewAction::None)
+    }
+
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn captures_text(&self) -> bool {
+        self.editing_filter
+    }
+
+    fn help_text(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("↑↓ / j/k", "Navigate messages"),
+            ("Enter", "Go to the dataflow or node"),
+            ("/", "Filter by text"),
+            ("f", "Cycle level filter"),
+            ("Esc", "Clear filter / go back"),
+        ]
+    }
+
+    fn title(&self) -> &str {
+        &self.base.title
+    }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+}
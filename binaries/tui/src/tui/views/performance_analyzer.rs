@@ -12,10 +12,7 @@ use std::time::Duration;
 
 #[cfg(test)]
 use super::TimeRange;
-use super::{
-    AlertSeverity, AnalyzerTab, BaseView, MetricType, PerformanceAlert, PerformanceAnalyzerState,
-    View, ViewAction,
-};
+use super::{AnalyzerTab, BaseView, MetricType, PerformanceAnalyzerState, View, ViewAction};
 use crate::tui::{Result, app::AppState, theme::ThemeConfig};
 
 /// Performance Analyzer View for system performance monitoring
@@ -123,7 +120,7 @@ impl PerformanceAnalyzerView {
         let help_text = match self.state.active_tab {
             AnalyzerTab::Overview => "[q]uit [←→]tabs [t]ime range [r]efresh",
             AnalyzerTab::Metrics => "[q]uit [←→]tabs [↑↓]metric [t]ime range",
-            AnalyzerTab::Alerts => "[q]uit [←→]tabs [↑↓]navigate [c]lear",
+            AnalyzerTab::Alerts => "[q]uit [←→]tabs [↑↓]navigate [a]cknowledge",
         };
 
         let help = Paragraph::new(help_text)
@@ -298,12 +295,8 @@ impl PerformanceAnalyzerView {
     /// Render Alerts tab
     fn render_alerts_tab(&self, f: &mut Frame, area: Rect) {
         if self.state.alerts.is_empty() {
-            let no_alerts = Paragraph::new("No performance alerts")
-                .block(
-                    Block::default()
-                        .title("Performance Alerts")
-                        .borders(Borders::ALL),
-                )
+            let no_alerts = Paragraph::new("No active alerts")
+                .block(Block::default().title("Alerts").borders(Borders::ALL))
                 .style(Style::default().fg(self.theme.colors.muted));
             f.render_widget(no_alerts, area);
             return;
@@ -323,15 +316,22 @@ impl PerformanceAnalyzerView {
                     Style::default().fg(self.theme.colors.text)
                 };
 
-                let severity_color = match alert.severity {
-                    AlertSeverity::Warning => Color::Yellow,
-                    AlertSeverity::Critical => Color::Red,
+                let level_color = if alert.acknowledged {
+                    self.theme.colors.muted
+                } else {
+                    alert.level.color()
                 };
 
                 let content = vec![
-                    Span::styled(alert.severity.symbol(), Style::default().fg(severity_color)),
+                    Span::styled(alert.level.icon(), Style::default().fg(level_color)),
                     Span::raw(" "),
+                    Span::styled(&alert.title, Style::default().fg(level_color)),
+                    Span::raw(": "),
                     Span::raw(&alert.message),
+                    Span::styled(
+                        format!("  {}s ago", alert.timestamp.elapsed().as_secs()),
+                        Style::default().fg(self.theme.colors.muted),
+                    ),
                 ];
 
                 ListItem::new(Line::from(content)).style(style)
@@ -342,7 +342,7 @@ impl PerformanceAnalyzerView {
             .block(
                 Block::default()
                     .title(format!(
-                        "Performance Alerts ({}) [c]lear",
+                        "Alerts ({}) [a]cknowledge",
                         self.state.alerts.len()
                     ))
                     .borders(Borders::ALL),
@@ -355,42 +355,13 @@ impl PerformanceAnalyzerView {
 
         f.render_widget(list, area);
     }
-
-    /// Update performance alerts based on current metrics
-    fn update_alerts(&mut self) {
-        // Generate mock alerts based on thresholds
-        let mut new_alerts = Vec::new();
-
-        for metric_type in MetricType::all() {
-            let data = self.generate_mock_data(metric_type);
-            if let Some((_, current_value)) = data.last() {
-                let warning_threshold = metric_type.warning_threshold();
-                let critical_threshold = metric_type.critical_threshold();
-
-                if *current_value >= critical_threshold {
-                    new_alerts.push(PerformanceAlert::new(
-                        metric_type,
-                        AlertSeverity::Critical,
-                        *current_value,
-                        critical_threshold,
-                    ));
-                } else if *current_value >= warning_threshold {
-                    new_alerts.push(PerformanceAlert::new(
-                        metric_type,
-                        AlertSeverity::Warning,
-                        *current_value,
-                        warning_threshold,
-                    ));
-                }
-            }
-        }
-
-        self.state.alerts = new_alerts;
-    }
 }
 
 impl View for PerformanceAnalyzerView {
-    fn render(&mut self, f: &mut Frame, area: Rect, _app_state: &AppState) {
+    fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        self.state
+            .set_alerts(app_state.alerts.active().into_iter().cloned().collect());
+
         // Main layout: title, tabs, content, help
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -427,7 +398,7 @@ impl View for PerformanceAnalyzerView {
         self.render_help_bar(f, chunks[3]);
     }
 
-    async fn handle_key(&mut self, key: KeyEvent, _app_state: &mut AppState) -> Result<ViewAction> {
+    async fn handle_key(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<ViewAction> {
         match key.code {
             // Quit
             KeyCode::Char('q') | KeyCode::Esc => {
@@ -468,14 +439,13 @@ impl View for PerformanceAnalyzerView {
                 self.state.next_alert();
             }
 
-            // Clear alerts
-            KeyCode::Char('c') if self.state.active_tab == AnalyzerTab::Alerts => {
-                self.state.clear_alerts();
+            // Acknowledge alerts
+            KeyCode::Char('a') if self.state.active_tab == AnalyzerTab::Alerts => {
+                app_state.alerts.acknowledge_all();
             }
 
             // Refresh
             KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.update_alerts();
                 return Ok(ViewAction::Refresh);
             }
 
@@ -485,10 +455,9 @@ impl View for PerformanceAnalyzerView {
         Ok(ViewAction::None)
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
         self.state.mark_refreshed();
-        self.update_alerts();
-        Ok(())
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -498,7 +467,7 @@ impl View for PerformanceAnalyzerView {
             ("1-3", "Jump to specific tab"),
             ("t", "Cycle time range"),
             ("↑/↓", "Navigate items"),
-            ("c", "Clear alerts"),
+            ("a", "Acknowledge alerts"),
             ("Ctrl+r", "Refresh"),
         ]
     }
@@ -507,6 +476,10 @@ impl View for PerformanceAnalyzerView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn auto_refresh(&self) -> Option<Duration> {
         self.base.auto_refresh_interval
     }
@@ -1,6 +1,8 @@
 /// Type definitions for Performance Analyzer View (Issue #26)
 use std::time::Instant;
 
+use super::Alert;
+
 /// Tab variants for Performance Analyzer
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AnalyzerTab {
@@ -57,8 +59,7 @@ impl AnalyzerTab {
 }
 
 /// Time range for performance data
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[derive(Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TimeRange {
     #[default]
     LastHour,
@@ -113,7 +114,6 @@ impl TimeRange {
     }
 }
 
-
 /// Type of performance metric
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MetricType {
@@ -175,71 +175,6 @@ impl MetricType {
     }
 }
 
-/// Performance alert severity
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AlertSeverity {
-    Warning,
-    Critical,
-}
-
-impl AlertSeverity {
-    /// Get the display name for this severity
-    pub fn name(&self) -> &str {
-        match self {
-            AlertSeverity::Warning => "Warning",
-            AlertSeverity::Critical => "Critical",
-        }
-    }
-
-    /// Get the symbol for this severity
-    pub fn symbol(&self) -> &str {
-        match self {
-            AlertSeverity::Warning => "⚠",
-            AlertSeverity::Critical => "🔴",
-        }
-    }
-}
-
-/// Performance alert
-#[derive(Debug, Clone)]
-pub struct PerformanceAlert {
-    pub metric_type: MetricType,
-    pub severity: AlertSeverity,
-    pub current_value: f64,
-    pub threshold: f64,
-    pub message: String,
-    pub timestamp: Instant,
-}
-
-impl PerformanceAlert {
-    /// Create a new performance alert
-    pub fn new(
-        metric_type: MetricType,
-        severity: AlertSeverity,
-        current_value: f64,
-        threshold: f64,
-    ) -> Self {
-        let message = format!(
-            "{} usage at {:.1}{} exceeds {} threshold of {:.1}{}",
-            metric_type.name(),
-            current_value,
-            metric_type.unit(),
-            severity.name().to_lowercase(),
-            threshold,
-            metric_type.unit()
-        );
-
-        Self {
-            metric_type,
-            severity,
-            current_value,
-            threshold,
-            message,
-            timestamp: Instant::now(),
-        }
-    }
-}
-
 /// State for the Performance Analyzer view
 #[derive(Debug, Clone)]
 pub struct PerformanceAnalyzerState {
@@ -252,8 +187,8 @@ pub struct PerformanceAnalyzerState {
     /// Selected metric for detailed view
     pub selected_metric: MetricType,
 
-    /// Current performance alerts
-    pub alerts: Vec<PerformanceAlert>,
+    /// Active alerts, most severe first
+    pub alerts: Vec<Alert>,
 
     /// Last refresh timestamp
     pub last_refresh: Instant,
@@ -339,15 +274,10 @@ impl PerformanceAnalyzerState {
         }
     }
 
-    /// Add a performance alert
-    pub fn add_alert(&mut self, alert: PerformanceAlert) {
-        self.alerts.push(alert);
-    }
-
-    /// Clear all alerts
-    pub fn clear_alerts(&mut self) {
-        self.alerts.clear();
-        self.selected_alert = 0;
+    /// Replace the alerts, keeping the selection in range
+    pub fn set_alerts(&mut self, alerts: Vec<Alert>) {
+        self.alerts = alerts;
+        self.selected_alert = self.selected_alert.min(self.alerts.len().saturating_sub(1));
     }
 
     /// Mark as refreshed
@@ -365,6 +295,7 @@ impl Default for PerformanceAnalyzerState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tui::views::AlertLevel;
 
     #[test]
     fn test_analyzer_tab_all_returns_three_tabs() {
@@ -488,24 +419,6 @@ mod tests {
         assert!(MetricType::Cpu.warning_threshold() < MetricType::Cpu.critical_threshold());
     }
 
-    #[test]
-    fn test_alert_severity_names() {
-        assert_eq!(AlertSeverity::Warning.name(), "Warning");
-        assert_eq!(AlertSeverity::Critical.name(), "Critical");
-    }
-
-    #[test]
-    fn test_performance_alert_creation() {
-        let alert = PerformanceAlert::new(MetricType::Cpu, AlertSeverity::Warning, 75.0, 70.0);
-
-        assert_eq!(alert.metric_type, MetricType::Cpu);
-        assert_eq!(alert.severity, AlertSeverity::Warning);
-        assert_eq!(alert.current_value, 75.0);
-        assert_eq!(alert.threshold, 70.0);
-        assert!(alert.message.contains("CPU"));
-        assert!(alert.message.contains("75.0"));
-    }
-
     #[test]
     fn test_performance_analyzer_state_new() {
         let state = PerformanceAnalyzerState::new();
@@ -566,11 +479,15 @@ mod tests {
 
         assert!(state.alerts.is_empty());
 
-        let alert1 = PerformanceAlert::new(MetricType::Cpu, AlertSeverity::Warning, 75.0, 70.0);
-        let alert2 = PerformanceAlert::new(MetricType::Memory, AlertSeverity::Critical, 96.0, 95.0);
+        let alert = |title: &str| Alert {
+            level: AlertLevel::Warning,
+            title: title.to_string(),
+            message: String::new(),
+            timestamp: Instant::now(),
+            acknowledged: false,
+        };
 
-        state.add_alert(alert1);
-        state.add_alert(alert2);
+        state.set_alerts(vec![alert("cpu"), alert("memory")]);
 
         assert_eq!(state.alerts.len(), 2);
 
@@ -580,7 +497,13 @@ mod tests {
         state.next_alert();
         assert_eq!(state.selected_alert, 0); // Wraps around
 
-        state.clear_alerts();
+        state.prev_alert();
+        assert_eq!(state.selected_alert, 1);
+
+        state.set_alerts(vec![alert("cpu")]);
+        assert_eq!(state.selected_alert, 0); // Kept in range
+
+        state.set_alerts(Vec::new());
         assert!(state.alerts.is_empty());
         assert_eq!(state.selected_alert, 0);
     }
@@ -293,9 +293,9 @@ impl View for SettingsView {
         }
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
         self.state.mark_refreshed();
-        Ok(())
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -318,6 +318,10 @@ impl View for SettingsView {
         &self.base.title
     }
 
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
+
     fn on_focus(&mut self) {
         self.base.set_focused(true);
     }
@@ -71,11 +71,11 @@ impl View for SystemMonitorView {
         _key: KeyEvent,
         _app_state: &mut AppState,
     ) -> Result<ViewAction> {
-        Ok(ViewAction::None)
+        Ok(ViewAction::Unhandled)
     }
 
-    async fn update(&mut self, _app_state: &mut AppState) -> Result<()> {
-        Ok(())
+    async fn update(&mut self, _app_state: &mut AppState) -> Result<bool> {
+        Ok(false)
     }
 
     fn help_text(&self) -> Vec<(&str, &str)> {
@@ -85,6 +85,10 @@ impl View for SystemMonitorView {
     fn title(&self) -> &str {
         &self.base.title
     }
+
+    fn set_theme(&mut self, theme: &ThemeConfig) {
+        self.theme = theme.clone();
+    }
 }
 
 fn render_status(
@@ -130,6 +134,11 @@ fn render_status(
         Span::styled(status_label, status_style.add_modifier(Modifier::BOLD)),
         Span::styled(" • ", Style::default().fg(theme.colors.muted)),
         Span::styled(age_text, Style::default().fg(theme.colors.muted)),
+        Span::styled(" • Source: ", Style::default().fg(theme.colors.text)),
+        Span::styled(
+            metrics.source.label(),
+            Style::default().fg(theme.colors.primary),
+        ),
         Span::styled(" • Samples: ", Style::default().fg(theme.colors.text)),
         Span::styled(
             samples.to_string(),
@@ -0,0 +1,640 @@
+//! Tiled panes and tabbed workspaces.
+//!
+//! A workspace tiles the body of the screen with panes. Each pane hosts a
+//! `ViewManager`, so it has its own view and navigation stack, and splits are
+//! laid out with `LayoutManager`. Workspaces are kept as named tabs; keys go
+//! to the focused pane of the active tab, and only that tab's panes are drawn
+//! and updated.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::Paragraph,
+};
+use serde::{Deserialize, Serialize};
+use tui_interface::LogService;
+
+use crate::tui::{
+    ComponentId, Result,
+    app::{AppState, ViewType},
+    keymap::Keymap,
+    session::{SavedPane, SavedWorkspace, Session},
+    theme::ThemeConfig,
+    view_manager::ViewManager,
+    views::{LayoutConfig, LayoutManager},
+};
+
+pub type PaneId = u32;
+
+/// Name of the tab the application starts with.
+const DEFAULT_TAB: &str = "main";
+/// Resizing never shrinks a pane below this share of its split.
+const MIN_PERCENT: u16 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitDirection {
+    /// Side by side
+    Horizontal,
+    /// One above the other
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// How the panes of a workspace tile its area.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PaneTree {
+    Pane {
+        id: PaneId,
+    },
+    Split {
+        direction: SplitDirection,
+        /// Share of the area taken by `first`
+        percent: u16,
+        first: Box<PaneTree>,
+        second: Box<PaneTree>,
+    },
+}
+
+impl PaneTree {
+    /// Pane ids in layout order.
+    pub fn panes(&self) -> Vec<PaneId> {
+        match self {
+            Self::Pane { id } => vec![*id],
+            Self::Split { first, second, .. } => {
+                let mut ids = first.panes();
+                ids.extend(second.panes());
+                ids
+            }
+        }
+    }
+
+    /// The area of every pane when the tree fills `area`, in layout order.
+    pub fn layout(&self, area: Rect) -> Vec<(PaneId, Rect)> {
+        let mut areas = Vec::new();
+        self.layout_into(area, &mut areas);
+        areas
+    }
+
+    fn layout_into(&self, area: Rect, areas: &mut Vec<(PaneId, Rect)>) {
+        match self {
+            Self::Pane { id } => areas.push((*id, area)),
+            Self::Split {
+                direction,
+                percent,
+                first,
+                second,
+            } => {
+                let first_id = ComponentId::new("first");
+                let second_id = ComponentId::new("second");
+                let components = vec![
+                    (first_id.clone(), Constraint::Percentage(*percent)),
+                    (second_id.clone(), Constraint::Percentage(100 - percent)),
+                ];
+                let mut manager = LayoutManager::new();
+                manager.set_layout(match direction {
+                    SplitDirection::Horizontal => LayoutConfig::Horizontal { components },
+                    SplitDirection::Vertical => LayoutConfig::Vertical { components },
+                });
+                let chunks = manager.calculate_layout(area);
+                first.layout_into(chunks[&first_id], areas);
+                second.layout_into(chunks[&second_id], areas);
+            }
+        }
+    }
+
+    /// Replace pane `id` with a split between it and `new_id`.
+    fn split(&mut self, id: PaneId, new_id: PaneId, direction: SplitDirection) -> bool {
+        match self {
+            Self::Pane { id: pane } if *pane == id => {
+                *self = Self::Split {
+                    direction,
+                    percent: 50,
+                    first: Box::new(Self::Pane { id }),
+                    second: Box::new(Self::Pane { id: new_id }),
+                };
+                true
+            }
+            Self::Pane { .. } => false,
+            Self::Split { first, second, .. } => {
+                first.split(id, new_id, direction) || second.split(id, new_id, direction)
+            }
+        }
+    }
+
+    /// Remove pane `id` and give its space to its sibling. Returns the
+    /// first pane of the sibling, or `None` if `id` is not in the tree or is
+    /// the only pane.
+    fn remove(&mut self, id: PaneId) -> Option<PaneId> {
+        let Self::Split { first, second, .. } = self else {
+            return None;
+        };
+        let sibling = match (&**first, &**second) {
+            (Self::Pane { id: pane }, _) if *pane == id => second,
+            (_, Self::Pane { id: pane }) if *pane == id => first,
+            _ => return first.remove(id).or_else(|| second.remove(id)),
+        };
+        let sibling = std::mem::replace(&mut **sibling, Self::Pane { id });
+        *self = sibling;
+        self.panes().first().copied()
+    }
+
+    /// Grow pane `id` by `delta` percent of the split that directly holds
+    /// it. Returns whether anything changed.
+    fn resize(&mut self, id: PaneId, delta: i16) -> bool {
+        let Self::Split {
+            percent,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        let delta = match (&**first, &**second) {
+            (Self::Pane { id: pane }, _) if *pane == id => delta,
+            (_, Self::Pane { id: pane }) if *pane == id => -delta,
+            _ => return first.resize(id, delta) || second.resize(id, delta),
+        };
+        let bound = MIN_PERCENT as i16;
+        let resized = (*percent as i16 + delta).clamp(bound, 100 - bound) as u16;
+        let changed = resized != *percent;
+        *percent = resized;
+        changed
+    }
+}
+
+/// A named set of tiled panes.
+pub struct Workspace {
+    name: String,
+    layout: PaneTree,
+    panes: BTreeMap<PaneId, ViewManager>,
+    focused: PaneId,
+    /// Where the workspace was last drawn, for mouse and directional focus
+    area: Rect,
+}
+
+impl Workspace {
+    fn new(name: String, pane: ViewManager) -> Self {
+        Self {
+            name,
+            layout: PaneTree::Pane { id: 0 },
+            panes: BTreeMap::from([(0, pane)]),
+            focused: 0,
+            area: Rect::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn layout(&self) -> &PaneTree {
+        &self.layout
+    }
+
+    pub fn pane_count(&self) -> usize {
+        self.panes.len()
+    }
+
+    pub fn focused_pane(&self) -> PaneId {
+        self.focused
+    }
+
+    fn focused(&self) -> &ViewManager {
+        &self.panes[&self.focused]
+    }
+
+    fn focused_mut(&mut self) -> &mut ViewManager {
+        self.panes
+            .get_mut(&self.focused)
+            .expect("the focused pane exists")
+    }
+
+    fn next_id(&self) -> PaneId {
+        self.panes.keys().next_back().map_or(0, |id| id + 1)
+    }
+
+    /// Focus the pane drawn at `column`, `row` on the last frame.
+    pub fn focus_at(&mut self, column: u16, row: u16) -> bool {
+        let hit = self.layout.layout(self.area).into_iter().find(|(_, area)| {
+            (area.x..area.right()).contains(&column) && (area.y..area.bottom()).contains(&row)
+        });
+        match hit {
+            Some((id, _)) if id != self.focused => {
+                self.focused = id;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Focus the nearest pane in `direction` that lines up with the focused
+    /// one, as laid out on the last frame.
+    pub fn focus_towards(&mut self, direction: FocusDirection) -> bool {
+        let areas = self.layout.layout(self.area);
+        let Some(&(_, from)) = areas.iter().find(|(id, _)| *id == self.focused) else {
+            return false;
+        };
+        let overlap = |a: (u16, u16), b: (u16, u16)| a.0 < b.1 && b.0 < a.1;
+        let target = areas
+            .iter()
+            .filter(|(id, _)| *id != self.focused)
+            .filter_map(|&(id, to)| {
+                let rows = overlap((from.y, from.bottom()), (to.y, to.bottom()));
+                let columns = overlap((from.x, from.right()), (to.x, to.right()));
+                let (gap, aligned) = match direction {
+                    FocusDirection::Left => (from.x.checked_sub(to.right())?, rows),
+                    FocusDirection::Right => (to.x.checked_sub(from.right())?, rows),
+                    FocusDirection::Up => (from.y.checked_sub(to.bottom())?, columns),
+                    FocusDirection::Down => (to.y.checked_sub(from.bottom())?, columns),
+                };
+                aligned.then_some((gap, id))
+            })
+            .min();
+        match target {
+            Some((_, id)) => {
+                self.focused = id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Grow the focused pane by `delta` percent, or shrink it if negative.
+    pub fn resize(&mut self, delta: i16) -> bool {
+        self.layout.resize(self.focused, delta)
+    }
+
+    /// Draw every pane. With more than one pane, each gets a title row and
+    /// the focused one is highlighted.
+    fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState, theme: &ThemeConfig) {
+        self.area = area;
+        if self.panes.len() == 1 {
+            self.focused_mut().render(f, area, app_state);
+            return;
+        }
+
+        for (id, pane_area) in self.layout.layout(area) {
+            let Some(pane) = self.panes.get_mut(&id) else {
+                continue;
+            };
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(pane_area);
+            let style = if id == self.focused {
+                theme.styles.selection_style
+            } else {
+                Style::default().fg(theme.colors.muted)
+            };
+            let title = Paragraph::new(format!(" {} ", pane.current().title())).style(style);
+            f.render_widget(title, chunks[0]);
+            pane.render(f, chunks[1], app_state);
+        }
+    }
+
+    /// Run the periodic update of every pane. Returns whether any pane
+    /// updated.
+    async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
+        let mut updated = false;
+        for pane in self.panes.values_mut() {
+            updated |= pane.update(app_state).await?;
+        }
+        Ok(updated)
+    }
+
+    fn close(&mut self) {
+        for pane in self.panes.values_mut() {
+            pane.close();
+        }
+    }
+}
+
+/// The open workspaces, one per tab.
+pub struct Workspaces {
+    tabs: Vec<Workspace>,
+    active: usize,
+    theme: ThemeConfig,
+    keymap: Arc<Keymap>,
+    log_service: Option<Arc<dyn LogService>>,
+}
+
+impl Workspaces {
+    pub fn new(initial_view: ViewType, theme: ThemeConfig) -> Self {
+        let pane = ViewManager::new(initial_view, theme.clone());
+        Self {
+            tabs: vec![Workspace::new(DEFAULT_TAB.to_string(), pane)],
+            active: 0,
+            theme,
+            keymap: Arc::default(),
+            log_service: None,
+        }
+    }
+
+    fn pane(&self, view_type: ViewType) -> ViewManager {
+        let mut pane = ViewManager::new(view_type, self.theme.clone());
+        pane.set_keymap(self.keymap.clone());
+        pane.set_log_service(self.log_service.clone());
+        pane
+    }
+
+    fn panes_mut(&mut self) -> impl Iterator<Item = &mut ViewManager> {
+        self.tabs.iter_mut().flat_map(|tab| tab.panes.values_mut())
+    }
+
+    pub fn set_log_service(&mut self, log_service: Option<Arc<dyn LogService>>) {
+        for pane in self.panes_mut() {
+            pane.set_log_service(log_service.clone());
+        }
+        self.log_service = log_service;
+    }
+
+    pub fn set_keymap(&mut self, keymap: Arc<Keymap>) {
+        for pane in self.panes_mut() {
+            pane.set_keymap(keymap.clone());
+        }
+        self.keymap = keymap;
+    }
+
+    pub fn set_theme(&mut self, theme: &ThemeConfig) {
+        for pane in self.panes_mut() {
+            pane.set_theme(theme);
+        }
+        self.theme = theme.clone();
+    }
+
+    pub fn tabs(&self) -> &[Workspace] {
+        &self.tabs
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> &Workspace {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Workspace {
+        &mut self.tabs[self.active]
+    }
+
+    /// The pane that receives keys.
+    pub fn focused(&self) -> &ViewManager {
+        self.active().focused()
+    }
+
+    pub fn focused_mut(&mut self) -> &mut ViewManager {
+        self.active_mut().focused_mut()
+    }
+
+    /// The views shown in the active tab.
+    pub fn visible_views(&self) -> impl Iterator<Item = &ViewType> {
+        self.active().panes.values().map(ViewManager::current)
+    }
+
+    /// Split the focused pane. The new pane shows `view_type`, or the
+    /// focused pane's view, and takes focus.
+    pub fn split(&mut self, direction: SplitDirection, view_type: Option<ViewType>) {
+        let view_type = view_type.unwrap_or_else(|| self.focused().current().clone());
+        let pane = self.pane(view_type);
+        let tab = self.active_mut();
+        let id = tab.next_id();
+        tab.layout.split(tab.focused, id, direction);
+        tab.panes.insert(id, pane);
+        tab.focused = id;
+    }
+
+    /// Close the focused pane. The last pane of a tab can't be closed.
+    pub fn close_pane(&mut self) -> bool {
+        let tab = self.active_mut();
+        let Some(next) = tab.layout.remove(tab.focused) else {
+            return false;
+        };
+        if let Some(mut pane) = tab.panes.remove(&tab.focused) {
+            pane.close();
+        }
+        tab.focused = next;
+        true
+    }
+
+    /// Open a tab after the active one, showing the focused view, and
+    /// switch to it.
+    pub fn new_tab(&mut self, name: Option<String>) {
+        let name = name.unwrap_or_else(|| format!("tab {}", self.tabs.len() + 1));
+        let pane = self.pane(self.focused().current().clone());
+        self.active += 1;
+        self.tabs.insert(self.active, Workspace::new(name, pane));
+    }
+
+    /// Close the active tab. The last tab can't be closed.
+    pub fn close_tab(&mut self) -> bool {
+        if self.tabs.len() == 1 {
+            return false;
+        }
+        self.tabs.remove(self.active).close();
+        self.active = self.active.min(self.tabs.len() - 1);
+        true
+    }
+
+    pub fn rename_tab(&mut self, name: String) {
+        self.active_mut().name = name;
+    }
+
+    pub fn next_tab(&mut self) {
+        self.active = (self.active + 1) % self.tabs.len();
+    }
+
+    pub fn previous_tab(&mut self) {
+        self.active = (self.active + self.tabs.len() - 1) % self.tabs.len();
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, app_state: &AppState) {
+        self.tabs[self.active].render(f, area, app_state, &self.theme);
+    }
+
+    /// Update the panes of the active tab. Returns whether any updated.
+    pub async fn update(&mut self, app_state: &mut AppState) -> Result<bool> {
+        self.active_mut().update(app_state).await
+    }
+
+    /// Every tab with its layout and the views of each pane.
+    pub fn session(&self, app_state: &AppState) -> Session {
+        let tabs = self
+            .tabs
+            .iter()
+            .map(|tab| SavedWorkspace {
+                name: tab.name.clone(),
+                layout: tab.layout.clone(),
+                focused: tab.focused,
+                panes: tab
+                    .panes
+                    .iter()
+                    .map(|(id, pane)| SavedPane {
+                        id: *id,
+                        views: pane.saved_views(app_state),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Session {
+            tabs,
+            active_tab: self.active,
+        }
+    }
+
+    /// Replace the open tabs with saved ones. An empty session leaves the
+    /// current tabs alone.
+    pub fn restore_session(&mut self, session: Session) {
+        if session.tabs.is_empty() {
+            return;
+        }
+        let tabs: Vec<Workspace> = session
+            .tabs
+            .into_iter()
+            .map(|saved| self.restore_tab(saved))
+            .collect();
+        for tab in &mut self.tabs {
+            tab.close();
+        }
+        self.active = session.active_tab.min(tabs.len() - 1);
+        self.tabs = tabs;
+    }
+
+    /// Panes missing from the saved layout are dropped and panes without
+    /// saved views show the dashboard.
+    fn restore_tab(&self, saved: SavedWorkspace) -> Workspace {
+        let mut views: BTreeMap<PaneId, _> = saved
+            .panes
+            .into_iter()
+            .map(|pane| (pane.id, pane.views))
+            .collect();
+        let panes: BTreeMap<PaneId, ViewManager> = saved
+            .layout
+            .panes()
+            .into_iter()
+            .map(|id| {
+                let mut pane = self.pane(ViewType::Dashboard);
+                pane.restore_views(views.remove(&id).unwrap_or_default());
+                (id, pane)
+            })
+            .collect();
+        let focused = if panes.contains_key(&saved.focused) {
+            saved.focused
+        } else {
+            *panes.keys().next().expect("a layout has at least one pane")
+        };
+        Workspace {
+            name: saved.name,
+            layout: saved.layout,
+            panes,
+            focused,
+            area: Rect::default(),
+        }
+    }
+
+    /// Drop views about dataflows or nodes that no longer exist from every
+    /// pane. Returns what was dropped.
+    pub fn drop_stale_views(&mut self, app_state: &AppState) -> Vec<String> {
+        self.panes_mut()
+            .flat_map(|pane| pane.drop_stale_views(app_state))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspaces() -> Workspaces {
+        Workspaces::new(ViewType::DataflowExplorer, ThemeConfig::default())
+    }
+
+    fn focused_area(workspaces: &Workspaces) -> Rect {
+        let tab = workspaces.active();
+        tab.layout
+            .layout(tab.area)
+            .into_iter()
+            .find(|(id, _)| *id == tab.focused)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn panes_split_resize_and_close() {
+        let mut workspaces = workspaces();
+        workspaces.active_mut().area = Rect::new(0, 0, 100, 40);
+
+        // Explorer left, logs right, monitor along the bottom.
+        workspaces.split(SplitDirection::Vertical, Some(ViewType::SystemMonitor));
+        assert!(workspaces.active_mut().focus_towards(FocusDirection::Up));
+        workspaces.split(
+            SplitDirection::Horizontal,
+            Some(ViewType::LogViewer {
+                target: "system".to_string(),
+            }),
+        );
+        assert_eq!(workspaces.active().layout().panes(), vec![0, 2, 1]);
+        assert_eq!(focused_area(&workspaces), Rect::new(50, 0, 50, 20));
+
+        assert!(workspaces.active_mut().resize(10));
+        assert_eq!(focused_area(&workspaces), Rect::new(40, 0, 60, 20));
+        assert!(workspaces.active_mut().resize(-100));
+        assert!(!workspaces.active_mut().resize(-10));
+        assert_eq!(focused_area(&workspaces).width, 10);
+
+        assert!(workspaces.active_mut().focus_towards(FocusDirection::Down));
+        assert_eq!(workspaces.focused().current(), &ViewType::SystemMonitor);
+        assert!(!workspaces.active_mut().focus_towards(FocusDirection::Down));
+        assert!(workspaces.active_mut().focus_at(5, 5));
+        assert_eq!(workspaces.focused().current(), &ViewType::DataflowExplorer);
+
+        assert!(workspaces.close_pane());
+        assert_eq!(workspaces.active().layout().panes(), vec![2, 1]);
+        assert_eq!(focused_area(&workspaces), Rect::new(0, 0, 100, 20));
+        assert!(workspaces.close_pane());
+        assert!(!workspaces.close_pane());
+        assert_eq!(workspaces.active().pane_count(), 1);
+        assert_eq!(workspaces.focused().current(), &ViewType::SystemMonitor);
+    }
+
+    #[test]
+    fn tabs_keep_their_own_panes() {
+        let mut workspaces = workspaces();
+        workspaces.split(SplitDirection::Horizontal, None);
+        workspaces.new_tab(Some("logs".to_string()));
+        workspaces.focused_mut().switch(ViewType::Notifications);
+        assert_eq!(workspaces.active().pane_count(), 1);
+
+        workspaces.next_tab();
+        assert_eq!(workspaces.active().name(), "main");
+        assert_eq!(workspaces.active().pane_count(), 2);
+        workspaces.previous_tab();
+        workspaces.rename_tab("alerts".to_string());
+
+        let session = workspaces.session(&AppState::default());
+        let mut restored = Workspaces::new(ViewType::Dashboard, ThemeConfig::default());
+        restored.restore_session(session);
+        let names: Vec<&str> = restored.tabs().iter().map(Workspace::name).collect();
+        assert_eq!(names, ["main", "alerts"]);
+        assert_eq!(restored.active_index(), 1);
+        assert_eq!(restored.focused().current(), &ViewType::Notifications);
+
+        assert!(restored.close_tab());
+        assert!(!restored.close_tab());
+        assert_eq!(restored.active().pane_count(), 2);
+        assert_eq!(restored.focused().current(), &ViewType::DataflowExplorer);
+    }
+}
@@ -2,21 +2,26 @@ mod error;
 
 use std::{
     io::{BufRead, BufReader, Lines},
-    sync::Arc,
+    sync::{Arc, mpsc},
+    thread,
 };
 
 use chrono::Utc;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
 use reqwest::blocking::{Client, Response};
 use serde::{Serialize, de::DeserializeOwned};
 use tui_interface::{
-    CoordinatorClient, DataflowSummary as UiDataflowSummary, InterfaceError, LegacyCliService,
-    NodeSummary, PreferencesStore, SystemMetrics as UiSystemMetrics, TelemetryService,
-    UserPreferencesSnapshot as UiPreferencesSnapshot,
+    CoordinatorClient, DataflowController, DataflowSummary as UiDataflowSummary, InterfaceError,
+    LegacyCliService, LogQuery, LogRecord, LogService, LogSubscription, NodeSummary,
+    OperationHandle as UiOperationHandle, OperationStatus as UiOperationStatus, PreferencesStore,
+    StartDataflowRequest as UiStartDataflowRequest, SystemMetrics as UiSystemMetrics,
+    TelemetryService, UserPreferencesSnapshot as UiPreferencesSnapshot,
 };
 use url::Url;
 
 use dora_protocol::{
-    DataflowSummary, NodeDescriptor, NodeKind, NodeSource, NodeStatus, SystemMetrics,
+    DataflowSummary, LogEvent, LogLevel, NodeDescriptor, NodeKind, NodeSource, NodeStatus,
+    OperationHandle, OperationState, OperationStatus, StartDataflowRequest, SystemMetrics, UiMode,
     UserPreferencesSnapshot,
 };
 use uuid::Uuid;
@@ -41,6 +46,18 @@ impl ProtocolClients {
         })
     }
 
+    pub fn dataflow_controller(&self) -> Arc<dyn DataflowController> {
+        Arc::new(ProtocolDataflowController {
+            transport: Arc::clone(&self.transport),
+        })
+    }
+
+    pub fn log_service(&self) -> Arc<dyn LogService> {
+        Arc::new(ProtocolLogService {
+            clients: self.clone(),
+        })
+    }
+
     pub fn telemetry_service(&self) -> Arc<dyn TelemetryService> {
         Arc::new(ProtocolTelemetryService {
             transport: Arc::clone(&self.transport),
@@ -93,6 +110,27 @@ impl Transport {
         Ok(())
     }
 
+    fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, error::ProtocolClientError> {
+        let url = self.endpoint(path)?;
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()?
+            .error_for_status()?;
+        Ok(response.json()?)
+    }
+
+    fn delete<T: DeserializeOwned>(&self, path: &str) -> Result<T, error::ProtocolClientError> {
+        let url = self.endpoint(path)?;
+        let response = self.client.delete(url).send()?.error_for_status()?;
+        Ok(response.json()?)
+    }
+
     fn get_stream(&self, path: &str) -> Result<Response, error::ProtocolClientError> {
         let url = self.endpoint(path)?;
         let response = self.client.get(url).send()?.error_for_status()?;
@@ -100,6 +138,18 @@ impl Transport {
     }
 }
 
+/// Everything but the RFC 3986 unreserved characters.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encode `value` for use as one path segment or query value.
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, COMPONENT).to_string()
+}
+
 fn normalize_base_url(raw: &str) -> Result<Url, error::ProtocolClientError> {
     let mut parsed = Url::parse(raw)?;
     if !parsed.path().ends_with('/') {
@@ -128,6 +178,128 @@ impl CoordinatorClient for ProtocolCoordinatorClient {
     }
 }
 
+/// Lifecycle actions on the gateway routes `POST /v1/dataflows` (start),
+/// `POST /v1/dataflows/{dataflow}/stop`, `DELETE /v1/dataflows/{dataflow}`
+/// and `GET /v1/operations/{handle}`. `dora-protocol` only defines their
+/// payloads; a gateway without these routes answers 404, which surfaces as
+/// the error of the action.
+#[derive(Clone)]
+struct ProtocolDataflowController {
+    transport: Arc<Transport>,
+}
+
+impl DataflowController for ProtocolDataflowController {
+    fn start(&self, request: &UiStartDataflowRequest) -> Result<UiOperationHandle, InterfaceError> {
+        let descriptor = std::fs::read_to_string(&request.descriptor_path).map_err(|err| {
+            InterfaceError::from(format!(
+                "failed to read {}: {err}",
+                request.descriptor_path.display()
+            ))
+        })?;
+        let payload = StartDataflowRequest {
+            descriptor,
+            name: request.name.clone(),
+            uv: request.uv,
+        };
+        let handle: OperationHandle = self
+            .transport
+            .post("/v1/dataflows", &payload)
+            .map_err(InterfaceError::from_proto_error)?;
+        Ok(map_handle_to_ui(handle))
+    }
+
+    fn stop(&self, dataflow: &str) -> Result<UiOperationHandle, InterfaceError> {
+        let handle: OperationHandle = self
+            .transport
+            .post(&format!("/v1/dataflows/{}/stop", encode(dataflow)), &())
+            .map_err(InterfaceError::from_proto_error)?;
+        Ok(map_handle_to_ui(handle))
+    }
+
+    fn destroy(&self, dataflow: &str) -> Result<UiOperationHandle, InterfaceError> {
+        let handle: OperationHandle = self
+            .transport
+            .delete(&format!("/v1/dataflows/{}", encode(dataflow)))
+            .map_err(InterfaceError::from_proto_error)?;
+        Ok(map_handle_to_ui(handle))
+    }
+
+    fn operation_status(
+        &self,
+        handle: &UiOperationHandle,
+    ) -> Result<UiOperationStatus, InterfaceError> {
+        let status: OperationStatus = self
+            .transport
+            .get(&format!("/v1/operations/{}", encode(&handle.id)))
+            .map_err(InterfaceError::from_proto_error)?;
+        Ok(map_operation_status_to_ui(status))
+    }
+}
+
+#[derive(Clone)]
+struct ProtocolLogService {
+    clients: ProtocolClients,
+}
+
+impl ProtocolLogService {
+    fn dataflow_id(query: &LogQuery) -> Result<Uuid, InterfaceError> {
+        Uuid::parse_str(&query.dataflow).map_err(|_| {
+            InterfaceError::from(format!(
+                "log streaming requires a dataflow UUID, got `{}`",
+                query.dataflow
+            ))
+        })
+    }
+}
+
+impl LogService for ProtocolLogService {
+    fn history(&self, query: &LogQuery, limit: usize) -> Result<Vec<LogRecord>, InterfaceError> {
+        let dataflow_id = Self::dataflow_id(query)?;
+        let mut path = format!("/v1/logs/{dataflow_id}?limit={limit}");
+        if let Some(node) = &query.node {
+            path.push_str(&format!("&node={}", encode(node)));
+        }
+        let events: Vec<LogEvent> = self
+            .clients
+            .transport
+            .get(&path)
+            .map_err(InterfaceError::from_proto_error)?;
+
+        let mut records: Vec<_> = events
+            .into_iter()
+            .filter(|event| query.matches_node(event.node.as_deref()))
+            .map(map_log_event_to_ui)
+            .collect();
+        let skip = records.len().saturating_sub(limit);
+        Ok(records.split_off(skip))
+    }
+
+    fn subscribe(&self, query: &LogQuery) -> Result<LogSubscription, InterfaceError> {
+        let dataflow_id = Self::dataflow_id(query)?;
+        let stream = self
+            .clients
+            .log_stream(&dataflow_id)
+            .map_err(InterfaceError::from_proto_error)?;
+
+        let (tx, rx) = mpsc::channel();
+        let query = query.clone();
+        thread::spawn(move || {
+            for event in stream {
+                let Ok(event) = event else {
+                    break;
+                };
+                if !query.matches_node(event.node.as_deref()) {
+                    continue;
+                }
+                if tx.send(map_log_event_to_ui(event)).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(LogSubscription::new(rx))
+    }
+}
+
 #[derive(Clone)]
 struct ProtocolTelemetryService {
     transport: Arc<Transport>,
@@ -187,7 +359,7 @@ fn map_summary_to_ui(summary: DataflowSummary) -> UiDataflowSummary {
     UiDataflowSummary {
         id: id.to_string(),
         name,
-        status: format_status(summary.status),
+        status: map_status_to_ui(summary.status),
         nodes,
     }
 }
@@ -196,8 +368,8 @@ fn map_node_to_ui(node: NodeDescriptor) -> NodeSummary {
     NodeSummary {
         id: node.id,
         name: node.name.unwrap_or_default(),
-        status: format_node_status(node.status),
-        kind: format_node_kind(node.kind),
+        status: map_node_status_to_ui(node.status),
+        kind: map_node_kind_to_ui(node.kind),
         description: node.description,
         inputs: node.inputs,
         outputs: node.outputs,
@@ -225,32 +397,68 @@ fn describe_node_source(source: &NodeSource) -> Option<String> {
     }
 }
 
-fn format_status(status: dora_protocol::DataflowStatus) -> String {
+fn map_status_to_ui(status: dora_protocol::DataflowStatus) -> tui_interface::DataflowStatus {
+    use tui_interface::DataflowStatus as Ui;
     match status {
-        dora_protocol::DataflowStatus::Pending => "pending".into(),
-        dora_protocol::DataflowStatus::Running => "running".into(),
-        dora_protocol::DataflowStatus::Stopped => "stopped".into(),
-        dora_protocol::DataflowStatus::Destroyed => "destroyed".into(),
-        dora_protocol::DataflowStatus::Failed => "failed".into(),
-        dora_protocol::DataflowStatus::Unknown => "unknown".into(),
+        dora_protocol::DataflowStatus::Pending => Ui::Pending,
+        dora_protocol::DataflowStatus::Running => Ui::Running,
+        dora_protocol::DataflowStatus::Stopped => Ui::Stopped,
+        dora_protocol::DataflowStatus::Destroyed => Ui::Destroyed,
+        dora_protocol::DataflowStatus::Failed => Ui::Failed,
+        dora_protocol::DataflowStatus::Unknown => Ui::Unknown,
     }
 }
 
-fn format_node_kind(kind: NodeKind) -> String {
+fn map_node_kind_to_ui(kind: NodeKind) -> tui_interface::NodeKind {
     match kind {
-        NodeKind::Runtime => "runtime".into(),
-        NodeKind::Operator => "operator".into(),
-        NodeKind::Custom => "custom".into(),
+        NodeKind::Runtime => tui_interface::NodeKind::Runtime,
+        NodeKind::Operator => tui_interface::NodeKind::Operator,
+        NodeKind::Custom => tui_interface::NodeKind::Custom,
     }
 }
 
-fn format_node_status(status: NodeStatus) -> String {
+fn map_node_status_to_ui(status: NodeStatus) -> tui_interface::NodeStatus {
+    use tui_interface::NodeStatus as Ui;
     match status {
-        NodeStatus::Initializing => "initializing".into(),
-        NodeStatus::Running => "running".into(),
-        NodeStatus::Stopped => "stopped".into(),
-        NodeStatus::Failed => "failed".into(),
-        NodeStatus::Unknown => "unknown".into(),
+        NodeStatus::Initializing => Ui::Initializing,
+        NodeStatus::Running => Ui::Running,
+        NodeStatus::Stopped => Ui::Stopped,
+        NodeStatus::Failed => Ui::Failed,
+        NodeStatus::Unknown => Ui::Unknown,
+    }
+}
+
+fn map_log_event_to_ui(event: LogEvent) -> LogRecord {
+    let level = match event.level {
+        LogLevel::Error => tui_interface::LogLevel::Error,
+        LogLevel::Warn => tui_interface::LogLevel::Warn,
+        LogLevel::Info => tui_interface::LogLevel::Info,
+        LogLevel::Debug => tui_interface::LogLevel::Debug,
+        LogLevel::Trace => tui_interface::LogLevel::Trace,
+    };
+    LogRecord {
+        timestamp: event.timestamp.into(),
+        level,
+        node: event.node,
+        message: event.line,
+    }
+}
+
+fn map_handle_to_ui(handle: OperationHandle) -> UiOperationHandle {
+    UiOperationHandle { id: handle.handle }
+}
+
+fn map_operation_status_to_ui(status: OperationStatus) -> UiOperationStatus {
+    let state = match status.state {
+        OperationState::Pending => tui_interface::OperationState::Pending,
+        OperationState::Running => tui_interface::OperationState::Running,
+        OperationState::Completed => tui_interface::OperationState::Completed,
+        OperationState::Failed => tui_interface::OperationState::Failed,
+    };
+    UiOperationStatus {
+        handle: UiOperationHandle { id: status.handle },
+        state,
+        message: status.message,
     }
 }
 
@@ -274,6 +482,7 @@ fn map_metrics_to_ui(snapshot: SystemMetrics) -> UiSystemMetrics {
             ..Default::default()
         },
         load_average,
+        source: tui_interface::MetricsSource::Gateway,
         ..Default::default()
     }
 }
@@ -287,9 +496,14 @@ fn map_preferences_to_ui(snapshot: UserPreferencesSnapshot) -> UiPreferencesSnap
             0
         },
         show_system_info: snapshot.auto_refresh.unwrap_or(true),
-        default_view: snapshot
-            .ui_mode
-            .map(|mode| format!("{mode:?}").to_lowercase()),
+        // The gateway has no notion of a start view.
+        default_view: None,
+        ui_mode: snapshot.ui_mode.map(map_ui_mode_to_ui),
+        // Mouse capture, accessibility and notifications are local terminal
+        // settings.
+        mouse_capture: true,
+        accessible: false,
+        notify_on: Vec::new(),
     }
 }
 
@@ -297,12 +511,30 @@ fn map_preferences_to_protocol(prefs: &UiPreferencesSnapshot) -> UserPreferences
     let auto_refresh = prefs.auto_refresh_interval_secs > 0;
     UserPreferencesSnapshot {
         theme: Some(prefs.theme.clone()),
-        ui_mode: None,
+        ui_mode: prefs.ui_mode.map(map_ui_mode_to_protocol),
         auto_refresh: Some(auto_refresh),
         updated_at: Utc::now(),
     }
 }
 
+fn map_ui_mode_to_ui(mode: UiMode) -> tui_interface::UiMode {
+    match mode {
+        UiMode::Auto => tui_interface::UiMode::Auto,
+        UiMode::Cli => tui_interface::UiMode::Cli,
+        UiMode::Tui => tui_interface::UiMode::Tui,
+        UiMode::Minimal => tui_interface::UiMode::Minimal,
+    }
+}
+
+fn map_ui_mode_to_protocol(mode: tui_interface::UiMode) -> UiMode {
+    match mode {
+        tui_interface::UiMode::Auto => UiMode::Auto,
+        tui_interface::UiMode::Cli => UiMode::Cli,
+        tui_interface::UiMode::Tui => UiMode::Tui,
+        tui_interface::UiMode::Minimal => UiMode::Minimal,
+    }
+}
+
 impl From<error::ProtocolClientError> for InterfaceError {
     fn from(value: error::ProtocolClientError) -> Self {
         InterfaceError::Message(value.to_string())
@@ -418,6 +650,39 @@ mod tests {
     use chrono::Utc;
     use std::io::Cursor;
 
+    #[test]
+    fn preferences_keep_theme_and_ui_mode() {
+        let prefs = UiPreferencesSnapshot {
+            theme: "light".to_string(),
+            ui_mode: Some(tui_interface::UiMode::Minimal),
+            ..Default::default()
+        };
+
+        let payload = map_preferences_to_protocol(&prefs);
+        assert_eq!(payload.ui_mode, Some(UiMode::Minimal));
+
+        let back = map_preferences_to_ui(payload);
+        assert_eq!(back.theme, "light");
+        assert_eq!(back.ui_mode, Some(tui_interface::UiMode::Minimal));
+        assert_eq!(back.default_view, None);
+    }
+
+    #[test]
+    fn dataflow_names_stay_one_path_segment() {
+        let transport = Transport {
+            client: Client::new(),
+            base: normalize_base_url("http://127.0.0.1:7267").unwrap(),
+        };
+        let url = transport
+            .endpoint(&format!("/v1/dataflows/{}/stop", encode("cam/front ?#1")))
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://127.0.0.1:7267/v1/dataflows/cam%2Ffront%20%3F%231/stop"
+        );
+        assert_eq!(encode("détecteur-1.2_x~"), "d%C3%A9tecteur-1.2_x~");
+    }
+
     #[test]
     fn parse_single_sse_event() {
         let data = b"event: log\ndata: {\"message\":\"hello\"}\n\n";
@@ -475,4 +740,35 @@ mod tests {
 
         assert!(stream.next().is_none());
     }
+
+    #[test]
+    fn operation_status_maps_to_ui() {
+        let status = OperationStatus {
+            handle: "op-1".to_string(),
+            state: OperationState::Failed,
+            message: Some("descriptor invalid".to_string()),
+            result: None,
+        };
+
+        let mapped = map_operation_status_to_ui(status);
+        assert_eq!(mapped.handle.id, "op-1");
+        assert_eq!(mapped.state, tui_interface::OperationState::Failed);
+        assert!(mapped.state.is_finished());
+        assert_eq!(mapped.message.as_deref(), Some("descriptor invalid"));
+    }
+
+    #[test]
+    fn log_events_map_to_records() {
+        let event = LogEvent {
+            timestamp: Utc::now(),
+            level: LogLevel::Warn,
+            node: Some("camera".to_string()),
+            line: "frame dropped".to_string(),
+        };
+
+        let record = map_log_event_to_ui(event);
+        assert_eq!(record.level, tui_interface::LogLevel::Warn);
+        assert_eq!(record.node.as_deref(), Some("camera"));
+        assert_eq!(record.message, "frame dropped");
+    }
 }
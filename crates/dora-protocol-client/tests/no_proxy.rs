@@ -54,7 +54,7 @@ fn protocol_client_ignores_proxy_environment() {
 
     assert_eq!(summaries.len(), 1);
     assert_eq!(summaries[0].name, "demo");
-    assert_eq!(summaries[0].status, "running");
+    assert_eq!(summaries[0].status, tui_interface::DataflowStatus::Running);
 
     unsafe {
         std::env::remove_var("HTTP_PROXY");
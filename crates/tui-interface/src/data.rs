@@ -4,28 +4,35 @@
 //! rest of the codebase on these shared definitions so the boundary becomes
 //! the single source of truth.
 
-use std::time::{Duration, Instant};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::status::{DataflowStatus, NodeKind, NodeStatus};
 
 /// Summary information about a running or archived dataflow.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataflowSummary {
     /// Unique identifier (UUID string) for the dataflow.
     pub id: String,
     /// Display name (falls back to `id` when unset).
     pub name: String,
-    /// Human-readable status string (e.g., "running", "failed").
-    pub status: String,
+    /// Lifecycle state reported by the backend.
+    pub status: DataflowStatus,
     /// Details about nodes inside the dataflow.
     pub nodes: Vec<NodeSummary>,
 }
 
 /// Details about a single node within a dataflow.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeSummary {
     pub id: String,
     pub name: String,
-    pub status: String,
-    pub kind: String,
+    pub status: NodeStatus,
+    pub kind: NodeKind,
     pub description: Option<String>,
     pub inputs: Vec<String>,
     pub outputs: Vec<String>,
@@ -34,14 +41,17 @@ pub struct NodeSummary {
 }
 
 /// Detailed runtime metadata for a node used by the inspector.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeResolvedDescriptor {
     pub kind: NodeResolvedKind,
     pub env: Vec<(String, String)>,
 }
 
 /// Simplified node kind variants required by the TUI.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum NodeResolvedKind {
     Custom(NodeExecutableDescriptor),
     Runtime(NodeRuntimeDescriptor),
@@ -50,7 +60,8 @@ pub enum NodeResolvedKind {
 }
 
 /// Executable metadata for custom nodes.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeExecutableDescriptor {
     pub path: String,
     pub args: Option<String>,
@@ -58,20 +69,187 @@ pub struct NodeExecutableDescriptor {
 }
 
 /// Runtime metadata for built-in runtime nodes.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeRuntimeDescriptor {
     pub operators: Vec<NodeRuntimeOperator>,
 }
 
 /// Minimal operator information exposed to the UI.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeRuntimeOperator {
     pub id: String,
     pub name: String,
 }
 
+/// Request to launch a dataflow from a descriptor file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StartDataflowRequest {
+    /// Path to the YAML descriptor.
+    pub descriptor_path: PathBuf,
+    /// Optional name to assign to the launched dataflow.
+    pub name: Option<String>,
+    /// Whether Python nodes should be run through `uv`.
+    pub uv: bool,
+}
+
+/// Lifecycle action a view can request without knowing the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DataflowCommand {
+    Start(StartDataflowRequest),
+    /// Stop the dataflow with the given UUID or name.
+    Stop(String),
+    /// Destroy the dataflow with the given UUID or name.
+    Destroy(String),
+}
+
+impl DataflowCommand {
+    /// Short verb used in status messages.
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Self::Start(_) => "start",
+            Self::Stop(_) => "stop",
+            Self::Destroy(_) => "destroy",
+        }
+    }
+
+    /// The dataflow (or descriptor) the command targets.
+    pub fn target(&self) -> String {
+        match self {
+            Self::Start(request) => request
+                .name
+                .clone()
+                .unwrap_or_else(|| request.descriptor_path.display().to_string()),
+            Self::Stop(dataflow) | Self::Destroy(dataflow) => dataflow.clone(),
+        }
+    }
+}
+
+/// Handle returned when a lifecycle operation has been submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperationHandle {
+    pub id: String,
+}
+
+/// Progress of a submitted lifecycle operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OperationState {
+    #[default]
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl OperationState {
+    /// Whether the operation has reached a final state.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+/// Latest known status of a lifecycle operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperationStatus {
+    pub handle: OperationHandle,
+    pub state: OperationState,
+    /// Backend supplied detail, usually the failure reason.
+    pub message: Option<String>,
+}
+
+/// Severity attached to a log record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+/// A single log line emitted by a dataflow or one of its nodes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogRecord {
+    pub timestamp: SystemTime,
+    pub level: LogLevel,
+    /// Node that produced the line; `None` for dataflow/daemon messages.
+    pub node: Option<String>,
+    pub message: String,
+}
+
+/// Selects the logs a history query or subscription covers.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogQuery {
+    /// Dataflow UUID or name.
+    pub dataflow: String,
+    /// Restrict to a single node.
+    pub node: Option<String>,
+}
+
+impl LogQuery {
+    pub fn dataflow(dataflow: impl Into<String>) -> Self {
+        Self {
+            dataflow: dataflow.into(),
+            node: None,
+        }
+    }
+
+    pub fn with_node(mut self, node: impl Into<String>) -> Self {
+        self.node = Some(node.into());
+        self
+    }
+
+    /// Whether a record emitted by `node` falls within this query.
+    pub fn matches_node(&self, node: Option<&str>) -> bool {
+        match &self.node {
+            Some(wanted) => node == Some(wanted.as_str()),
+            None => true,
+        }
+    }
+}
+
+/// Where a [`SystemMetrics`] snapshot was collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MetricsSource {
+    /// Reported by the protocol gateway.
+    Gateway,
+    /// Collected on the machine running the TUI.
+    Local,
+    /// Gateway figures with the fields it does not report filled in locally.
+    Merged,
+    #[default]
+    Unknown,
+}
+
+impl MetricsSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Gateway => "gateway",
+            Self::Local => "local",
+            Self::Merged => "gateway + local",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// High-level system metrics snapshot used by the dashboard and monitor views.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SystemMetrics {
     pub cpu_usage: f32,
     pub memory_usage: f32,
@@ -82,7 +260,10 @@ pub struct SystemMetrics {
     pub load_average: Option<LoadAverages>,
     pub uptime: Duration,
     pub process_count: usize,
+    /// Monotonic time of collection; not meaningful outside this process.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub last_update: Option<Instant>,
+    pub source: MetricsSource,
 }
 
 impl Default for SystemMetrics {
@@ -98,12 +279,14 @@ impl Default for SystemMetrics {
             uptime: Duration::default(),
             process_count: 0,
             last_update: None,
+            source: MetricsSource::Unknown,
         }
     }
 }
 
 /// Detailed memory usage information.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryMetrics {
     pub total_bytes: u64,
     pub used_bytes: u64,
@@ -115,7 +298,8 @@ pub struct MemoryMetrics {
 }
 
 /// Disk utilisation snapshot.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DiskMetrics {
     pub total_bytes: u64,
     pub used_bytes: u64,
@@ -123,7 +307,8 @@ pub struct DiskMetrics {
 }
 
 /// Network throughput and totals.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NetworkMetrics {
     pub total_received: u64,
     pub total_transmitted: u64,
@@ -132,7 +317,8 @@ pub struct NetworkMetrics {
 }
 
 /// Load averages over different windows.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoadAverages {
     pub one: f64,
     pub five: f64,
@@ -161,11 +347,66 @@ impl Default for SystemMetricsSample {
     }
 }
 
+/// Interface a user prefers when launching Dora.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum UiMode {
+    #[default]
+    Auto,
+    Cli,
+    Tui,
+    Minimal,
+}
+
+impl UiMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Cli => "cli",
+            Self::Tui => "tui",
+            Self::Minimal => "minimal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "cli" => Some(Self::Cli),
+            "tui" => Some(Self::Tui),
+            "minimal" => Some(Self::Minimal),
+            _ => None,
+        }
+    }
+}
+
 /// Minimal user preference snapshot shared between CLI and TUI.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserPreferencesSnapshot {
     pub theme: String,
     pub auto_refresh_interval_secs: u64,
     pub show_system_info: bool,
     pub default_view: Option<String>,
+    pub ui_mode: Option<UiMode>,
+    pub mouse_capture: bool,
+    /// ASCII output, the high-contrast theme and no blinking
+    pub accessible: bool,
+    /// Events announced to the terminal, e.g. `dataflow_failed`
+    pub notify_on: Vec<String>,
+}
+
+impl Default for UserPreferencesSnapshot {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            auto_refresh_interval_secs: 5,
+            show_system_info: true,
+            default_view: None,
+            ui_mode: None,
+            mouse_capture: true,
+            accessible: false,
+            notify_on: Vec::new(),
+        }
+    }
 }
@@ -1,15 +1,23 @@
 //! Shared boundary between the Dora framework and the TUI.
 //!
 //! See [`ADR-001`](../../../docs/tui-boundary/adr-001-tui-interface-boundary.md) for context.
+//!
+//! The `serde` feature makes the data contracts serializable.
 
 pub mod data;
 pub mod error;
 pub mod services;
+pub mod status;
 
 pub use data::*;
 pub use error::*;
 pub use services::*;
+pub use status::*;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod mocks;
 #[cfg(any(test, feature = "test-utils"))]
 pub use mocks::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod scenario;
+#[cfg(any(test, feature = "test-utils"))]
+pub use scenario::*;
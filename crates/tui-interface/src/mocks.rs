@@ -1,11 +1,17 @@
 use std::{
     path::{Path, PathBuf},
-    sync::Mutex,
+    sync::{
+        Mutex,
+        mpsc::{self, Sender},
+    },
+    time::Duration,
 };
 
 use crate::{
-    CoordinatorClient, DataflowSummary, InterfaceError, LegacyCliService, PreferencesStore,
-    SystemMetrics, TelemetryService, UserPreferencesSnapshot,
+    CoordinatorClient, DataflowCommand, DataflowController, DataflowSummary, InterfaceError,
+    LegacyCliService, LogQuery, LogRecord, LogService, LogSubscription, OperationHandle,
+    OperationState, OperationStatus, PreferencesStore, StartDataflowRequest, SystemMetrics,
+    TelemetryService, UserPreferencesSnapshot,
 };
 
 pub struct MockCoordinatorClient {
@@ -36,6 +42,79 @@ impl CoordinatorClient for MockCoordinatorClient {
     }
 }
 
+pub struct MockDataflowController {
+    pub calls: Mutex<Vec<DataflowCommand>>,
+    result: Mutex<Result<OperationHandle, InterfaceError>>,
+    status: Mutex<Result<OperationState, InterfaceError>>,
+    latency: Mutex<Duration>,
+}
+
+impl Default for MockDataflowController {
+    fn default() -> Self {
+        Self {
+            calls: Mutex::new(Vec::new()),
+            result: Mutex::new(Ok(OperationHandle {
+                id: "mock-operation".to_string(),
+            })),
+            status: Mutex::new(Ok(OperationState::Completed)),
+            latency: Mutex::new(Duration::ZERO),
+        }
+    }
+}
+
+impl MockDataflowController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Result returned by `start`, `stop` and `destroy`.
+    pub fn set_result(&self, result: Result<OperationHandle, InterfaceError>) {
+        *self.result.lock().unwrap() = result;
+    }
+
+    /// State reported by `operation_status` for any handle.
+    pub fn set_status(&self, status: Result<OperationState, InterfaceError>) {
+        *self.status.lock().unwrap() = status;
+    }
+
+    /// Block `start`, `stop` and `destroy` for `latency` of real time.
+    pub fn set_latency(&self, latency: Duration) {
+        *self.latency.lock().unwrap() = latency;
+    }
+
+    fn record(&self, call: DataflowCommand) -> Result<OperationHandle, InterfaceError> {
+        std::thread::sleep(*self.latency.lock().unwrap());
+        self.calls.lock().unwrap().push(call);
+        self.result.lock().unwrap().clone()
+    }
+}
+
+impl DataflowController for MockDataflowController {
+    fn start(&self, request: &StartDataflowRequest) -> Result<OperationHandle, InterfaceError> {
+        self.record(DataflowCommand::Start(request.clone()))
+    }
+
+    fn stop(&self, dataflow: &str) -> Result<OperationHandle, InterfaceError> {
+        self.record(DataflowCommand::Stop(dataflow.to_string()))
+    }
+
+    fn destroy(&self, dataflow: &str) -> Result<OperationHandle, InterfaceError> {
+        self.record(DataflowCommand::Destroy(dataflow.to_string()))
+    }
+
+    fn operation_status(
+        &self,
+        handle: &OperationHandle,
+    ) -> Result<OperationStatus, InterfaceError> {
+        let state = self.status.lock().unwrap().clone()?;
+        Ok(OperationStatus {
+            handle: handle.clone(),
+            state,
+            message: None,
+        })
+    }
+}
+
 pub struct MockLegacyCliService {
     pub calls: Mutex<Vec<(Vec<String>, PathBuf)>>,
     result: Mutex<Result<(), InterfaceError>>,
@@ -70,6 +149,66 @@ impl LegacyCliService for MockLegacyCliService {
     }
 }
 
+pub struct MockLogService {
+    history: Mutex<Result<Vec<LogRecord>, InterfaceError>>,
+    subscribers: Mutex<Vec<(LogQuery, Sender<LogRecord>)>>,
+    pub subscriptions: Mutex<Vec<LogQuery>>,
+}
+
+impl Default for MockLogService {
+    fn default() -> Self {
+        Self {
+            history: Mutex::new(Ok(Vec::new())),
+            subscribers: Mutex::new(Vec::new()),
+            subscriptions: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl MockLogService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_history(&self, history: Result<Vec<LogRecord>, InterfaceError>) {
+        *self.history.lock().unwrap() = history;
+    }
+
+    /// Deliver a record to every live subscription whose query matches it.
+    pub fn emit(&self, record: LogRecord) {
+        self.subscribers.lock().unwrap().retain(|(query, sender)| {
+            if !query.matches_node(record.node.as_deref()) {
+                return true;
+            }
+            sender.send(record.clone()).is_ok()
+        });
+    }
+
+    /// Drop all subscriptions, as if the backend stream had ended.
+    pub fn disconnect(&self) {
+        self.subscribers.lock().unwrap().clear();
+    }
+}
+
+impl LogService for MockLogService {
+    fn history(&self, query: &LogQuery, limit: usize) -> Result<Vec<LogRecord>, InterfaceError> {
+        let records = self.history.lock().unwrap().clone()?;
+        let mut matching: Vec<_> = records
+            .into_iter()
+            .filter(|record| query.matches_node(record.node.as_deref()))
+            .collect();
+        let skip = matching.len().saturating_sub(limit);
+        Ok(matching.split_off(skip))
+    }
+
+    fn subscribe(&self, query: &LogQuery) -> Result<LogSubscription, InterfaceError> {
+        let (tx, rx) = mpsc::channel();
+        self.subscriptions.lock().unwrap().push(query.clone());
+        self.subscribers.lock().unwrap().push((query.clone(), tx));
+        Ok(LogSubscription::new(rx))
+    }
+}
+
 pub struct MockTelemetryService {
     response: Mutex<Result<SystemMetrics, InterfaceError>>,
 }
@@ -106,12 +245,7 @@ pub struct MockPreferencesStore {
 impl Default for MockPreferencesStore {
     fn default() -> Self {
         Self {
-            load_result: Mutex::new(Ok(UserPreferencesSnapshot {
-                theme: "dark".to_string(),
-                auto_refresh_interval_secs: 5,
-                show_system_info: true,
-                default_view: None,
-            })),
+            load_result: Mutex::new(Ok(UserPreferencesSnapshot::default())),
             save_result: Mutex::new(Ok(())),
         }
     }
@@ -0,0 +1,330 @@
+//! Scripted fake backends for tests.
+//!
+//! A [`Scenario`] is a timeline of changes to the dataflow list and to the
+//! system metrics. A [`ScenarioBackend`] replays it against a [`ManualClock`]
+//! and answers [`CoordinatorClient`] and [`TelemetryService`] calls with the
+//! state at the clock's current time, so a test can say "at t=5s node
+//! `camera` fails" and then advance the clock past it.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    CoordinatorClient, DataflowStatus, DataflowSummary, InterfaceError, NodeStatus, NodeSummary,
+    SystemMetrics, TelemetryService,
+};
+
+/// Clock that only moves when a test tells it to.
+///
+/// Clones share the same time, so a test keeps one handle and gives another
+/// to the backend.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time since the start of the scenario.
+    pub fn now(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    pub fn set(&self, elapsed: Duration) {
+        *self.elapsed.lock().unwrap() = elapsed;
+    }
+}
+
+/// A single change applied to the backend state.
+#[derive(Debug, Clone)]
+pub enum ScenarioEvent {
+    /// Add a dataflow, replacing any existing one with the same id.
+    AddDataflow(DataflowSummary),
+    RemoveDataflow(String),
+    DataflowStatus {
+        dataflow: String,
+        status: DataflowStatus,
+    },
+    /// Change a node's status. The node is added if the dataflow does not
+    /// list it yet.
+    NodeStatus {
+        dataflow: String,
+        node: String,
+        status: NodeStatus,
+    },
+    /// Replace the whole metrics snapshot.
+    Metrics(SystemMetrics),
+    Cpu(f32),
+    Memory(f32),
+    /// Make `list_dataflows` fail until [`ScenarioEvent::CoordinatorRecovered`].
+    CoordinatorError(InterfaceError),
+    CoordinatorRecovered,
+    /// Make `latest_metrics` fail until [`ScenarioEvent::TelemetryRecovered`].
+    TelemetryError(InterfaceError),
+    TelemetryRecovered,
+}
+
+/// Initial state plus a timeline of [`ScenarioEvent`]s.
+#[derive(Debug, Clone, Default)]
+pub struct Scenario {
+    dataflows: Vec<DataflowSummary>,
+    metrics: SystemMetrics,
+    timeline: Vec<(Duration, ScenarioEvent)>,
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Dataflow present from the start.
+    pub fn with_dataflow(mut self, dataflow: DataflowSummary) -> Self {
+        self.dataflows.push(dataflow);
+        self
+    }
+
+    /// Metrics reported from the start.
+    pub fn with_metrics(mut self, metrics: SystemMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Schedule `event` at `at` after the start. Events at the same time are
+    /// applied in the order they were added.
+    pub fn at(mut self, at: Duration, event: ScenarioEvent) -> Self {
+        let index = self.timeline.partition_point(|(time, _)| *time <= at);
+        self.timeline.insert(index, (at, event));
+        self
+    }
+
+    /// Move CPU usage linearly from `from` to `to` percent between `start`
+    /// and `end`, one step per second.
+    pub fn ramp_cpu(mut self, start: Duration, end: Duration, from: f32, to: f32) -> Self {
+        let seconds = end.saturating_sub(start).as_secs().max(1);
+        for step in 0..=seconds {
+            let value = from + (to - from) * step as f32 / seconds as f32;
+            self = self.at(start + Duration::from_secs(step), ScenarioEvent::Cpu(value));
+        }
+        self
+    }
+
+    /// Time of the last scheduled event.
+    pub fn duration(&self) -> Duration {
+        self.timeline
+            .last()
+            .map(|(time, _)| *time)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ScenarioState {
+    dataflows: Vec<DataflowSummary>,
+    metrics: SystemMetrics,
+    coordinator_error: Option<InterfaceError>,
+    telemetry_error: Option<InterfaceError>,
+}
+
+impl ScenarioState {
+    fn apply(&mut self, event: &ScenarioEvent) {
+        match event {
+            ScenarioEvent::AddDataflow(dataflow) => {
+                self.dataflows.retain(|df| df.id != dataflow.id);
+                self.dataflows.push(dataflow.clone());
+            }
+            ScenarioEvent::RemoveDataflow(id) => self.dataflows.retain(|df| &df.id != id),
+            ScenarioEvent::DataflowStatus { dataflow, status } => {
+                if let Some(df) = self.dataflow_mut(dataflow) {
+                    df.status = *status;
+                }
+            }
+            ScenarioEvent::NodeStatus {
+                dataflow,
+                node,
+                status,
+            } => {
+                if let Some(df) = self.dataflow_mut(dataflow) {
+                    match df.nodes.iter_mut().find(|n| &n.id == node) {
+                        Some(existing) => existing.status = *status,
+                        None => df.nodes.push(NodeSummary {
+                            id: node.clone(),
+                            name: node.clone(),
+                            status: *status,
+                            ..Default::default()
+                        }),
+                    }
+                }
+            }
+            ScenarioEvent::Metrics(metrics) => self.metrics = metrics.clone(),
+            ScenarioEvent::Cpu(value) => self.metrics.cpu_usage = *value,
+            ScenarioEvent::Memory(value) => self.metrics.memory_usage = *value,
+            ScenarioEvent::CoordinatorError(err) => self.coordinator_error = Some(err.clone()),
+            ScenarioEvent::CoordinatorRecovered => self.coordinator_error = None,
+            ScenarioEvent::TelemetryError(err) => self.telemetry_error = Some(err.clone()),
+            ScenarioEvent::TelemetryRecovered => self.telemetry_error = None,
+        }
+    }
+
+    fn dataflow_mut(&mut self, id: &str) -> Option<&mut DataflowSummary> {
+        self.dataflows
+            .iter_mut()
+            .find(|df| df.id == id || df.name == id)
+    }
+}
+
+/// Replays a [`Scenario`] as coordinator and telemetry backends.
+pub struct ScenarioBackend {
+    scenario: Scenario,
+    clock: ManualClock,
+    started: Instant,
+    latency: Duration,
+}
+
+impl ScenarioBackend {
+    pub fn new(scenario: Scenario, clock: ManualClock) -> Self {
+        Self {
+            scenario,
+            clock,
+            started: Instant::now(),
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Block every call for `latency` of real time, like a slow backend.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    /// Whether every scheduled event has been reached.
+    pub fn is_finished(&self) -> bool {
+        self.clock.now() >= self.scenario.duration()
+    }
+
+    fn state(&self) -> ScenarioState {
+        std::thread::sleep(self.latency);
+        let now = self.clock.now();
+        let mut state = ScenarioState {
+            dataflows: self.scenario.dataflows.clone(),
+            metrics: self.scenario.metrics.clone(),
+            coordinator_error: None,
+            telemetry_error: None,
+        };
+        for (_, event) in self
+            .scenario
+            .timeline
+            .iter()
+            .take_while(|(time, _)| *time <= now)
+        {
+            state.apply(event);
+        }
+        state
+    }
+}
+
+impl CoordinatorClient for ScenarioBackend {
+    fn list_dataflows(&self) -> Result<Vec<DataflowSummary>, InterfaceError> {
+        let state = self.state();
+        match state.coordinator_error {
+            Some(err) => Err(err),
+            None => Ok(state.dataflows),
+        }
+    }
+}
+
+impl TelemetryService for ScenarioBackend {
+    fn latest_metrics(&self) -> Result<SystemMetrics, InterfaceError> {
+        let state = self.state();
+        if let Some(err) = state.telemetry_error {
+            return Err(err);
+        }
+        let mut metrics = state.metrics;
+        // Report the sample as taken at the scenario time so consumers that
+        // compute rates see the clock move.
+        metrics.last_update = Some(self.started + self.clock.now());
+        Ok(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(id: &str) -> DataflowSummary {
+        DataflowSummary {
+            id: id.to_string(),
+            name: id.to_string(),
+            status: DataflowStatus::Running,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_apply_once_the_clock_reaches_them() {
+        let scenario = Scenario::new()
+            .with_dataflow(running("df-1"))
+            .at(
+                Duration::from_secs(5),
+                ScenarioEvent::NodeStatus {
+                    dataflow: "df-1".to_string(),
+                    node: "camera".to_string(),
+                    status: NodeStatus::Failed,
+                },
+            )
+            .at(
+                Duration::from_secs(2),
+                ScenarioEvent::AddDataflow(running("df-2")),
+            );
+        let clock = ManualClock::new();
+        let backend = ScenarioBackend::new(scenario, clock.clone());
+
+        assert_eq!(backend.list_dataflows().unwrap().len(), 1);
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(backend.list_dataflows().unwrap().len(), 2);
+        assert!(backend.list_dataflows().unwrap()[0].nodes.is_empty());
+
+        clock.advance(Duration::from_secs(3));
+        let dataflows = backend.list_dataflows().unwrap();
+        assert_eq!(dataflows[0].nodes[0].status, NodeStatus::Failed);
+        assert!(backend.is_finished());
+
+        // Rewinding replays from the initial state.
+        clock.set(Duration::ZERO);
+        assert_eq!(backend.list_dataflows().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn cpu_ramp_and_outages() {
+        let scenario = Scenario::new()
+            .ramp_cpu(Duration::ZERO, Duration::from_secs(4), 15.0, 95.0)
+            .at(
+                Duration::from_secs(2),
+                ScenarioEvent::TelemetryError(InterfaceError::Unimplemented),
+            )
+            .at(Duration::from_secs(3), ScenarioEvent::TelemetryRecovered);
+        let clock = ManualClock::new();
+        let backend = ScenarioBackend::new(scenario, clock.clone());
+
+        assert_eq!(backend.latest_metrics().unwrap().cpu_usage, 15.0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(backend.latest_metrics().unwrap().cpu_usage, 35.0);
+        clock.advance(Duration::from_secs(1));
+        assert!(backend.latest_metrics().is_err());
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(backend.latest_metrics().unwrap().cpu_usage, 95.0);
+    }
+}
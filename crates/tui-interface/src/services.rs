@@ -1,5 +1,53 @@
-use crate::{DataflowSummary, SystemMetrics, UserPreferencesSnapshot};
-use std::path::Path;
+use crate::{
+    DataflowCommand, DataflowSummary, LogQuery, LogRecord, OperationHandle, OperationStatus,
+    StartDataflowRequest, SystemMetrics, UserPreferencesSnapshot,
+};
+use std::{
+    ops::Deref,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::Receiver,
+    },
+};
+
+/// Live log feed. The sender side disconnects when the underlying stream
+/// ends; dropping the subscription cancels it.
+#[derive(Debug)]
+pub struct LogSubscription {
+    receiver: Receiver<LogRecord>,
+    closed: Arc<AtomicBool>,
+}
+
+impl LogSubscription {
+    pub fn new(receiver: Receiver<LogRecord>) -> Self {
+        Self {
+            receiver,
+            closed: Arc::default(),
+        }
+    }
+
+    /// A flag set once the subscription is dropped, for producers that may
+    /// go a long time without sending anything.
+    pub fn closed_flag(&self) -> Arc<AtomicBool> {
+        self.closed.clone()
+    }
+}
+
+impl Deref for LogSubscription {
+    type Target = Receiver<LogRecord>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
+
+impl Drop for LogSubscription {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+}
 
 /// Abstraction over coordinator interactions.
 pub trait CoordinatorClient: Send + Sync {
@@ -7,12 +55,54 @@ pub trait CoordinatorClient: Send + Sync {
     fn list_dataflows(&self) -> Result<Vec<DataflowSummary>, crate::InterfaceError>;
 }
 
+/// Lifecycle control over dataflows, independent of the backend.
+///
+/// Operations may complete asynchronously; callers poll
+/// [`DataflowController::operation_status`] with the returned handle.
+pub trait DataflowController: Send + Sync {
+    /// Launch a dataflow from a descriptor.
+    fn start(
+        &self,
+        request: &StartDataflowRequest,
+    ) -> Result<OperationHandle, crate::InterfaceError>;
+    /// Gracefully stop a running dataflow, identified by UUID or name.
+    fn stop(&self, dataflow: &str) -> Result<OperationHandle, crate::InterfaceError>;
+    /// Tear down a dataflow and release its resources.
+    fn destroy(&self, dataflow: &str) -> Result<OperationHandle, crate::InterfaceError>;
+    /// Look up the progress of a previously submitted operation.
+    fn operation_status(
+        &self,
+        handle: &OperationHandle,
+    ) -> Result<OperationStatus, crate::InterfaceError>;
+
+    /// Dispatch a [`DataflowCommand`] to the matching method.
+    fn submit(&self, command: &DataflowCommand) -> Result<OperationHandle, crate::InterfaceError> {
+        match command {
+            DataflowCommand::Start(request) => self.start(request),
+            DataflowCommand::Stop(dataflow) => self.stop(dataflow),
+            DataflowCommand::Destroy(dataflow) => self.destroy(dataflow),
+        }
+    }
+}
+
 /// Bridge that lets the TUI trigger legacy CLI operations.
 pub trait LegacyCliService: Send + Sync {
     /// Execute a CLI command expressed as argv tokens using the given working directory.
     fn execute(&self, argv: &[String], working_dir: &Path) -> Result<(), crate::InterfaceError>;
 }
 
+/// Access to dataflow and node logs.
+pub trait LogService: Send + Sync {
+    /// Fetch up to `limit` of the most recent records, oldest first.
+    fn history(
+        &self,
+        query: &LogQuery,
+        limit: usize,
+    ) -> Result<Vec<LogRecord>, crate::InterfaceError>;
+    /// Follow new records as they are produced.
+    fn subscribe(&self, query: &LogQuery) -> Result<LogSubscription, crate::InterfaceError>;
+}
+
 /// Provides system telemetry to the TUI.
 pub trait TelemetryService: Send + Sync {
     fn latest_metrics(&self) -> Result<SystemMetrics, crate::InterfaceError>;
@@ -0,0 +1,302 @@
+//! Status and kind vocabularies shared by every backend.
+//!
+//! The protocol gateway and the legacy CLI report states with slightly
+//! different words ("stopped" vs "finished", "exited" vs "completed"). The
+//! enums below are the single vocabulary the UI works with; backends convert
+//! into them at the boundary and views never compare raw strings.
+
+use std::fmt;
+
+/// Lifecycle state of a dataflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DataflowStatus {
+    Pending,
+    Running,
+    Stopped,
+    /// The dataflow ran to completion (legacy CLI wording).
+    Finished,
+    Failed,
+    Destroyed,
+    #[default]
+    Unknown,
+}
+
+impl DataflowStatus {
+    /// Canonical lowercase label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+            Self::Finished => "finished",
+            Self::Failed => "failed",
+            Self::Destroyed => "destroyed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+
+    /// Running or about to run.
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Pending | Self::Running)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed)
+    }
+
+    /// No longer running and did not fail (stopped, finished, destroyed or
+    /// an unrecognised state).
+    pub fn is_inactive(&self) -> bool {
+        !self.is_active() && !self.is_failed()
+    }
+
+    pub fn tone(&self) -> StatusTone {
+        match self {
+            Self::Pending => StatusTone::Pending,
+            Self::Running => StatusTone::Healthy,
+            Self::Stopped | Self::Destroyed => StatusTone::Stopped,
+            Self::Finished => StatusTone::Completed,
+            Self::Failed => StatusTone::Failed,
+            Self::Unknown => StatusTone::Unknown,
+        }
+    }
+}
+
+impl From<&str> for DataflowStatus {
+    /// Lenient conversion accepting the spellings used by both backends.
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pending" | "starting" | "spawning" => Self::Pending,
+            "running" | "active" => Self::Running,
+            "stopped" | "stopping" => Self::Stopped,
+            "finished" | "completed" | "succeeded" => Self::Finished,
+            "failed" | "error" => Self::Failed,
+            "destroyed" => Self::Destroyed,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for DataflowStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Lifecycle state of a single node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NodeStatus {
+    Initializing,
+    Running,
+    Stopped,
+    /// The node process exited (legacy CLI wording).
+    Exited,
+    /// The node finished its work successfully.
+    Completed,
+    Failed,
+    #[default]
+    Unknown,
+}
+
+impl NodeStatus {
+    /// Canonical lowercase label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Initializing => "initializing",
+            Self::Running => "running",
+            Self::Stopped => "stopped",
+            Self::Exited => "exited",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed)
+    }
+
+    pub fn tone(&self) -> StatusTone {
+        match self {
+            Self::Initializing => StatusTone::Pending,
+            Self::Running => StatusTone::Healthy,
+            Self::Stopped | Self::Exited => StatusTone::Stopped,
+            Self::Completed => StatusTone::Completed,
+            Self::Failed => StatusTone::Failed,
+            Self::Unknown => StatusTone::Unknown,
+        }
+    }
+}
+
+impl From<&str> for NodeStatus {
+    /// Lenient conversion accepting the spellings used by both backends.
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "initializing" | "pending" | "starting" => Self::Initializing,
+            "running" | "active" => Self::Running,
+            "stopped" => Self::Stopped,
+            "exited" => Self::Exited,
+            "completed" | "finished" => Self::Completed,
+            "failed" | "error" => Self::Failed,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for NodeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Kind of node as declared in the dataflow descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NodeKind {
+    Custom,
+    Runtime,
+    Operator,
+    #[default]
+    Unknown,
+}
+
+impl NodeKind {
+    /// Canonical lowercase label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Custom => "custom",
+            Self::Runtime => "runtime",
+            Self::Operator => "operator",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<&str> for NodeKind {
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "custom" => Self::Custom,
+            "runtime" => Self::Runtime,
+            "operator" => Self::Operator,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Presentation category for a status, independent of what it describes.
+///
+/// Renderers map tones to colours and symbols so dataflows, nodes and
+/// free-form health labels share one palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StatusTone {
+    Healthy,
+    Pending,
+    Warning,
+    Failed,
+    Stopped,
+    Completed,
+    Idle,
+    #[default]
+    Unknown,
+}
+
+impl From<&str> for StatusTone {
+    fn from(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "running" | "active" | "healthy" | "ok" | "success" => Self::Healthy,
+            "pending" | "starting" | "initializing" => Self::Pending,
+            "warning" | "degraded" | "slow" => Self::Warning,
+            "error" | "failed" | "critical" | "down" => Self::Failed,
+            "stopped" | "inactive" | "exited" | "destroyed" => Self::Stopped,
+            "finished" | "completed" => Self::Completed,
+            "info" | "idle" | "waiting" => Self::Idle,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl From<DataflowStatus> for StatusTone {
+    fn from(status: DataflowStatus) -> Self {
+        status.tone()
+    }
+}
+
+impl From<NodeStatus> for StatusTone {
+    fn from(status: NodeStatus) -> Self {
+        status.tone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dataflow_status_accepts_both_backend_spellings() {
+        assert_eq!(DataflowStatus::from("Running"), DataflowStatus::Running);
+        assert_eq!(DataflowStatus::from("finished"), DataflowStatus::Finished);
+        assert_eq!(DataflowStatus::from("completed"), DataflowStatus::Finished);
+        assert_eq!(DataflowStatus::from("something"), DataflowStatus::Unknown);
+    }
+
+    #[test]
+    fn node_status_accepts_both_backend_spellings() {
+        assert_eq!(NodeStatus::from("exited"), NodeStatus::Exited);
+        assert_eq!(NodeStatus::from("finished"), NodeStatus::Completed);
+        assert_eq!(NodeStatus::from("initializing"), NodeStatus::Initializing);
+    }
+
+    #[test]
+    fn labels_round_trip() {
+        for status in [
+            DataflowStatus::Pending,
+            DataflowStatus::Running,
+            DataflowStatus::Stopped,
+            DataflowStatus::Finished,
+            DataflowStatus::Failed,
+            DataflowStatus::Destroyed,
+        ] {
+            assert_eq!(DataflowStatus::from(status.to_string().as_str()), status);
+        }
+        for kind in [NodeKind::Custom, NodeKind::Runtime, NodeKind::Operator] {
+            assert_eq!(NodeKind::from(kind.as_str()), kind);
+        }
+    }
+
+    #[test]
+    fn dataflow_groups_are_exhaustive() {
+        assert!(DataflowStatus::Pending.is_active());
+        assert!(DataflowStatus::Finished.is_inactive());
+        assert!(DataflowStatus::Unknown.is_inactive());
+        assert!(!DataflowStatus::Failed.is_inactive());
+    }
+
+    #[test]
+    fn tones_follow_status() {
+        assert_eq!(
+            StatusTone::from(DataflowStatus::Running),
+            StatusTone::Healthy
+        );
+        assert_eq!(StatusTone::from(NodeStatus::Exited), StatusTone::Stopped);
+        assert_eq!(StatusTone::from("degraded"), StatusTone::Warning);
+    }
+}